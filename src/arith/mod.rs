@@ -6,11 +6,20 @@
 //! nonnegative representatives of their residue class. Violating this
 //! constraint causes two's complement wrapping.
 //!
+//! Functions `add_mod`, `sub_mod`, `mult_mod`, `exp_mod`, `multip_inv` and `multip_inv_batch`
+//! are the public interface to this arithmetic. They accept any operands and check the modulus.
+//!
+//! Type `Barrett` is meant for repeated multiplications modulo a fixed modulus.
+//! It precomputes an approximation of the modulus reciprocal once, after which
+//! products are reduced with multiplications and a couple of subtractions only.
+//!
 use std::cmp::{self, Ordering};
 use std::convert::{From, TryFrom};
 use std::mem;
 
-use num::{PrimInt, Signed, Unsigned};
+use num::{NumCast, PrimInt, Signed, Unsigned};
+
+use crate::UInt;
 
 pub trait CoreArith<T: PrimInt + Unsigned> {
     /// Unsafe modular addition, `x` + `y`.
@@ -41,7 +50,14 @@ pub trait CoreArith<T: PrimInt + Unsigned> {
     ///
     /// Two's complement wrapping occurs if the argument
     /// `x` is not smaller than `modu`.
+    ///
+    /// Types up to 64 bits compute the product in u128 and reduce it once,
+    /// wider types use the double-and-add method.
     fn mult_mod_unsafe(mut x: T, mut y: T, modu: T) -> T {
+        if mem::size_of::<T>() <= mem::size_of::<u64>() {
+            return Self::mult_mod_widening(x, y, modu);
+        }
+
         let mut res = T::zero();
 
         while y > T::zero() {
@@ -56,6 +72,17 @@ pub trait CoreArith<T: PrimInt + Unsigned> {
         res
     }
 
+    /// Modular multiplication in u128 for types of at most 64 bits.
+    ///
+    /// Product of such operands fits into u128, hence it's reduced only once.
+    fn mult_mod_widening(x: T, y: T, modu: T) -> T {
+        match (x.to_u128(), y.to_u128(), modu.to_u128()) {
+            // Remainder is smaller than `modu`, thus it fits back into the type T
+            (Some(x), Some(y), Some(modu)) => <T as NumCast>::from(x * y % modu).unwrap(),
+            _ => unreachable!("unsigned integers convert to u128"),
+        }
+    }
+
     /// Unsafe modular exponentation, `base` ^ `ex`.
     ///
     /// Uses directly unsafe modular multiplication.
@@ -158,7 +185,8 @@ where
     ///
     /// If the inverse `x^(-1)` exists, meaning that
     /// x * x^(-1) = 1 (mod modu) holds, it will be returned.
-    /// Otherwise the return value will be zero.
+    /// Otherwise the return value will be zero, which is ambiguous for modulo one.
+    /// Free function `multip_inv` wraps this to return None instead.
     fn multip_inv(mut x: T, modu: T) -> T {
         if x >= modu {
             x = x % modu;
@@ -199,7 +227,7 @@ where
 
         while x > T::zero() {
             while x & T::one() == T::zero() {
-                x = x.signed_shr(1);
+                x = x.unsigned_shr(1);
 
                 let par_r = n & 7.into();
                 if par_r == 3.into() || par_r == 5.into() {
@@ -240,6 +268,309 @@ where
     }
 }
 
+/// Modular addition, `x` + `y` (mod `modu`).
+///
+/// Operands can be any values of the type, they don't need to be smaller than
+/// the modulo. None is returned if the modulo is zero.
+///
+/// # Examples
+///
+/// ```
+/// use modular_equations::add_mod;
+///
+/// assert_eq!(add_mod::<u128>(u128::MAX - 1, u128::MAX - 1, u128::MAX), Some(u128::MAX - 2));
+/// assert_eq!(add_mod::<u8>(200, 100, 0), None);
+/// ```
+pub fn add_mod<T: UInt>(x: T, y: T, modu: T) -> Option<T> {
+    (modu > T::zero()).then(|| T::add_mod(x, y, modu))
+}
+
+/// Modular subtraction, `x` - `y` (mod `modu`).
+///
+/// Operands can be any values of the type and None is returned if the modulo is zero.
+///
+/// # Examples
+///
+/// ```
+/// use modular_equations::sub_mod;
+///
+/// assert_eq!(sub_mod::<u32>(3, 5, 7), Some(5));
+/// ```
+pub fn sub_mod<T: UInt>(x: T, y: T, modu: T) -> Option<T> {
+    (modu > T::zero()).then(|| T::sub_mod(x, y, modu))
+}
+
+/// Modular multiplication, `x` * `y` (mod `modu`).
+///
+/// Product is computed without overflow for every unsigned type up to u128.
+/// None is returned if the modulo is zero.
+///
+/// # Examples
+///
+/// ```
+/// use modular_equations::mult_mod;
+///
+/// let modu = u128::MAX;
+///
+/// assert_eq!(mult_mod::<u128>(modu - 1, modu - 1, modu), Some(1));
+/// ```
+pub fn mult_mod<T: UInt>(x: T, y: T, modu: T) -> Option<T> {
+    (modu > T::zero()).then(|| T::mult_mod(x, y, modu))
+}
+
+/// Modular exponentiation, `base` ^ `ex` (mod `modu`).
+///
+/// None is returned if the modulo is zero. For modulo one the result is always zero.
+///
+/// # Examples
+///
+/// ```
+/// use modular_equations::exp_mod;
+///
+/// assert_eq!(exp_mod::<u64>(3, 200, 1_000_000_007), Some(136_318_165));
+/// assert_eq!(exp_mod::<u64>(3, 0, 1), Some(0));
+/// ```
+pub fn exp_mod<T: UInt>(base: T, ex: T, modu: T) -> Option<T> {
+    match modu {
+        m if m == T::zero() => None,
+        m if m == T::one() => Some(T::zero()),
+        _ => Some(T::exp_mod(base, ex, modu)),
+    }
+}
+
+/// Modular exponentiation, `base` ^ `ex` (mod `modu`), same as `exp_mod`.
+///
+/// Provided under the name of modpow functions of other libraries. None is returned
+/// if the modulo is zero.
+///
+/// # Examples
+///
+/// ```
+/// use modular_equations::pow_mod;
+///
+/// assert_eq!(pow_mod::<u8>(2, 10, 255), Some(4));
+/// // Fermat's little theorem for prime 2^128 - 159
+/// assert_eq!(pow_mod::<u128>(3, u128::MAX - 159, u128::MAX - 158), Some(1));
+/// assert_eq!(pow_mod::<u32>(3, 2, 0), None);
+/// ```
+pub fn pow_mod<T: UInt>(base: T, ex: T, modu: T) -> Option<T> {
+    exp_mod(base, ex, modu)
+}
+
+/// Multiplicative inverse of `x` modulo `modu`.
+///
+/// Inverse exists if and only if gcd(x, modu) = 1, otherwise or if the modulo is zero
+/// None is returned. Unlike a zero sentinel, this doesn't conflate the missing inverse
+/// with the inverse zero modulo one.
+///
+/// # Examples
+///
+/// ```
+/// use modular_equations::multip_inv;
+///
+/// assert_eq!(multip_inv::<u16>(3, 7), Some(5));
+/// assert_eq!(multip_inv::<u16>(4, 6), None);
+/// assert_eq!(multip_inv::<u16>(4, 1), Some(0));
+/// ```
+pub fn multip_inv<T: UInt>(x: T, modu: T) -> Option<T> {
+    match modu {
+        m if m == T::zero() => None,
+        m if m == T::one() => Some(T::zero()),
+        _ => match T::multip_inv(x, modu) {
+            inv if inv == T::zero() => None,
+            inv => Some(inv),
+        },
+    }
+}
+
+/// Multiplicative inverses of the elements of `xs` modulo `modu`.
+///
+/// Montgomery's trick is used: products of the prefixes of `xs` are inverted by a single
+/// inversion, after which each inverse takes two multiplications. Elements without an
+/// inverse get None. If some nonzero element shares a factor with the modulo, the
+/// inverses are computed one by one instead.
+///
+/// # Examples
+///
+/// ```
+/// use modular_equations::multip_inv_batch;
+///
+/// assert_eq!(
+///     multip_inv_batch::<u32>(&[2, 3, 6, 4], 9),
+///     vec![Some(5), None, None, Some(7)]
+/// );
+/// assert_eq!(multip_inv_batch::<u32>(&[2, 3, 4], 7), vec![Some(4), Some(5), Some(2)]);
+/// ```
+pub fn multip_inv_batch<T: UInt>(xs: &[T], modu: T) -> Vec<Option<T>> {
+    if modu <= T::one() {
+        return xs.iter().map(|&x| multip_inv(x, modu)).collect();
+    }
+
+    let mut prefix_prods = Vec::with_capacity(xs.len());
+    let mut prod = T::one();
+
+    for &x in xs.iter() {
+        let x = x % modu;
+
+        if x > T::zero() {
+            prod = T::mult_mod(prod, x, modu);
+        }
+        prefix_prods.push(prod);
+    }
+
+    let mut prod_inv = T::multip_inv(prod, modu);
+
+    if prod_inv == T::zero() {
+        return xs.iter().map(|&x| multip_inv(x, modu)).collect();
+    }
+
+    let mut invs = vec![None; xs.len()];
+
+    for j in (0..xs.len()).rev() {
+        let x = xs[j] % modu;
+
+        if x == T::zero() {
+            continue;
+        }
+
+        let prefix_prod = if j > 0 { prefix_prods[j - 1] } else { T::one() };
+
+        invs[j] = Some(T::mult_mod(prod_inv, prefix_prod, modu));
+        prod_inv = T::mult_mod(prod_inv, x, modu);
+    }
+
+    invs
+}
+
+/// Barrett reduction for multiplications modulo a fixed `modu`.
+///
+/// For types up to 64 bits the product p of two residues fits into u128 and is reduced
+/// as p - q * modu, where q = floor(p * mu / 2^128) and mu = floor((2^128 - 1) / modu)
+/// is precomputed. Quotient q underestimates p / modu by at most two, hence at most two
+/// subtractions of the modulo finalize the reduction. Wider types fall back to the
+/// double-and-add method of `CoreArith`.
+#[derive(Debug, Clone, Copy)]
+pub struct Barrett<T> {
+    modu: T,
+    modu_wide: u128,
+    mu: u128,
+}
+
+impl<T> Barrett<T>
+where
+    T: PrimInt + Unsigned + CoreArith<T>,
+{
+    /// Precompute the reduction for a positive modulo `modu`.
+    pub fn new(modu: T) -> Self {
+        let modu_wide = Self::widen(modu);
+        let mu = if Self::is_narrow() {
+            u128::MAX / modu_wide
+        } else {
+            0
+        };
+
+        Self {
+            modu,
+            modu_wide,
+            mu,
+        }
+    }
+
+    pub fn modu(&self) -> T {
+        self.modu
+    }
+
+    /// Modular multiplication, `x` * `y`, for operands smaller than the modulo.
+    pub fn mult_mod(&self, x: T, y: T) -> T {
+        if !Self::is_narrow() {
+            return T::mult_mod_unsafe(x, y, self.modu);
+        }
+
+        let prod = Self::widen(x) * Self::widen(y);
+        let quot = mult_high(prod, self.mu);
+
+        // quot <= prod / modu, thus the product cannot overflow
+        let mut rem = prod - quot * self.modu_wide;
+
+        while rem >= self.modu_wide {
+            rem -= self.modu_wide;
+        }
+
+        // Remainder is smaller than `modu`, thus it fits back into the type T
+        <T as NumCast>::from(rem).unwrap()
+    }
+
+    /// Modular exponentiation, `base` ^ `ex`.
+    pub fn exp_mod(&self, mut base: T, mut ex: T) -> T {
+        let mut res = T::one() % self.modu;
+        base = base % self.modu;
+
+        while ex > T::zero() {
+            if ex & T::one() == T::one() {
+                res = self.mult_mod(res, base);
+            }
+
+            ex = ex.unsigned_shr(1);
+            base = self.mult_mod(base, base);
+        }
+
+        res
+    }
+
+    fn is_narrow() -> bool {
+        mem::size_of::<T>() <= mem::size_of::<u64>()
+    }
+
+    fn widen(x: T) -> u128 {
+        match x.to_u128() {
+            Some(x) => x,
+            None => unreachable!("unsigned integers convert to u128"),
+        }
+    }
+}
+
+/// Modular square `x` * `x` for the Mersenne modulus 2^`exp` - 1, 2 <= exp <= 127.
+///
+/// As 2^exp = 1 (mod 2^exp - 1), the 256-bit square is reduced without divisions by
+/// adding its exp-bit chunks together. Argument `x` must be smaller than the modulus.
+pub fn square_mod_mersenne(x: u128, exp: u32) -> u128 {
+    let modu = (1u128 << exp) - 1;
+    let (high, low) = (mult_high(x, x), x.wrapping_mul(x));
+
+    let fold = |mut y: u128| {
+        while y > modu {
+            y = (y & modu) + (y >> exp);
+        }
+        y
+    };
+
+    // 2^128 = 2^(128 - exp) (mod 2^exp - 1) and high is nonzero only if exp > 64,
+    // in which case high * 2^(128 - exp) < 2^exp
+    let high_folded = if high > 0 { high << (128 - exp) } else { 0 };
+    let res = fold(fold(low) + high_folded);
+
+    if res == modu {
+        0
+    } else {
+        res
+    }
+}
+
+/// High 128 bits of the 256-bit product `x` * `y`.
+fn mult_high(x: u128, y: u128) -> u128 {
+    let mask = u64::MAX as u128;
+    let (x_high, x_low) = (x >> 64, x & mask);
+    let (y_high, y_low) = (y >> 64, y & mask);
+
+    let low = x_low * y_low;
+    let mid_1 = x_low * y_high;
+    let mid_2 = x_high * y_low;
+
+    let carry = ((low >> 64) + (mid_1 & mask) + (mid_2 & mask)) >> 64;
+
+    x_high * y_high + (mid_1 >> 64) + (mid_2 >> 64) + carry
+}
+
 pub trait SignCast<S, T>
 where
     S: PrimInt + Signed,
@@ -283,6 +614,22 @@ where
 
         Some(k * modu - x_abs)
     }
+
+    /// Cast `x` from unsigned type T to signed type S as the balanced
+    /// representative of its residue class, i.e. the representative
+    /// in range (-`modu`/2, `modu`/2].
+    ///
+    /// Cast fails only if the representative doesn't fit into S, which
+    /// cannot happen for types of the same size but is possible if T is wider.
+    fn cast_to_balanced(x: T, modu: T) -> Option<S> {
+        let x = x % modu;
+
+        if x > modu.unsigned_shr(1) {
+            <S as NumCast>::from(modu - x).map(|x_abs| -x_abs)
+        } else {
+            <S as NumCast>::from(x)
+        }
+    }
 }
 
 #[cfg(test)]
@@ -1,4 +1,7 @@
-use crate::arith::{Arith, SignCast};
+use crate::arith::{
+    add_mod, exp_mod, mult_mod, multip_inv, multip_inv_batch, pow_mod, square_mod_mersenne,
+    sub_mod, Arith, Barrett, SignCast,
+};
 
 #[test]
 fn add_small_type() {
@@ -213,6 +216,88 @@ fn mult_large_max_mod() {
     }
 }
 
+#[test]
+fn mult_widening_matches_large_type() {
+    let moduli: [u64; 4] = [2, 1_000_000_007, u64::MAX - 58, u64::MAX];
+    let operands: [u64; 6] = [
+        0,
+        1,
+        12_345_678_901,
+        u32::MAX as u64 + 2,
+        u64::MAX - 1,
+        u64::MAX,
+    ];
+
+    for &modu in moduli.iter() {
+        for &x in operands.iter() {
+            for &y in operands.iter() {
+                // u128 operands are multiplied by the double-and-add method
+                let corr_res = u128::mult_mod(x.into(), y.into(), modu.into()) as u64;
+
+                assert_eq!(u64::mult_mod(x, y, modu), corr_res, "x: {}, y: {}", x, y);
+            }
+        }
+    }
+}
+
+#[test]
+fn barrett_mult_small_type() {
+    for modu in 1..=u8::MAX {
+        let barrett = Barrett::new(modu);
+
+        for x in 0..modu {
+            for y in 0..modu {
+                let corr_res = (x as u16 * y as u16 % modu as u16) as u8;
+
+                assert_eq!(barrett.mult_mod(x, y), corr_res, "x: {}, y: {}", x, y);
+            }
+        }
+    }
+}
+
+#[test]
+fn barrett_mult_mid_type() {
+    let moduli: [u64; 5] = [2, 1 << 63, 1_000_000_007, u64::MAX - 58, u64::MAX];
+
+    for &modu in moduli.iter() {
+        let barrett = Barrett::new(modu);
+        let operands = [0, 1, 2, modu / 3, modu / 2 + 1, modu - 2, modu - 1];
+
+        for &x in operands.iter().filter(|&&x| x < modu) {
+            for &y in operands.iter().filter(|&&y| y < modu) {
+                let corr_res = (x as u128 * y as u128 % modu as u128) as u64;
+
+                assert_eq!(barrett.mult_mod(x, y), corr_res, "x: {}, y: {}", x, y);
+            }
+        }
+    }
+}
+
+#[test]
+fn barrett_exp_matches_exp_mod() {
+    let moduli: [u128; 4] = [2, 1_000_000_007, u64::MAX as u128, u128::MAX];
+
+    for &modu in moduli.iter() {
+        for &base in [0, 2, 3, u64::MAX as u128, u128::MAX].iter() {
+            for &ex in [0, 1, 5, 1_000_000_006, u128::MAX].iter() {
+                let corr_res = u128::exp_mod(base, ex, modu);
+
+                assert_eq!(Barrett::new(modu).exp_mod(base, ex), corr_res);
+
+                if modu <= u64::MAX as u128 {
+                    let barrett = Barrett::new(modu as u64);
+                    let (base, ex) = ((base % modu) as u64, ex as u64);
+
+                    assert_eq!(
+                        barrett.exp_mod(base, ex) as u128,
+                        u128::exp_mod(base as u128, ex as u128, modu)
+                    );
+                }
+            }
+        }
+    }
+}
+
 #[test]
 fn exp_small_type() {
     let modu = 5;
@@ -396,11 +481,14 @@ fn jacobi_symbol_small_operands() {
 fn jacobi_symbol_large_operands() {
     let max_i128 = i128::MAX as u128;
 
-    let test_cases: [(u128, u128, i8); 4] = [
+    let test_cases: [(u128, u128, i8); 6] = [
         (1_241_942_351, 2_147_483_647, 1),
         (99, max_i128, 1),
         (max_i128 - 1, max_i128, -1),
         (max_i128, max_i128, 0),
+        // Even x with the highest bit set
+        (u128::MAX - 277, u128::MAX - 274, -1),
+        (u128::MAX - 1, u128::MAX - 158, 1),
     ];
 
     for case in test_cases.iter() {
@@ -555,3 +643,128 @@ fn sign_cast_failure_large_type() {
         None => assert!(true),
     }
 }
+
+#[test]
+fn sign_cast_balanced() {
+    // (x, modu, x_corr)
+    let test_cases: [(u8, u8, i8); 8] = [
+        (0, 7, 0),
+        (3, 7, 3),
+        (4, 7, -3),
+        (6, 7, -1),
+        (5, 10, 5),
+        (6, 10, -4),
+        (17, 10, -3),
+        (u8::MAX - 1, u8::MAX, -1),
+    ];
+
+    for (x, modu, x_corr) in test_cases.iter() {
+        assert_eq!(i8::cast_to_balanced(*x, *modu), Some(*x_corr), "x: {}", x);
+    }
+
+    assert_eq!(i8::cast_to_balanced(128, u8::MAX), Some(-127));
+    assert_eq!(
+        i128::cast_to_balanced(u128::MAX / 2 + 1, u128::MAX),
+        Some(i128::MIN + 1)
+    );
+    assert_eq!(
+        i128::cast_to_balanced(u128::MAX / 2, u128::MAX),
+        Some(i128::MAX)
+    );
+}
+
+#[test]
+fn sign_cast_wider_unsigned_type() {
+    // (x, modu, x_corr)
+    let test_cases: [(i8, u128, u128); 5] = [
+        (0, 7, 0),
+        (5, 1_000, 5),
+        (-1, u128::MAX, u128::MAX - 1),
+        (-100, 7, 5),
+        (i8::MIN + 1, 1 << 100, (1 << 100) - 127),
+    ];
+
+    for (x, modu, x_corr) in test_cases.iter() {
+        assert_eq!(i8::cast_to_unsigned(*x, *modu), Some(*x_corr), "x: {}", x);
+    }
+
+    assert!(i8::cast_to_unsigned(i8::MIN, 1u128 << 100).is_none());
+
+    assert_eq!(i32::cast_to_balanced(u64::MAX - 5, u64::MAX), Some(-5));
+    assert_eq!(i32::cast_to_balanced(1u64 << 40, u64::MAX), None);
+}
+
+#[test]
+fn free_functions_check_modulus() {
+    assert_eq!(add_mod::<u8>(u8::MAX, u8::MAX, 7), Some(6));
+    assert_eq!(sub_mod::<u8>(0, u8::MAX, 7), Some(4));
+    assert_eq!(mult_mod::<u64>(u64::MAX, u64::MAX, 1 << 63), Some(1));
+    assert_eq!(exp_mod::<u16>(2, 16, u16::MAX), Some(1));
+    assert_eq!(pow_mod::<u16>(2, 16, u16::MAX), Some(1));
+    assert_eq!(
+        multip_inv::<u128>(u128::MAX - 1, u128::MAX),
+        Some(u128::MAX - 1)
+    );
+
+    assert_eq!(add_mod::<u32>(1, 2, 0), None);
+    assert_eq!(sub_mod::<u32>(1, 2, 0), None);
+    assert_eq!(mult_mod::<u32>(1, 2, 0), None);
+    assert_eq!(exp_mod::<u32>(1, 2, 0), None);
+    assert_eq!(pow_mod::<u32>(1, 2, 0), None);
+    assert_eq!(multip_inv::<u32>(1, 0), None);
+
+    assert_eq!(exp_mod::<u32>(5, 0, 1), Some(0));
+    assert_eq!(multip_inv::<u32>(5, 1), Some(0));
+    assert_eq!(multip_inv::<u32>(6, 9), None);
+    assert_eq!(multip_inv::<u32>(0, 9), None);
+}
+
+#[test]
+fn multip_inv_batch_matches_single_inversions() {
+    let moduli: [u128; 4] = [2, 1_000_003, 3 * 5 * 7 * 11, u128::MAX];
+    let xs: Vec<u128> = (0..60)
+        .map(|j| j * j * 7_919 + u128::MAX / (j + 1))
+        .collect();
+
+    for &modu in moduli.iter() {
+        let invs = multip_inv_batch(&xs, modu);
+
+        assert_eq!(invs.len(), xs.len());
+
+        for (x, inv) in xs.iter().zip(invs.iter()) {
+            assert_eq!(*inv, multip_inv(*x, modu), "x: {}, modu: {}", x, modu);
+        }
+    }
+
+    assert_eq!(multip_inv_batch::<u8>(&[], 7), vec![]);
+    assert_eq!(multip_inv_batch::<u8>(&[0, 7, 14], 7), vec![None; 3]);
+    assert_eq!(multip_inv_batch::<u8>(&[3, 5], 0), vec![None; 2]);
+    assert_eq!(multip_inv_batch::<u8>(&[3, 5], 1), vec![Some(0); 2]);
+}
+
+#[test]
+fn square_mod_mersenne_matches_mult_mod() {
+    for exp in [2, 3, 7, 31, 61, 64, 65, 89, 127].iter() {
+        let modu = (1u128 << exp) - 1;
+        let operands = [
+            0,
+            1,
+            2,
+            modu / 3,
+            modu / 2,
+            modu / 2 + 1,
+            modu - 2,
+            modu - 1,
+        ];
+
+        for &x in operands.iter() {
+            assert_eq!(
+                square_mod_mersenne(x, *exp),
+                u128::mult_mod(x, x, modu),
+                "x: {}, exp: {}",
+                x,
+                exp
+            );
+        }
+    }
+}
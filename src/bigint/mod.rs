@@ -0,0 +1,417 @@
+//! Implements solvers for linear and quadratic equations with arbitrary-precision terms.
+//!
+//! Module is compiled only with the feature `bigint`. Types `LinEqBig` and `QuadEqBig`
+//! correspond to `LinEq` and `QuadEq` but their terms are of type `BigUint` of the
+//! `num-bigint` crate, hence moduli aren't limited to 128 bits.
+//!
+//! Linear equations are solved for every modulo. Quadratic equations are solved if the
+//! modulo fits into u128, in which case the equation is passed to `QuadEq`, or if the
+//! modulo is an odd prime. Larger composite moduli would require a factorization
+//! beyond the reach of this crate and they aren't supported.
+//!
+//! Primality of moduli larger than 128 bits is checked with the Miller-Rabin test for
+//! a fixed set of bases. This test is probabilistic for numbers of this size.
+//!
+//! With the feature `u256` equations can be constructed from the 256-bit integers `U256`
+//! of the `primitive-types` crate and their solutions converted back to `U256`. This type
+//! doesn't implement the integer traits of `num` required by `UInt`, hence such equations
+//! are solved with the arbitrary-precision types of this module.
+//!
+use std::error::Error;
+use std::fmt;
+
+use num::{Integer, One, ToPrimitive, Zero};
+use num_bigint::BigUint;
+#[cfg(feature = "u256")]
+use primitive_types::U256;
+
+use crate::{lin, QuadEq};
+
+/// Bases of the Miller-Rabin test, deterministic for numbers below 3.3 * 10^24.
+const MR_BASES: [u8; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+
+/// Errors that can occur when solving equations with arbitrary-precision terms.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BigIntError {
+    /// Modulo is composite and doesn't fit into u128.
+    CompositeModulus,
+}
+
+impl fmt::Display for BigIntError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BigIntError::CompositeModulus => {
+                write!(f, "composite modulus larger than 128 bits is not supported")
+            }
+        }
+    }
+}
+
+impl Error for BigIntError {}
+
+/// Type for linear equations with arbitrary-precision terms.
+///
+/// Linear modular equations are of the form ax + b = c (mod modu) where the modulo
+/// `modu` must be positive.
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct LinEqBig {
+    pub a: BigUint,
+    pub b: BigUint,
+    pub c: BigUint,
+    pub modu: BigUint,
+}
+
+/// Type for quadratic equations with arbitrary-precision terms.
+///
+/// Quadratic modular equations are of the form ax^2 + bx + c = d (mod modu) where the
+/// modulo `modu` must be positive.
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct QuadEqBig {
+    pub a: BigUint,
+    pub b: BigUint,
+    pub c: BigUint,
+    pub d: BigUint,
+    pub modu: BigUint,
+}
+
+impl LinEqBig {
+    /// Solve linear modular equation ax + b = c (mod modu).
+    ///
+    /// There are gcd(a, modu) solutions if gcd(a, modu) divides c - b and otherwise none.
+    /// As for `LinEq`, if a % modu == 0 every residue is a solution when b = c (mod modu)
+    /// and otherwise there are no solutions. Every residue is enumerated only for moduli up
+    /// to 2^20, for larger moduli None is returned.
+    ///
+    /// If there aren't solutions, None is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use modular_equations::LinEqBig;
+    /// use num_bigint::BigUint;
+    ///
+    /// // 3x = 1 (mod 2^200)
+    /// let lin_eq = LinEqBig {
+    ///     a: BigUint::from(3u8),
+    ///     b: BigUint::from(0u8),
+    ///     c: BigUint::from(1u8),
+    ///     modu: BigUint::from(1u8) << 200u32,
+    /// };
+    ///
+    /// match lin_eq.solve() {
+    ///     Some(x) => assert_eq!(&x[0] * 3u8 % &lin_eq.modu, BigUint::from(1u8)),
+    ///     None => panic!("3 has an inverse modulo 2^200"),
+    /// }
+    /// ```
+    pub fn solve(&self) -> Option<Vec<BigUint>> {
+        let modu = &self.modu;
+
+        if modu.is_zero() {
+            return None;
+        }
+
+        let a = &self.a % modu;
+        let c = sub_mod(&self.c, &self.b, modu);
+
+        // For a = 0 the gcd is the modulo itself and every residue is a solution if c = 0
+        let gcd = a.gcd(modu);
+
+        if !(&c % &gcd).is_zero() {
+            return None;
+        }
+
+        // Unique solution x_0 modulo m = modu / gcd, others are x_0 + k * m
+        let m = modu / &gcd;
+        let x_0 = if m.is_one() {
+            if *modu > BigUint::from(lin::ALL_RESIDUES_MAX_MODULO) {
+                return None;
+            }
+            BigUint::zero()
+        } else {
+            (&a / &gcd).modinv(&m)? * (c / &gcd) % &m
+        };
+
+        let mut sols = vec![];
+        let mut x = x_0;
+
+        while x < *modu {
+            let x_next = &x + &m;
+            sols.push(x);
+            x = x_next;
+        }
+
+        Some(sols)
+    }
+}
+
+impl QuadEqBig {
+    /// Solve quadratic modular equation ax^2 + bx + c = d (mod modu).
+    ///
+    /// Modulo must either fit into u128 or be an odd prime. For an odd prime modulo the
+    /// solutions are (-b ± sqrt(b^2 - 4a(c - d))) / 2a, square root computed by the
+    /// Tonelli-Shanks algorithm. If a % modu == 0, the equation is solved as the linear
+    /// equation bx + c = d (mod modu).
+    ///
+    /// If the modulo is composite and larger than 128 bits, `BigIntError::CompositeModulus`
+    /// is returned. Otherwise None is returned inside `Ok` if there aren't solutions.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use modular_equations::QuadEqBig;
+    /// use num_bigint::BigUint;
+    ///
+    /// // x^2 = 4 (mod 2^521 - 1), modulo being a Mersenne prime
+    /// let modu = (BigUint::from(1u8) << 521u32) - 1u8;
+    /// let quad_eq = QuadEqBig {
+    ///     a: BigUint::from(1u8),
+    ///     b: BigUint::from(0u8),
+    ///     c: BigUint::from(0u8),
+    ///     d: BigUint::from(4u8),
+    ///     modu: modu.clone(),
+    /// };
+    ///
+    /// assert_eq!(quad_eq.solve(), Ok(Some(vec![BigUint::from(2u8), modu - 2u8])));
+    /// ```
+    pub fn solve(&self) -> Result<Option<Vec<BigUint>>, BigIntError> {
+        let modu = &self.modu;
+
+        if modu.is_zero() {
+            return Ok(None);
+        }
+
+        if let Some(quad_eq) = self.to_u128() {
+            return Ok(quad_eq
+                .solve()
+                .map(|sols| sols.into_iter().map(BigUint::from).collect()));
+        }
+
+        if !is_probable_prime(modu) {
+            return Err(BigIntError::CompositeModulus);
+        }
+
+        Ok(self.solve_odd_prime_mod())
+    }
+
+    fn solve_odd_prime_mod(&self) -> Option<Vec<BigUint>> {
+        let modu = &self.modu;
+
+        let a = &self.a % modu;
+        let b = &self.b % modu;
+        let c = sub_mod(&self.c, &self.d, modu);
+
+        if a.is_zero() {
+            let lin_eq = LinEqBig {
+                a: b,
+                b: c,
+                c: BigUint::zero(),
+                modu: modu.clone(),
+            };
+            return lin_eq.solve();
+        }
+
+        // Discriminant b^2 - 4ac
+        let discr = sub_mod(&(&b * &b), &(&a * &c * 4u8), modu);
+        let a_double_inv = (&a << 1u8).modinv(modu)?;
+
+        if discr.is_zero() {
+            return Some(vec![
+                sub_mod(&BigUint::zero(), &b, modu) * a_double_inv % modu,
+            ]);
+        }
+
+        let discr_sqrt = tonelli_shanks(&discr, modu)?;
+
+        let mut sols = vec![
+            sub_mod(&discr_sqrt, &b, modu) * &a_double_inv % modu,
+            sub_mod(&(modu - &discr_sqrt), &b, modu) * &a_double_inv % modu,
+        ];
+        sols.sort_unstable();
+
+        Some(sols)
+    }
+
+    fn to_u128(&self) -> Option<QuadEq<u128>> {
+        let modu = self.modu.to_u128()?;
+
+        Some(QuadEq {
+            a: (&self.a % &self.modu).to_u128()?,
+            b: (&self.b % &self.modu).to_u128()?,
+            c: (&self.c % &self.modu).to_u128()?,
+            d: (&self.d % &self.modu).to_u128()?,
+            modu,
+        })
+    }
+}
+
+#[cfg(feature = "u256")]
+impl LinEqBig {
+    /// Construct linear equation ax + b = c (mod modu) from 256-bit terms.
+    pub fn from_u256(a: U256, b: U256, c: U256, modu: U256) -> Self {
+        LinEqBig {
+            a: u256_to_big(a),
+            b: u256_to_big(b),
+            c: u256_to_big(c),
+            modu: u256_to_big(modu),
+        }
+    }
+}
+
+#[cfg(feature = "u256")]
+impl QuadEqBig {
+    /// Construct quadratic equation ax^2 + bx + c = d (mod modu) from 256-bit terms.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use modular_equations::{solutions_to_u256, QuadEqBig};
+    /// use primitive_types::U256;
+    ///
+    /// // x^2 = 9 (mod 2^255 - 19)
+    /// let modu = (U256::one() << 255) - 19;
+    /// let quad_eq = QuadEqBig::from_u256(1.into(), 0.into(), 0.into(), 9.into(), modu);
+    ///
+    /// let x = solutions_to_u256(&quad_eq.solve().unwrap().unwrap());
+    ///
+    /// assert_eq!(x, vec![U256::from(3), modu - 3]);
+    /// ```
+    pub fn from_u256(a: U256, b: U256, c: U256, d: U256, modu: U256) -> Self {
+        QuadEqBig {
+            a: u256_to_big(a),
+            b: u256_to_big(b),
+            c: u256_to_big(c),
+            d: u256_to_big(d),
+            modu: u256_to_big(modu),
+        }
+    }
+}
+
+/// Convert solutions `sols` to 256-bit integers.
+///
+/// Solutions of equations constructed from 256-bit terms are smaller than their
+/// modulo and always fit. Larger values are truncated to their lowest 256 bits.
+#[cfg(feature = "u256")]
+pub fn solutions_to_u256(sols: &[BigUint]) -> Vec<U256> {
+    sols.iter()
+        .map(|x| {
+            let mut bytes = x.to_bytes_le();
+            bytes.truncate(32);
+
+            U256::from_little_endian(&bytes)
+        })
+        .collect()
+}
+
+#[cfg(feature = "u256")]
+fn u256_to_big(x: U256) -> BigUint {
+    let mut bytes = [0; 32];
+    x.to_little_endian(&mut bytes);
+
+    BigUint::from_bytes_le(&bytes)
+}
+
+/// Modular subtraction, `x` - `y`.
+fn sub_mod(x: &BigUint, y: &BigUint, modu: &BigUint) -> BigUint {
+    let (x, y) = (x % modu, y % modu);
+
+    if x >= y {
+        x - y
+    } else {
+        modu - (y - x)
+    }
+}
+
+/// Check whether `num` is a prime, probabilistically for large numbers.
+pub fn is_probable_prime(num: &BigUint) -> bool {
+    if *num < BigUint::from(2u8) {
+        return false;
+    }
+
+    for &prm in MR_BASES.iter() {
+        let prm = BigUint::from(prm);
+
+        if *num == prm {
+            return true;
+        }
+        if (num % &prm).is_zero() {
+            return false;
+        }
+    }
+
+    let num_even = num - 1u8;
+    // num_even > 0, hence it has a lowest set bit
+    let pow = num_even.trailing_zeros().unwrap_or(0);
+    let num_odd = &num_even >> pow;
+
+    'base: for &base in MR_BASES.iter() {
+        let mut q = BigUint::from(base).modpow(&num_odd, num);
+
+        if q.is_one() || q == num_even {
+            continue;
+        }
+
+        for _ in 1..pow {
+            q = &q * &q % num;
+
+            if q == num_even {
+                continue 'base;
+            }
+        }
+
+        return false;
+    }
+
+    true
+}
+
+/// Square root of a quadratic residue `q` modulo an odd prime `prm`.
+///
+/// If `q` isn't a quadratic residue, None is returned.
+fn tonelli_shanks(q: &BigUint, prm: &BigUint) -> Option<BigUint> {
+    let prm_even = prm - 1u8;
+    let euler_exp = &prm_even >> 1u8;
+
+    if q.modpow(&euler_exp, prm) != BigUint::one() {
+        return None;
+    }
+
+    // prm - 1 = 2^s * odd
+    let s = prm_even.trailing_zeros().unwrap_or(0);
+    let odd = &prm_even >> s;
+
+    let mut z = BigUint::from(2u8);
+
+    while z.modpow(&euler_exp, prm) != prm_even {
+        z += 1u8;
+    }
+
+    let mut m = s;
+    let mut c = z.modpow(&odd, prm);
+    let mut t = q.modpow(&odd, prm);
+    let mut r = q.modpow(&((&odd + 1u8) >> 1u8), prm);
+
+    while !t.is_one() {
+        // Smallest i, 0 < i < m, s.t. t^(2^i) = 1
+        let mut i = 0;
+        let mut t_pow = t.clone();
+
+        while !t_pow.is_one() {
+            t_pow = &t_pow * &t_pow % prm;
+            i += 1;
+        }
+
+        let b = c.modpow(&(BigUint::one() << (m - i - 1)), prm);
+
+        m = i;
+        c = &b * &b % prm;
+        t = t * &c % prm;
+        r = r * b % prm;
+    }
+
+    Some(r)
+}
+
+#[cfg(test)]
+mod tests;
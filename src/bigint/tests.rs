@@ -0,0 +1,278 @@
+use num::{One, Zero};
+use num_bigint::BigUint;
+#[cfg(feature = "u256")]
+use primitive_types::U256;
+
+#[cfg(feature = "u256")]
+use crate::bigint::solutions_to_u256;
+use crate::bigint::{is_probable_prime, BigIntError, LinEqBig, QuadEqBig};
+use crate::{LinEq, QuadEq};
+
+fn big(x: u128) -> BigUint {
+    BigUint::from(x)
+}
+
+fn mersenne(k: usize) -> BigUint {
+    (BigUint::one() << k) - 1u8
+}
+
+fn evaluate_quad(quad_eq: &QuadEqBig, x: &BigUint) -> BigUint {
+    (&quad_eq.a * x * x + &quad_eq.b * x + &quad_eq.c) % &quad_eq.modu
+}
+
+#[test]
+fn lin_eq_matches_small_type() {
+    for modu in 2..40u32 {
+        for a in 0..modu {
+            for c in [0, 1, 6, 12].iter() {
+                let lin_eq = LinEq {
+                    a,
+                    b: 3,
+                    c: *c,
+                    modu,
+                };
+                let lin_eq_big = LinEqBig {
+                    a: big(a.into()),
+                    b: big(3),
+                    c: big((*c).into()),
+                    modu: big(modu.into()),
+                };
+
+                let corr_sols = lin_eq
+                    .solve()
+                    .map(|sols| sols.into_iter().map(|x| big(x.into())).collect());
+
+                assert_eq!(
+                    lin_eq_big.solve(),
+                    corr_sols,
+                    "a: {}, c: {}, modu: {}",
+                    a,
+                    c,
+                    modu
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn lin_eq_large_modulo() {
+    // 6x + 1 = 5 (mod 2^300), solutions are odd multiples of 2^299 shifted by x_0
+    let modu = BigUint::one() << 300u32;
+    let lin_eq = LinEqBig {
+        a: big(6),
+        b: big(1),
+        c: big(5),
+        modu: modu.clone(),
+    };
+
+    match lin_eq.solve() {
+        Some(sols) => {
+            assert_eq!(sols.len(), 2);
+
+            for x in sols.iter() {
+                assert_eq!((x * 6u8 + 1u8) % &modu, big(5));
+            }
+        }
+        None => panic!("no solutions"),
+    }
+
+    let lin_eq = LinEqBig {
+        a: big(6),
+        b: big(0),
+        c: big(1),
+        modu,
+    };
+    assert_eq!(lin_eq.solve(), None);
+}
+
+#[test]
+fn modulo_one() {
+    let lin_eq = LinEqBig {
+        a: big(6),
+        b: big(1),
+        c: big(5),
+        modu: big(1),
+    };
+    assert_eq!(lin_eq.solve(), Some(vec![big(0)]));
+
+    let quad_eq = QuadEqBig {
+        a: big(1),
+        b: big(0),
+        c: big(0),
+        d: big(2),
+        modu: big(1),
+    };
+    assert_eq!(quad_eq.solve(), Ok(Some(vec![big(0)])));
+
+    let lin_eq = LinEqBig {
+        a: big(6),
+        b: big(1),
+        c: big(5),
+        modu: big(0),
+    };
+    assert_eq!(lin_eq.solve(), None);
+}
+
+#[test]
+fn quad_eq_matches_small_type() {
+    let test_cases: [[u128; 5]; 4] = [
+        [1, 1, 1, 21, 22],
+        [3, 0, 0, 12, 1 << 64],
+        [1, 0, 0, 1, u128::MAX],
+        [0, 4, 0, 2, 1_000_000_007],
+    ];
+
+    for test in test_cases.iter() {
+        let quad_eq = QuadEq {
+            a: test[0],
+            b: test[1],
+            c: test[2],
+            d: test[3],
+            modu: test[4],
+        };
+        let quad_eq_big = QuadEqBig {
+            a: big(test[0]),
+            b: big(test[1]),
+            c: big(test[2]),
+            d: big(test[3]),
+            modu: big(test[4]),
+        };
+
+        let corr_sols = quad_eq
+            .solve()
+            .map(|sols| sols.into_iter().map(big).collect());
+
+        assert_eq!(quad_eq_big.solve(), Ok(corr_sols), "test: {:?}", test);
+    }
+}
+
+#[test]
+fn quad_eq_large_prime_modulo() {
+    // 2^521 - 1 and 2^255 - 19 are primes
+    let moduli = [mersenne(521), (BigUint::one() << 255u32) - 19u8];
+
+    for modu in moduli.iter() {
+        // (x - r_1)(x - r_2) = x^2 - (r_1 + r_2)x + r_1 * r_2
+        let r_1 = modu / 3u8;
+        let r_2 = modu - 12_345u32;
+
+        let quad_eq = QuadEqBig {
+            a: big(5),
+            b: modu - (&r_1 + &r_2) * 5u8 % modu,
+            c: &r_1 * &r_2 * 5u8 % modu,
+            d: BigUint::zero(),
+            modu: modu.clone(),
+        };
+
+        assert_eq!(quad_eq.solve(), Ok(Some(vec![r_1.clone(), r_2.clone()])));
+
+        for x in [&r_1, &r_2].iter() {
+            assert_eq!(evaluate_quad(&quad_eq, x), BigUint::zero());
+        }
+    }
+}
+
+#[test]
+fn quad_eq_large_prime_modulo_special_cases() {
+    let modu = (BigUint::one() << 255u32) - 19u8;
+
+    // 2 is not a quadratic residue as modu = 5 (mod 8)
+    let quad_eq = QuadEqBig {
+        a: big(1),
+        b: big(0),
+        c: big(0),
+        d: big(2),
+        modu: modu.clone(),
+    };
+    assert_eq!(quad_eq.solve(), Ok(None));
+
+    // (x + 1)^2 = 0 has a double root
+    let quad_eq = QuadEqBig {
+        a: big(1),
+        b: big(2),
+        c: big(1),
+        d: big(0),
+        modu: modu.clone(),
+    };
+    assert_eq!(quad_eq.solve(), Ok(Some(vec![&modu - 1u8])));
+
+    // Linear equation 2x + 1 = 0
+    let quad_eq = QuadEqBig {
+        a: modu.clone(),
+        b: big(2),
+        c: big(1),
+        d: big(0),
+        modu: modu.clone(),
+    };
+    assert_eq!(quad_eq.solve(), Ok(Some(vec![&modu >> 1u8])));
+}
+
+#[test]
+fn quad_eq_large_composite_modulo() {
+    let modu = mersenne(521) * mersenne(127);
+
+    let quad_eq = QuadEqBig {
+        a: big(1),
+        b: big(0),
+        c: big(0),
+        d: big(1),
+        modu,
+    };
+
+    assert_eq!(quad_eq.solve(), Err(BigIntError::CompositeModulus));
+}
+
+#[test]
+fn probable_primes() {
+    let primes = [
+        big(2),
+        big(37),
+        big(1_000_000_007),
+        mersenne(127),
+        mersenne(521),
+    ];
+
+    for prm in primes.iter() {
+        assert!(is_probable_prime(prm), "{}", prm);
+    }
+
+    let composites = [
+        big(0),
+        big(1),
+        big(561),
+        big(3_215_031_751),
+        mersenne(127) * mersenne(89),
+        mersenne(523),
+    ];
+
+    for num in composites.iter() {
+        assert!(!is_probable_prime(num), "{}", num);
+    }
+}
+
+#[cfg(feature = "u256")]
+#[test]
+fn equations_from_u256() {
+    let modu = U256::MAX;
+
+    // 2x + 3 = 1 (mod 2^256 - 1), x = -1
+    let lin_eq = LinEqBig::from_u256(2.into(), 3.into(), 1.into(), modu);
+    assert_eq!(
+        lin_eq.solve().map(|sols| solutions_to_u256(&sols)),
+        Some(vec![modu - 1])
+    );
+
+    // x^2 + x = 2 (mod 2^255 - 19), roots 1 and -2
+    let modu = (U256::one() << 255) - 19;
+    let quad_eq = QuadEqBig::from_u256(1.into(), 1.into(), 0.into(), 2.into(), modu);
+
+    match quad_eq.solve() {
+        Ok(Some(sols)) => assert_eq!(solutions_to_u256(&sols), vec![U256::one(), modu - 2]),
+        _ => panic!("no solutions"),
+    }
+
+    // Composite modulo of 256 bits cannot be factorized
+    let quad_eq = QuadEqBig::from_u256(1.into(), 0.into(), 0.into(), 1.into(), U256::MAX);
+    assert_eq!(quad_eq.solve(), Err(BigIntError::CompositeModulus));
+}
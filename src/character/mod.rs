@@ -0,0 +1,358 @@
+//! Implements Dirichlet characters modulo n.
+//!
+//! Dirichlet character χ modulo n is a completely multiplicative function on the
+//! integers s.t. χ(a) depends only on the residue class of a, χ(a) = 0 whenever
+//! gcd(a, n) > 1 and otherwise χ(a) is a complex root of unity. Characters modulo
+//! n are precisely the group homomorphisms from the unit group (Z/nZ)* to the
+//! complex unit circle.
+//!
+//! Unit group (Z/nZ)* decomposes to a product of cyclic groups <g_1> x ... x <g_m>
+//! with orders o_1, ..., o_m and a character is determined by its values at the
+//! generators, χ(g_j) = exp(2πi * e_j / o_j) for some index e_j in \[0, o_j).
+//! Evaluating a character at a unit a thus requires discrete logarithms of a
+//! in every cyclic component.
+//!
+//! Values are kept exact by representing χ(a) as an exponent k of the root of
+//! unity exp(2πi / L), L being the exponent of the unit group (Carmichael's
+//! function of n). Complex values are available for convenience.
+//!
+//! Quadratic characters, taking only values -1, 0 and 1, can also be evaluated
+//! directly with the Kronecker symbol (a|n) which extends the Jacobi symbol
+//! to even n.
+//!
+//! Gauss sum of a character χ is the sum of χ(x) * exp(2πi * ax / n) over the
+//! residues x modulo n. For the quadratic character modulo an odd prime p this equals
+//! the quadratic Gauss sum g(a; p), the sum of exp(2πi * ax^2 / p), which has the
+//! closed form (a|p) * sqrt(p) if p = 1 (mod 4) and (a|p) * i * sqrt(p) if p = 3 (mod 4).
+//!
+use std::f64::consts::PI;
+
+use num::{complex::Complex64, iter};
+
+use crate::{
+    arith::{Arith, CoreArith},
+    dlog::baby_step_giant_step,
+    prime::is_odd_prime,
+    ring::{lift_generator, unit_group_components, CyclicComponent},
+    UInt,
+};
+
+/// Type for Dirichlet characters modulo `modu`.
+///
+/// Character is constructed from its indices e_j, one for each cyclic component
+/// of the unit group. Components are ordered as given by method `group_structure`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DirichletCharacter<T: UInt> {
+    modu: T,
+    components: Vec<CyclicComponent<T>>,
+    indices: Vec<T>,
+    value_order: T,
+}
+
+impl<T: 'static + UInt> DirichletCharacter<T> {
+    /// Create a Dirichlet character modulo `modu` with character `indices`.
+    ///
+    /// Index e_j sets the value at j'th generator to exp(2πi * e_j / o_j), where o_j is
+    /// the order of the generator. Count of indices must match the count of cyclic
+    /// components and modulo must be strictly larger than one, otherwise None is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use modular_equations::DirichletCharacter;
+    ///
+    /// // Unit group modulo 7 is cyclic of order 6, generated by 3
+    /// assert_eq!(DirichletCharacter::<u32>::group_structure(7), Some(vec![(3, 6)]));
+    ///
+    /// // Quadratic character, i.e. the Legendre symbol modulo 7
+    /// let chi = DirichletCharacter::<u32>::new(7, &[3]).unwrap();
+    ///
+    /// assert_eq!(chi.value_order(), 6);
+    /// // chi(3) = exp(2πi * 3 / 6) = -1
+    /// assert_eq!(chi.value(3), Some(3));
+    /// assert_eq!(chi.value(2), Some(0));
+    /// assert_eq!(chi.value(14), None);
+    /// ```
+    pub fn new(modu: T, indices: &[T]) -> Option<Self> {
+        if modu <= T::one() {
+            return None;
+        }
+
+        let components = unit_group_components(modu);
+
+        if components.len() != indices.len() {
+            return None;
+        }
+
+        let value_order = components.iter().fold(T::one(), |lcm, comp| {
+            lcm / T::gcd_mod(lcm, comp.order) * comp.order
+        });
+
+        let indices = components
+            .iter()
+            .zip(indices.iter())
+            .map(|(comp, &e)| e % comp.order)
+            .collect();
+
+        Some(Self {
+            modu,
+            components,
+            indices,
+            value_order,
+        })
+    }
+
+    /// Principal character modulo `modu`, taking value one at every unit.
+    pub fn principal(modu: T) -> Option<Self> {
+        if modu <= T::one() {
+            return None;
+        }
+
+        let count = unit_group_components(modu).len();
+
+        Self::new(modu, &vec![T::zero(); count])
+    }
+
+    /// Cyclic decomposition of the unit group (Z/`modu`Z)*.
+    ///
+    /// Returns a pair (g_j, o_j) for every cyclic component, where g_j is
+    /// the generator of the component lifted to the residue class modulo
+    /// `modu` (being one in every other component) and o_j is its order.
+    pub fn group_structure(modu: T) -> Option<Vec<(T, T)>> {
+        if modu <= T::one() {
+            return None;
+        }
+
+        let structure = unit_group_components(modu)
+            .iter()
+            .map(|comp| (lift_generator(comp, modu), comp.order))
+            .collect();
+
+        Some(structure)
+    }
+
+    /// Modulo of the character.
+    pub fn modu(&self) -> T {
+        self.modu
+    }
+
+    /// Order L of the root of unity exp(2πi / L) in which values are expressed.
+    pub fn value_order(&self) -> T {
+        self.value_order
+    }
+
+    /// Check whether the character is the principal character.
+    pub fn is_principal(&self) -> bool {
+        self.indices.iter().all(|&e| e == T::zero())
+    }
+
+    /// Value of the character at `a` as an exponent k in \[0, L).
+    ///
+    /// Value of the character is then exp(2πi * k / L), where L is given by the
+    /// method `value_order`. If `a` is not coprime to the modulo, the character
+    /// value is zero and None is returned.
+    pub fn value(&self, a: T) -> Option<T> {
+        if T::gcd_mod(a % self.modu, self.modu) != T::one() {
+            return None;
+        }
+
+        let mut k = T::zero();
+
+        for (comp, &e) in self.components.iter().zip(self.indices.iter()) {
+            if e == T::zero() {
+                continue;
+            }
+
+            let x = component_log(comp, a % comp.prm_power)?;
+
+            let scale = self.value_order / comp.order;
+            let term = T::mult_mod(T::mult_mod(e, x, self.value_order), scale, self.value_order);
+
+            k = T::add_mod_unsafe(k, term, self.value_order);
+        }
+
+        Some(k)
+    }
+
+    /// Value of the character at `a` as a complex number.
+    pub fn value_complex(&self, a: T) -> Complex64 {
+        match self.value(a) {
+            Some(k) => {
+                let (k, order): (u128, u128) = (k.into(), self.value_order.into());
+
+                Complex64::from_polar(1.0, 2.0 * PI * (k as f64) / (order as f64))
+            }
+            None => Complex64::new(0.0, 0.0),
+        }
+    }
+
+    /// Gauss sum of the character, the sum of χ(x) * exp(2πi * `a`x / n) over x modulo n.
+    ///
+    /// Sum is evaluated numerically term by term and thus requires time linear
+    /// in the modulo. For a primitive character χ and `a` coprime to n the
+    /// absolute value of the sum is sqrt(n).
+    pub fn gauss_sum(&self, a: T) -> Complex64 {
+        let modu: u128 = self.modu.into();
+        let a = a % self.modu;
+
+        iter::range(T::one(), self.modu)
+            .map(|x| {
+                let ax: u128 = T::mult_mod(a, x, self.modu).into();
+                let angle = 2.0 * PI * (ax as f64) / (modu as f64);
+
+                self.value_complex(x) * Complex64::from_polar(1.0, angle)
+            })
+            .sum()
+    }
+}
+
+/// Closed form of the quadratic Gauss sum g(a; p).
+///
+/// Value of the sum is `sign` * sqrt(`modu`), multiplied further by
+/// the imaginary unit if `imaginary` is true.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GaussSum<T: UInt> {
+    pub sign: i8,
+    pub imaginary: bool,
+    pub modu: T,
+}
+
+impl<T: UInt> GaussSum<T> {
+    /// Value of the Gauss sum as a complex number.
+    pub fn to_complex(&self) -> Complex64 {
+        let modu: u128 = self.modu.into();
+        let magnitude = f64::from(self.sign) * (modu as f64).sqrt();
+
+        if self.imaginary {
+            Complex64::new(0.0, magnitude)
+        } else {
+            Complex64::new(magnitude, 0.0)
+        }
+    }
+}
+
+/// Quadratic Gauss sum g(`a`; `prm`) in exact form, `prm` being an odd prime.
+///
+/// Sum is determined by the Legendre symbol (a|p) and the residue of p modulo 4.
+/// If `prm` is not an odd prime or `a` is divisible by it (the sum degenerating
+/// to p), None is returned.
+///
+/// # Examples
+///
+/// ```
+/// use modular_equations::{gauss_sum, GaussSum};
+///
+/// // 2 is a quadratic non-residue modulo 7 and 7 = 3 (mod 4)
+/// assert_eq!(
+///     gauss_sum::<u32>(2, 7),
+///     Some(GaussSum { sign: 1, imaginary: true, modu: 7 })
+/// );
+/// assert_eq!(
+///     gauss_sum::<u32>(3, 7),
+///     Some(GaussSum { sign: -1, imaginary: true, modu: 7 })
+/// );
+/// ```
+pub fn gauss_sum<T: UInt>(a: T, prm: T) -> Option<GaussSum<T>> {
+    if !is_odd_prime(prm) || a % prm == T::zero() {
+        return None;
+    }
+
+    Some(GaussSum {
+        sign: T::jacobi_symbol(a % prm, prm),
+        imaginary: prm & 3.into() == 3.into(),
+        modu: prm,
+    })
+}
+
+/// Legendre symbol (a|p) for an odd prime `prm`.
+///
+/// Symbol is computed by Euler's criterion a^((p-1)/2) = (a|p) (mod p), the same test
+/// the quadratic solver uses to decide quadratic residuosity. Return value is 0 if p
+/// divides a, 1 if a is a quadratic residue and -1 otherwise. None is returned if `prm`
+/// isn't an odd prime.
+///
+/// # Examples
+///
+/// ```
+/// use modular_equations::legendre;
+///
+/// assert_eq!(legendre::<u64>(2, 1_000_000_007), Some(1));
+/// assert_eq!(legendre::<u64>(5, 1_000_000_007), Some(-1));
+/// assert_eq!(legendre::<u64>(5, 15), None);
+/// ```
+pub fn legendre<T: UInt>(a: T, prm: T) -> Option<i8> {
+    if !is_odd_prime(prm) {
+        return None;
+    }
+
+    match T::exp_mod(a, (prm - T::one()) / 2.into(), prm) {
+        r if r == T::zero() => Some(0),
+        r if r == T::one() => Some(1),
+        _ => Some(-1),
+    }
+}
+
+/// Kronecker symbol (a|n), the quadratic character extending the Jacobi symbol.
+///
+/// For odd n this equals the Jacobi symbol. Factors of two in n contribute
+/// (a|2), which is zero for even a, one if a = 1, 7 (mod 8) and minus one
+/// if a = 3, 5 (mod 8). Return value will be one of -1, 0 or 1.
+///
+/// # Examples
+///
+/// ```
+/// use modular_equations::kronecker_symbol;
+///
+/// assert_eq!(kronecker_symbol::<u32>(2, 7), 1);
+/// assert_eq!(kronecker_symbol::<u32>(3, 8), -1);
+/// assert_eq!(kronecker_symbol::<u32>(6, 10), 0);
+/// ```
+pub fn kronecker_symbol<T: UInt>(a: T, n: T) -> i8 {
+    if n == T::zero() {
+        return if a == T::one() { 1 } else { 0 };
+    }
+
+    let pow = n.trailing_zeros();
+    let n_odd = n.unsigned_shr(pow);
+
+    let mut symbol = 1;
+
+    if pow > 0 {
+        if a & T::one() == T::zero() {
+            return 0;
+        }
+
+        let a_mod_eight = a & 7.into();
+
+        if pow & 1 == 1 && (a_mod_eight == 3.into() || a_mod_eight == 5.into()) {
+            symbol = -1;
+        }
+    }
+
+    if n_odd == T::one() {
+        symbol
+    } else {
+        symbol * T::jacobi_symbol(a, n_odd)
+    }
+}
+
+/// Discrete logarithm of a unit `a` (mod `prm_power`) in the cyclic component.
+fn component_log<T: UInt>(comp: &CyclicComponent<T>, a: T) -> Option<T> {
+    if comp.prm != 2.into() {
+        return baby_step_giant_step(comp.generator, a, comp.order, comp.prm_power);
+    }
+
+    // For 2^k, a = (-1)^s * 5^t (mod 2^k) and s is determined by a (mod 4)
+    let s_is_zero = a & 3.into() == T::one();
+
+    if comp.generator == comp.prm_power - T::one() {
+        return Some(if s_is_zero { T::zero() } else { T::one() });
+    }
+
+    let b = if s_is_zero { a } else { comp.prm_power - a };
+
+    baby_step_giant_step(comp.generator, b, comp.order, comp.prm_power)
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,321 @@
+use crate::arith::Arith;
+use crate::character::{gauss_sum, kronecker_symbol, legendre, DirichletCharacter, GaussSum};
+use crate::utils;
+
+use num::complex::Complex64;
+use std::f64::consts::PI;
+
+fn all_characters(modu: u32) -> Vec<DirichletCharacter<u32>> {
+    let orders: Vec<usize> = DirichletCharacter::group_structure(modu)
+        .unwrap()
+        .iter()
+        .map(|&(_, order)| order as usize)
+        .collect();
+
+    if orders.is_empty() {
+        return vec![DirichletCharacter::principal(modu).unwrap()];
+    }
+
+    utils::make_index_combinations(&orders)
+        .unwrap()
+        .iter()
+        .map(|comb| {
+            let indices: Vec<u32> = comb.iter().map(|&e| e as u32).collect();
+            DirichletCharacter::new(modu, &indices).unwrap()
+        })
+        .collect()
+}
+
+#[test]
+fn group_structure_small_moduli() {
+    let test_cases: [(u32, &[(u32, u32)]); 6] = [
+        (2, &[]),
+        (4, &[(3, 2)]),
+        (7, &[(3, 6)]),
+        (9, &[(2, 6)]),
+        (16, &[(15, 2), (5, 4)]),
+        (15, &[(11, 2), (7, 4)]),
+    ];
+
+    for (modu, corr_structure) in test_cases.iter() {
+        assert_eq!(
+            DirichletCharacter::group_structure(*modu),
+            Some(corr_structure.to_vec()),
+            "modu: {}",
+            modu
+        );
+    }
+}
+
+#[test]
+fn group_structure_generators_have_correct_orders() {
+    for modu in 2..300u32 {
+        for (g, order) in DirichletCharacter::group_structure(modu).unwrap() {
+            assert_eq!(u32::exp_mod(g, order, modu), 1, "modu: {}, g: {}", modu, g);
+
+            for d in 1..order {
+                if order % d == 0 {
+                    assert_ne!(u32::exp_mod(g, d, modu), 1, "modu: {}, g: {}", modu, g);
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn character_invalid_arguments() {
+    assert_eq!(DirichletCharacter::<u32>::new(1, &[]), None);
+    assert_eq!(DirichletCharacter::<u32>::new(15, &[1]), None);
+    assert_eq!(DirichletCharacter::<u32>::principal(0), None);
+    assert_eq!(DirichletCharacter::<u32>::group_structure(1), None);
+}
+
+#[test]
+fn principal_character() {
+    let modu = 360u32;
+    let chi = DirichletCharacter::principal(modu).unwrap();
+
+    assert!(chi.is_principal());
+
+    for a in 0..modu {
+        if u32::gcd_mod(a, modu) == 1 {
+            assert_eq!(chi.value(a), Some(0), "a: {}", a);
+        } else {
+            assert_eq!(chi.value(a), None, "a: {}", a);
+        }
+    }
+}
+
+#[test]
+fn quadratic_character_matches_legendre_symbol() {
+    for &prm in [3u32, 5, 7, 11, 101, 1_009].iter() {
+        let order = prm - 1;
+        let chi = DirichletCharacter::new(prm, &[order / 2]).unwrap();
+
+        for a in 1..prm {
+            let corr_value = if u32::jacobi_symbol(a, prm) == 1 {
+                0
+            } else {
+                order / 2
+            };
+
+            assert_eq!(chi.value(a), Some(corr_value), "prm: {}, a: {}", prm, a);
+        }
+    }
+}
+
+#[test]
+fn characters_are_completely_multiplicative() {
+    for &modu in [16u32, 24, 45, 63].iter() {
+        for chi in all_characters(modu).iter() {
+            let order = chi.value_order();
+
+            for a in 1..modu {
+                for b in 1..modu {
+                    let value_ab = chi.value(a * b);
+
+                    match (chi.value(a), chi.value(b)) {
+                        (Some(x), Some(y)) => assert_eq!(value_ab, Some((x + y) % order)),
+                        _ => assert_eq!(value_ab, None),
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn characters_orthogonality() {
+    for &modu in [12u32, 20, 27, 32].iter() {
+        let characters = all_characters(modu);
+        let unit_count = characters.len() as f64;
+
+        for a in 1..modu {
+            let sum: f64 = characters.iter().map(|chi| chi.value_complex(a).re).sum();
+
+            let corr_sum = if a == 1 { unit_count } else { 0.0 };
+
+            assert!((sum - corr_sum).abs() < 1e-9, "modu: {}, a: {}", modu, a);
+        }
+    }
+}
+
+#[test]
+fn character_large_type() {
+    let modu: u64 = 1_000_000_007;
+    let chi = DirichletCharacter::new(modu, &[1]).unwrap();
+
+    // 5 is a primitive root modulo 1e9+7
+    assert_eq!(chi.value(5), Some(1));
+    assert_eq!(chi.value(25), Some(2));
+    assert_eq!(chi.value(modu - 1), Some((modu - 1) / 2));
+}
+
+#[test]
+fn kronecker_symbol_small_type() {
+    // [a, n, (a|n) + 1]
+    let test_cases: [[u8; 3]; 10] = [
+        [1, 0, 2],
+        [2, 0, 1],
+        [3, 1, 2],
+        [3, 2, 0],
+        [7, 2, 2],
+        [5, 4, 2],
+        [5, 8, 0],
+        [2, 7, 2],
+        [6, 10, 1],
+        [11, 30, 2],
+    ];
+
+    for test in test_cases.iter() {
+        assert_eq!(
+            kronecker_symbol(test[0], test[1]),
+            test[2] as i8 - 1,
+            "a: {}, n: {}",
+            test[0],
+            test[1]
+        );
+    }
+}
+
+#[test]
+fn kronecker_symbol_is_quadratic_character() {
+    // (a|n) is a real character modulo 4n for odd a and n
+    for n in 1..60u32 {
+        let modu = 4 * n;
+        let chi_values: Vec<i8> = (0..modu).map(|a| kronecker_symbol(a, n)).collect();
+
+        for a in 0..modu {
+            for b in 0..modu {
+                if u32::gcd_mod(a * b, modu) == 1 {
+                    assert_eq!(
+                        chi_values[((a * b) % modu) as usize],
+                        chi_values[a as usize] * chi_values[b as usize],
+                        "n: {}, a: {}, b: {}",
+                        n,
+                        a,
+                        b
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn quadratic_gauss_sum_small_primes() {
+    // [a, prm, sign + 1, imaginary]
+    let test_cases: [[u16; 4]; 6] = [
+        [1, 3, 2, 1],
+        [2, 3, 0, 1],
+        [1, 5, 2, 0],
+        [2, 5, 0, 0],
+        [4, 13, 2, 0],
+        [5, 23, 0, 1],
+    ];
+
+    for test in test_cases.iter() {
+        assert_eq!(
+            gauss_sum(test[0], test[1]),
+            Some(GaussSum {
+                sign: test[2] as i8 - 1,
+                imaginary: test[3] == 1,
+                modu: test[1]
+            }),
+            "a: {}, prm: {}",
+            test[0],
+            test[1]
+        );
+    }
+}
+
+#[test]
+fn quadratic_gauss_sum_matches_direct_summation() {
+    for &prm in [3u32, 7, 13, 31, 97].iter() {
+        for a in 1..prm {
+            let direct_sum: Complex64 = (0..prm)
+                .map(|x| {
+                    let angle = 2.0 * PI * ((a * x * x) % prm) as f64 / prm as f64;
+                    Complex64::from_polar(1.0, angle)
+                })
+                .sum();
+
+            let closed_form = gauss_sum(a, prm).unwrap().to_complex();
+
+            assert!(
+                (direct_sum - closed_form).norm() < 1e-9,
+                "a: {}, prm: {}",
+                a,
+                prm
+            );
+        }
+    }
+}
+
+#[test]
+fn quadratic_gauss_sum_invalid_arguments() {
+    assert_eq!(gauss_sum(0u32, 7), None);
+    assert_eq!(gauss_sum(14u32, 7), None);
+    assert_eq!(gauss_sum(1u32, 2), None);
+    assert_eq!(gauss_sum(1u32, 15), None);
+}
+
+#[test]
+fn character_gauss_sum_of_quadratic_character() {
+    for &prm in [5u32, 11, 43].iter() {
+        let chi = DirichletCharacter::new(prm, &[(prm - 1) / 2]).unwrap();
+
+        for a in 1..prm {
+            let corr_sum = gauss_sum(a, prm).unwrap().to_complex();
+
+            assert!(
+                (chi.gauss_sum(a) - corr_sum).norm() < 1e-9,
+                "a: {}, prm: {}",
+                a,
+                prm
+            );
+        }
+    }
+}
+
+#[test]
+fn character_gauss_sum_magnitudes() {
+    // Every nonprincipal character modulo a prime is primitive
+    let modu = 17u32;
+
+    for chi in all_characters(modu).iter() {
+        let magnitude = chi.gauss_sum(3).norm();
+
+        if chi.is_principal() {
+            // Ramanujan sum c_17(3) = -1
+            assert!((magnitude - 1.0).abs() < 1e-9);
+        } else {
+            assert!((magnitude - (modu as f64).sqrt()).abs() < 1e-9);
+        }
+    }
+}
+
+#[test]
+fn legendre_symbol_matches_jacobi_symbol() {
+    for &prm in [3u64, 5, 13, 1_009, 1_000_000_007].iter() {
+        for a in (0..200).chain(prm - 100..prm + 100) {
+            assert_eq!(
+                legendre(a, prm),
+                Some(u64::jacobi_symbol(a, prm)),
+                "prm: {}, a: {}",
+                prm,
+                a
+            );
+        }
+    }
+
+    let prm = u128::MAX - 158;
+    assert_eq!(
+        legendre(u128::MAX, prm),
+        Some(u128::jacobi_symbol(u128::MAX, prm))
+    );
+
+    for &modu in [0u64, 1, 2, 9, 15, 1_000_000_007 * 3].iter() {
+        assert_eq!(legendre(2, modu), None, "modu: {}", modu);
+    }
+}
@@ -0,0 +1,363 @@
+//! Implements the Chinese remainder theorem for congruences.
+//!
+//! Two congruences x = r_1 (mod m_1) and x = r_2 (mod m_2) have a common solution
+//! if and only if gcd(m_1, m_2) divides r_2 - r_1. In that case the solutions form
+//! a single residue class modulo lcm(m_1, m_2). Moduli need not be coprime.
+//!
+//! Congruences can be folded in one at a time with `CrtBuilder`, which keeps
+//! the combined congruence x = r (mod m) of all congruences pushed so far.
+//! Type `CongruenceSystem` holds a whole system of congruences, possibly formed
+//! from linear equations a_i * x + b_i = c_i (mod m_i), and solves it at once. Function
+//! `crt` solves a slice of congruences directly.
+//!
+//! For pairwise coprime moduli m_1, ..., m_k, Garner's algorithm computes the mixed
+//! radix representation x = v_1 + v_2 * m_1 + ... + v_k * m_1 * ... * m_(k-1) of the
+//! solution, where 0 <= v_i < m_i. Digits v_i are computed with arithmetic modulo m_i
+//! only, so the full product of the moduli is needed only when the digits are finally
+//! recombined to x.
+//!
+use std::{error::Error, fmt};
+
+use crate::{arith::Arith, lin, prime, LinEq, UInt};
+
+/// Errors that can occur when combining congruences.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CrtError {
+    /// Congruence contradicts the previously combined congruences.
+    Inconsistent,
+    /// Combined modulus wouldn't fit into the integer type.
+    ModulusOverflow,
+    /// Modulus of the congruence is zero.
+    InvalidModulus,
+}
+
+impl fmt::Display for CrtError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CrtError::Inconsistent => write!(f, "congruences are inconsistent"),
+            CrtError::ModulusOverflow => write!(f, "combined modulus overflows"),
+            CrtError::InvalidModulus => write!(f, "modulus must be positive"),
+        }
+    }
+}
+
+impl Error for CrtError {}
+
+/// Builder combining congruences x = r (mod m) incrementally.
+///
+/// Initially the builder holds the trivial congruence x = 0 (mod 1). Every
+/// successfully pushed congruence refines the combined congruence, while
+/// a failed push leaves it untouched.
+///
+/// # Examples
+///
+/// ```
+/// use modular_equations::{CrtBuilder, CrtError};
+///
+/// let mut crt = CrtBuilder::<u32>::new();
+///
+/// assert_eq!(crt.push(2, 3), Ok(()));
+/// assert_eq!(crt.push(3, 5), Ok(()));
+/// assert_eq!(crt.congruence(), (8, 15));
+///
+/// // x = 8 (mod 15) and x = 1 (mod 6) would require x to be even and odd
+/// assert_eq!(crt.push(1, 6), Err(CrtError::Inconsistent));
+/// assert_eq!(crt.congruence(), (8, 15));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CrtBuilder<T: UInt> {
+    residue: T,
+    modu: T,
+}
+
+impl<T: UInt> CrtBuilder<T> {
+    /// Create a builder holding the trivial congruence x = 0 (mod 1).
+    pub fn new() -> Self {
+        Self {
+            residue: T::zero(),
+            modu: T::one(),
+        }
+    }
+
+    /// Fold congruence x = `residue` (mod `modu`) into the combined congruence.
+    ///
+    /// Residue doesn't need to be reduced. Combined modulus will be the lcm
+    /// of all pushed moduli and it must fit into the type T.
+    pub fn push(&mut self, residue: T, modu: T) -> Result<(), CrtError> {
+        if modu == T::zero() {
+            return Err(CrtError::InvalidModulus);
+        }
+
+        let gcd = T::gcd_mod(self.modu, modu);
+
+        if self.modu.checked_mul(&(modu / gcd)).is_none() {
+            return Err(CrtError::ModulusOverflow);
+        }
+
+        match combine_pair(self.residue, self.modu, residue % modu, modu) {
+            Some((residue, modu)) => {
+                self.residue = residue;
+                self.modu = modu;
+                Ok(())
+            }
+            None => Err(CrtError::Inconsistent),
+        }
+    }
+
+    /// Residue r of the combined congruence x = r (mod m).
+    pub fn residue(&self) -> T {
+        self.residue
+    }
+
+    /// Modulus m of the combined congruence x = r (mod m).
+    pub fn modu(&self) -> T {
+        self.modu
+    }
+
+    /// Combined congruence x = r (mod m) as a pair (r, m).
+    pub fn congruence(&self) -> (T, T) {
+        (self.residue, self.modu)
+    }
+}
+
+impl<T: UInt> Default for CrtBuilder<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Type for systems of congruences x = r_i (mod m_i).
+///
+/// Each element of `congruences` is a pair (r_i, m_i). Moduli need not be pairwise
+/// coprime and residues need not be reduced.
+///
+/// # Examples
+///
+/// ```
+/// use modular_equations::{CongruenceSystem, LinEq};
+///
+/// let system = CongruenceSystem::<u32> {congruences: vec![(3, 4), (5, 6)]};
+///
+/// // x = 11 (mod 12) satisfies both congruences
+/// assert_eq!(system.solve(), Some((11, 12)));
+///
+/// // 3x = 2 (mod 4) and 2x = 4 (mod 6), i.e. x = 2 (mod 4) and x = 2 (mod 3)
+/// let lin_eqs = [
+///     LinEq::<u32> {a: 3, b: 0, c: 2, modu: 4},
+///     LinEq::<u32> {a: 2, b: 0, c: 4, modu: 6},
+/// ];
+/// let system = CongruenceSystem::from_lin_eqs(&lin_eqs).unwrap();
+///
+/// assert_eq!(system.solve(), Some((2, 12)));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CongruenceSystem<T: UInt> {
+    pub congruences: Vec<(T, T)>,
+}
+
+impl<T: UInt> CongruenceSystem<T> {
+    /// Form the system from linear equations a_i * x + b_i = c_i (mod m_i).
+    ///
+    /// Every linear equation is solved to a single congruence x = r_i (mod m_i / gcd(a_i, m_i)).
+    /// If some of the equations doesn't have solutions, None is returned.
+    pub fn from_lin_eqs(lin_eqs: &[LinEq<T>]) -> Option<Self> {
+        let congruences = lin_eqs
+            .iter()
+            .map(lin::solve_as_congruence)
+            .collect::<Option<Vec<(T, T)>>>()?;
+
+        Some(Self { congruences })
+    }
+
+    /// Solve the system as a single residue class x = r (mod m).
+    ///
+    /// Combined modulus m is the lcm of the moduli. Empty system corresponds to the
+    /// trivial class x = 0 (mod 1). If the congruences are inconsistent, some modulus
+    /// is zero or the lcm doesn't fit into the type T, None is returned.
+    pub fn solve(&self) -> Option<(T, T)> {
+        crt(&self.congruences)
+    }
+}
+
+/// Solve congruences x = r_i (mod m_i), given as pairs (r_i, m_i), as x = r (mod m).
+///
+/// Moduli need not be pairwise coprime, in which case the congruences are checked to be
+/// consistent. Combined modulus m is the lcm of the moduli. Empty slice corresponds to the
+/// trivial class x = 0 (mod 1). If the congruences are inconsistent, some modulus is zero
+/// or the lcm doesn't fit into the type T, None is returned.
+///
+/// # Examples
+///
+/// ```
+/// use modular_equations::crt;
+///
+/// assert_eq!(crt::<u32>(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+/// assert_eq!(crt::<u32>(&[(3, 4), (5, 6)]), Some((11, 12)));
+/// // x = 3 (mod 4) is odd but x = 2 (mod 6) even
+/// assert_eq!(crt::<u32>(&[(3, 4), (2, 6)]), None);
+/// ```
+pub fn crt<T: UInt>(congruences: &[(T, T)]) -> Option<(T, T)> {
+    let mut builder = CrtBuilder::new();
+
+    for &(residue, modu) in congruences.iter() {
+        builder.push(residue, modu).ok()?;
+    }
+
+    Some(builder.congruence())
+}
+
+/// Combine congruences x = `r_1` (mod `m_1`) and x = `r_2` (mod `m_2`).
+///
+/// Residues must be reduced, i.e. smaller than their moduli. Returns the combined
+/// congruence as a pair (r, lcm(m_1, m_2)) or None if the congruences are inconsistent.
+/// Caller is responsible for the lcm fitting into the type T.
+pub fn combine_pair<T: UInt>(r_1: T, m_1: T, r_2: T, m_2: T) -> Option<(T, T)> {
+    let gcd = T::gcd_mod(m_1, m_2);
+    let diff = T::sub_mod(r_2, r_1 % m_2, m_2);
+
+    if diff % gcd != T::zero() {
+        return None;
+    }
+
+    let m_2_red = m_2 / gcd;
+    let lcm = m_1 * m_2_red;
+
+    if m_2_red == T::one() {
+        return Some((r_1, lcm));
+    }
+
+    // x = r_1 + m_1 * t, where t = (diff / gcd) * (m_1 / gcd)^(-1) (mod m_2 / gcd)
+    let t = T::mult_mod(
+        diff / gcd,
+        T::multip_inv((m_1 / gcd) % m_2_red, m_2_red),
+        m_2_red,
+    );
+
+    Some((r_1 + m_1 * t, lcm))
+}
+
+/// Mixed radix digits of the solution of congruences x = `residues`\[i\] (mod `moduli`\[i\]).
+///
+/// Moduli must be positive and pairwise coprime, otherwise None is returned. Digits
+/// v_i satisfy 0 <= v_i < m_i and x = v_1 + v_2 * m_1 + ... + v_k * m_1 * ... * m_(k-1).
+/// Product of the moduli doesn't need to fit into the type T.
+///
+/// # Examples
+///
+/// ```
+/// use modular_equations::mixed_radix_digits;
+///
+/// // x = 8 = 2 + 2 * 3 (mod 15)
+/// assert_eq!(mixed_radix_digits::<u8>(&[2, 3], &[3, 5]), Some(vec![2, 2]));
+/// ```
+pub fn mixed_radix_digits<T: UInt>(residues: &[T], moduli: &[T]) -> Option<Vec<T>> {
+    if residues.len() != moduli.len() || moduli.iter().any(|&m| m == T::zero()) {
+        return None;
+    }
+
+    let mut digits: Vec<T> = Vec::with_capacity(moduli.len());
+
+    for (i, (&r_i, &m_i)) in residues.iter().zip(moduli.iter()).enumerate() {
+        let mut digit = r_i % m_i;
+
+        for (&v_j, &m_j) in digits.iter().zip(moduli[..i].iter()) {
+            if T::gcd_mod(m_j, m_i) != T::one() {
+                return None;
+            }
+            if m_i == T::one() {
+                continue;
+            }
+
+            // v_i = (...((r_i - v_1) / m_1 - v_2) / m_2 - ...) / m_(i-1) (mod m_i)
+            let m_j_inv = T::multip_inv(m_j % m_i, m_i);
+            digit = T::mult_mod(T::sub_mod(digit, v_j, m_i), m_j_inv, m_i);
+        }
+
+        digits.push(digit);
+    }
+
+    Some(digits)
+}
+
+/// Solve congruences x = `residues`\[i\] (mod `moduli`\[i\]) by Garner's algorithm.
+///
+/// Moduli must be positive and pairwise coprime and their product must fit into
+/// the type T, otherwise None is returned. Returned solution is the smallest
+/// nonnegative one, i.e. reduced modulo the product of the moduli.
+///
+/// # Examples
+///
+/// ```
+/// use modular_equations::crt_garner;
+///
+/// assert_eq!(crt_garner::<u32>(&[2, 3, 2], &[3, 5, 7]), Some(23));
+/// // Moduli 4 and 6 are not coprime
+/// assert_eq!(crt_garner::<u32>(&[1, 1], &[4, 6]), None);
+/// ```
+pub fn crt_garner<T: UInt>(residues: &[T], moduli: &[T]) -> Option<T> {
+    let digits = mixed_radix_digits(residues, moduli)?;
+
+    moduli
+        .iter()
+        .try_fold(T::one(), |prod: T, &m| prod.checked_mul(&m))?;
+
+    // Horner's rule, x = v_1 + m_1 * (v_2 + m_2 * (v_3 + ...))
+    let x = digits
+        .iter()
+        .zip(moduli.iter())
+        .rev()
+        .fold(T::zero(), |x, (&v, &m)| v + m * x);
+
+    Some(x)
+}
+
+/// Modular exponentiation `base` ^ `ex` (mod n) for n given by its prime factorization
+/// `factors`: \[(p_1,k_1), ..., (p_m,k_m)\], e.g. an RSA modulus with known primes.
+///
+/// Power is computed modulo every prime power p_i^k_i, with the exponent reduced modulo
+/// phi(p_i^k_i) = p_i^(k_i - 1) * (p_i - 1) when the base is coprime to p_i, and the results
+/// are combined by Garner's algorithm. Primes must be distinct, exponents k_i positive and
+/// n must fit into the type T, otherwise None is returned. Empty factorization stands for
+/// n = 1 for which the result is zero.
+///
+/// # Examples
+///
+/// ```
+/// use modular_equations::{exp_mod, exp_mod_crt};
+///
+/// let (p, q) = (4_294_967_291u64, 4_294_967_279u64);
+///
+/// assert_eq!(exp_mod_crt(3, 65_537, &[(p, 1), (q, 1)]), exp_mod(3, 65_537, p * q));
+/// assert_eq!(exp_mod_crt::<u32>(5, 3, &[(3, 2), (2, 3)]), Some(125 % 72));
+/// // 4 isn't a prime
+/// assert_eq!(exp_mod_crt::<u32>(5, 3, &[(4, 1)]), None);
+/// ```
+pub fn exp_mod_crt<T: UInt>(base: T, ex: T, factors: &[(T, u8)]) -> Option<T> {
+    let mut residues = Vec::with_capacity(factors.len());
+    let mut moduli = Vec::with_capacity(factors.len());
+
+    for (j, &(prm, k)) in factors.iter().enumerate() {
+        if k == 0 || !prime::is_prime(prm) || factors[..j].iter().any(|&(p, _)| p == prm) {
+            return None;
+        }
+
+        let prm_power = num::checked_pow(prm, k.into())?;
+        let base = base % prm_power;
+
+        let residue = if base % prm == T::zero() {
+            T::exp_mod(base, ex, prm_power)
+        } else {
+            // Euler's theorem, base^phi(p^k) = 1 (mod p^k)
+            let phi = prm_power / prm * (prm - T::one());
+            T::exp_mod(base, ex % phi, prm_power)
+        };
+
+        residues.push(residue);
+        moduli.push(prm_power);
+    }
+
+    crt_garner(&residues, &moduli)
+}
+
+#[cfg(test)]
+mod tests;
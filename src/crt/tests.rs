@@ -0,0 +1,370 @@
+use crate::arith::Arith;
+use crate::crt::{
+    combine_pair, crt, crt_garner, exp_mod_crt, mixed_radix_digits, CongruenceSystem, CrtBuilder,
+    CrtError,
+};
+use crate::LinEq;
+
+#[test]
+fn combine_pair_coprime_moduli() {
+    // [r_1, m_1, r_2, m_2, r, lcm]
+    let test_cases: [[u32; 6]; 5] = [
+        [0, 1, 3, 7, 3, 7],
+        [2, 3, 3, 5, 8, 15],
+        [1, 4, 2, 9, 29, 36],
+        [6, 7, 0, 11, 55, 77],
+        [10, 11, 12, 13, 142, 143],
+    ];
+
+    for test in test_cases.iter() {
+        assert_eq!(
+            combine_pair(test[0], test[1], test[2], test[3]),
+            Some((test[4], test[5])),
+            "test: {:?}",
+            test
+        );
+    }
+}
+
+#[test]
+fn combine_pair_non_coprime_moduli() {
+    assert_eq!(combine_pair(2u32, 6, 8, 10), Some((8, 30)));
+    assert_eq!(combine_pair(3u32, 12, 3, 4), Some((3, 12)));
+    assert_eq!(combine_pair(1u32, 4, 2, 6), None);
+    assert_eq!(combine_pair(5u32, 12, 3, 8), None);
+}
+
+#[test]
+fn combine_pair_brute_force_small_moduli() {
+    for m_1 in 1..25u32 {
+        for m_2 in 1..25u32 {
+            for r_1 in 0..m_1 {
+                for r_2 in 0..m_2 {
+                    let corr = (0..m_1 * m_2).find(|x| x % m_1 == r_1 && x % m_2 == r_2);
+
+                    match combine_pair(r_1, m_1, r_2, m_2) {
+                        Some((r, lcm)) => {
+                            assert_eq!(Some(r), corr, "{} {} {} {}", r_1, m_1, r_2, m_2);
+                            assert_eq!(lcm * u32::gcd_mod(m_1, m_2), m_1 * m_2);
+                        }
+                        None => assert_eq!(corr, None, "{} {} {} {}", r_1, m_1, r_2, m_2),
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn combine_pair_large_type() {
+    let m_1: u128 = 18_446_744_073_709_551_557; // largest prime below 2^64
+    let m_2: u128 = 18_446_744_073_709_551_533;
+
+    match combine_pair(1, m_1, 2, m_2) {
+        Some((r, lcm)) => {
+            assert_eq!(lcm, m_1 * m_2);
+            assert_eq!(r % m_1, 1);
+            assert_eq!(r % m_2, 2);
+        }
+        None => panic!("combined congruence: None"),
+    }
+}
+
+#[test]
+fn builder_folds_congruences() {
+    let mut crt = CrtBuilder::<u64>::new();
+
+    assert_eq!(crt.congruence(), (0, 1));
+
+    // [r, m, combined r, combined m]
+    let test_cases: [[u64; 4]; 5] = [
+        [1, 4, 1, 4],
+        [14, 9, 5, 36],
+        [0, 6, 0, 0],
+        [5, 12, 5, 36],
+        [1, 25, 401, 900],
+    ];
+
+    for test in test_cases.iter() {
+        let res = crt.push(test[0], test[1]);
+
+        if test[3] == 0 {
+            assert_eq!(res, Err(CrtError::Inconsistent), "test: {:?}", test);
+        } else {
+            assert_eq!(res, Ok(()), "test: {:?}", test);
+            assert_eq!(crt.residue(), test[2]);
+            assert_eq!(crt.modu(), test[3]);
+        }
+    }
+}
+
+#[test]
+fn builder_invalid_modulus_and_overflow() {
+    let mut crt = CrtBuilder::<u8>::default();
+
+    assert_eq!(crt.push(1, 0), Err(CrtError::InvalidModulus));
+    assert_eq!(crt.push(3, 17), Ok(()));
+    assert_eq!(crt.push(1, 16), Err(CrtError::ModulusOverflow));
+    assert_eq!(crt.congruence(), (3, 17));
+
+    assert_eq!(crt.push(5, 15), Ok(()));
+    assert_eq!(crt.congruence(), (20, 255));
+    assert_eq!(crt.push(20, 255), Ok(()));
+    assert_eq!(crt.congruence(), (20, 255));
+}
+
+#[test]
+fn mixed_radix_digits_small_type() {
+    let test_cases: [(&[u16], &[u16], &[u16]); 4] = [
+        (&[5], &[7], &[5]),
+        (&[2, 3], &[3, 5], &[2, 2]),
+        (&[2, 3, 2], &[3, 5, 7], &[2, 2, 1]),
+        (&[0, 0, 1, 1], &[2, 1, 3, 5], &[0, 0, 2, 2]),
+    ];
+
+    for (residues, moduli, corr_digits) in test_cases.iter() {
+        assert_eq!(
+            mixed_radix_digits(residues, moduli),
+            Some(corr_digits.to_vec()),
+            "moduli: {:?}",
+            moduli
+        );
+    }
+}
+
+#[test]
+fn garner_brute_force_small_moduli() {
+    let moduli: [u32; 4] = [4, 9, 5, 7];
+    let prod: u32 = moduli.iter().product();
+
+    for x in 0..prod {
+        let residues: Vec<u32> = moduli.iter().map(|m| x % m).collect();
+
+        assert_eq!(crt_garner(&residues, &moduli), Some(x), "x: {}", x);
+    }
+}
+
+#[test]
+fn garner_near_type_limit() {
+    // Pairwise coprime factors of 2^128 - 1
+    let moduli: [u128; 5] = [
+        3 * 5 * 17 * 257,
+        641,
+        65_537,
+        6_700_417,
+        274_177 * 67_280_421_310_721,
+    ];
+    let x = u128::MAX - 12_345;
+
+    let residues: Vec<u128> = moduli.iter().map(|m| x % m).collect();
+
+    assert_eq!(crt_garner(&residues, &moduli), Some(x));
+
+    match mixed_radix_digits(&residues, &moduli) {
+        Some(digits) => {
+            for (v, m) in digits.iter().zip(moduli.iter()) {
+                assert!(v < m);
+            }
+        }
+        None => panic!("digits: None"),
+    }
+}
+
+#[test]
+fn garner_invalid_moduli() {
+    assert_eq!(crt_garner(&[1u32, 1], &[4, 6]), None);
+    assert_eq!(crt_garner(&[1u32, 1], &[4, 0]), None);
+    assert_eq!(crt_garner(&[1u32], &[4, 5]), None);
+    assert_eq!(mixed_radix_digits(&[1u32, 1, 1], &[3, 5, 9]), None);
+
+    // Product of the moduli overflows but the digits can still be computed
+    let (residues, moduli) = ([1u8, 2, 3], [11u8, 13, 17]);
+
+    assert_eq!(crt_garner(&residues, &moduli), None);
+    assert!(mixed_radix_digits(&residues, &moduli).is_some());
+}
+
+#[test]
+fn congruence_system_brute_force() {
+    let systems: [[(u32, u32); 3]; 5] = [
+        [(1, 2), (2, 3), (3, 5)],
+        [(3, 4), (5, 6), (1, 10)],
+        [(3, 4), (5, 6), (2, 10)],
+        [(0, 1), (7, 8), (15, 16)],
+        [(9, 12), (15, 18), (21, 30)],
+    ];
+
+    for congruences in systems.iter() {
+        let system = CongruenceSystem {
+            congruences: congruences.to_vec(),
+        };
+
+        let lcm = congruences
+            .iter()
+            .fold(1, |lcm, &(_, m)| lcm / u32::gcd_mod(lcm, m) * m);
+        let x = (0..lcm).find(|x| congruences.iter().all(|&(r, m)| x % m == r % m));
+
+        assert_eq!(
+            system.solve(),
+            x.map(|x| (x, lcm)),
+            "congruences: {:?}",
+            congruences
+        );
+    }
+}
+
+#[test]
+fn congruence_system_edge_cases() {
+    let system = CongruenceSystem::<u8> {
+        congruences: vec![],
+    };
+    assert_eq!(system.solve(), Some((0, 1)));
+
+    let system = CongruenceSystem::<u8> {
+        congruences: vec![(1, 0)],
+    };
+    assert_eq!(system.solve(), None);
+
+    // lcm 17 * 16 doesn't fit into u8
+    let system = CongruenceSystem::<u8> {
+        congruences: vec![(1, 17), (1, 16)],
+    };
+    assert_eq!(system.solve(), None);
+}
+
+#[test]
+fn congruence_system_from_lin_eqs() {
+    let lin_eqs = [
+        LinEq::<u64> {
+            a: 4,
+            b: 1,
+            c: 3,
+            modu: 10,
+        },
+        LinEq::<u64> {
+            a: 1,
+            b: 0,
+            c: 2,
+            modu: 7,
+        },
+    ];
+
+    // 4x = 2 (mod 10) gives x = 3 (mod 5), thus x = 23 (mod 35)
+    match CongruenceSystem::from_lin_eqs(&lin_eqs) {
+        Some(system) => {
+            assert_eq!(system.congruences, vec![(3, 5), (2, 7)]);
+            assert_eq!(system.solve(), Some((23, 35)));
+        }
+        None => panic!("linear equations have solutions"),
+    }
+
+    // 2x = 1 (mod 4) doesn't have solutions
+    let lin_eqs = [LinEq::<u64> {
+        a: 2,
+        b: 0,
+        c: 1,
+        modu: 4,
+    }];
+
+    assert_eq!(CongruenceSystem::from_lin_eqs(&lin_eqs), None);
+}
+
+#[test]
+fn crt_brute_force_non_coprime_moduli() {
+    for m_1 in 1..20u32 {
+        for m_2 in 1..20u32 {
+            let lcm = m_1 / u32::gcd_mod(m_1, m_2) * m_2;
+
+            for r_1 in 0..m_1 {
+                for r_2 in [0, 1, m_2 - 1, m_2 + 3].iter() {
+                    let x = (0..lcm).find(|x| x % m_1 == r_1 && x % m_2 == r_2 % m_2);
+
+                    assert_eq!(
+                        crt(&[(r_1, m_1), (*r_2, m_2)]),
+                        x.map(|x| (x, lcm)),
+                        "r_1: {}, m_1: {}, r_2: {}, m_2: {}",
+                        r_1,
+                        m_1,
+                        r_2,
+                        m_2
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn crt_large_type() {
+    // Moduli 2^64 * 3 and 2^63 * 5 share the factor 2^63
+    let m_1 = 3u128 << 64;
+    let m_2 = 5u128 << 63;
+    let x = u128::MAX / 7;
+
+    match crt(&[(x % m_1, m_1), (x % m_2, m_2)]) {
+        Some((r, m)) => {
+            assert_eq!(m, 15u128 << 64);
+            assert_eq!(r, x % m);
+        }
+        None => panic!("congruences should be consistent"),
+    }
+
+    assert_eq!(crt(&[(1, u128::MAX), (0, 2)]), None);
+}
+
+#[test]
+fn exp_mod_crt_small_moduli() {
+    let factorizations: [&[(u32, u8)]; 6] = [
+        &[(2, 1)],
+        &[(2, 5)],
+        &[(3, 2), (2, 3)],
+        &[(5, 1), (7, 1), (11, 1)],
+        &[(3, 4), (7, 2)],
+        &[(65_521, 1), (3, 1)],
+    ];
+
+    for factors in factorizations.iter() {
+        let modu: u32 = factors.iter().map(|&(p, k)| p.pow(k.into())).product();
+
+        for base in [0, 1, 2, 3, 6, 14, 21, modu - 1, modu, modu + 5, u32::MAX] {
+            for ex in [0, 1, 2, 3, 4, 5, 100, 65_537, u32::MAX] {
+                assert_eq!(
+                    exp_mod_crt(base, ex, factors),
+                    Some(u32::exp_mod(base, ex, modu)),
+                    "base: {}, ex: {}, modu: {}",
+                    base,
+                    ex,
+                    modu
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn exp_mod_crt_large_modulo() {
+    let (p, q) = (
+        18_446_744_073_709_551_557u128,
+        18_446_744_073_709_551_533u128,
+    );
+    let modu = p * q;
+
+    for base in [2, 3, 12_345_678_901_234_567_890, modu - 1] {
+        for ex in [65_537, modu - 2, u128::MAX] {
+            assert_eq!(
+                exp_mod_crt(base, ex, &[(p, 1), (q, 1)]),
+                Some(u128::exp_mod(base, ex, modu))
+            );
+        }
+    }
+}
+
+#[test]
+fn exp_mod_crt_invalid_factors() {
+    assert_eq!(exp_mod_crt::<u32>(2, 3, &[]), Some(0));
+    assert_eq!(exp_mod_crt::<u32>(2, 3, &[(6, 1)]), None);
+    assert_eq!(exp_mod_crt::<u32>(2, 3, &[(3, 0)]), None);
+    assert_eq!(exp_mod_crt::<u32>(2, 3, &[(3, 1), (5, 1), (3, 2)]), None);
+    assert_eq!(exp_mod_crt::<u32>(2, 3, &[(65_537, 2)]), None);
+    assert_eq!(exp_mod_crt::<u32>(2, 3, &[(65_537, 1), (65_539, 1)]), None);
+}
@@ -0,0 +1,237 @@
+//! Implements constant-time modular arithmetic.
+//!
+//! Module is compiled only with the feature `ct`. Its functions are meant for callers
+//! whose operands are secret, e.g. private exponents or coefficients in cryptographic
+//! code, and their running time doesn't depend on the operand values. Only the modulus
+//! is considered public.
+//!
+//! Operations don't branch on the operands. Conditional steps are done by selecting
+//! with bit masks and comparisons by computing the borrow of a subtraction in two
+//! half-width digits. Operands aren't reduced modulo `modu`, as division isn't
+//! constant-time on every platform, hence they must already be smaller than the modulus.
+//! Otherwise None is returned, which reveals only that the input was invalid.
+//!
+//! Exponentiation runs the Montgomery ladder over every bit of the exponent type and
+//! the multiplicative inverse is computed with a binary extended Euclidean algorithm
+//! of a fixed iteration count, which requires an odd modulus.
+//!
+use std::hint;
+
+use crate::UInt;
+
+/// Bit count of the type T.
+fn bits<T: UInt>() -> usize {
+    T::zero().count_zeros() as usize
+}
+
+/// Mask of all ones if `bit` is one and of zeros if it's zero.
+fn mask<T: UInt>(bit: T) -> T {
+    hint::black_box(T::max_value() * bit)
+}
+
+/// Swap `x` and `y` if `mask` is all ones.
+fn swap<T: UInt>(mask: T, x: &mut T, y: &mut T) {
+    let diff = (*x ^ *y) & mask;
+
+    *x = *x ^ diff;
+    *y = *y ^ diff;
+}
+
+/// One if `x` < `y` and zero otherwise.
+fn less_than<T: UInt>(x: T, y: T) -> T {
+    let half = bits::<T>() / 2;
+    let base = T::one() << half;
+    let low_mask = base - T::one();
+
+    // Subtract half-width digits offset by `base`, the top bit telling the borrow
+    let low = (x & low_mask) + base - (y & low_mask);
+    let borrow = T::one() - (low >> half);
+    let high = (x >> half) + base - (y >> half) - borrow;
+
+    T::one() - (high >> half)
+}
+
+/// Addition for `x` and `y` smaller than `modu`.
+fn add<T: UInt>(x: T, y: T, modu: T) -> T {
+    let y_compl = modu - y;
+    // Sum wraps around the modulus if `x` >= `modu` - `y`
+    let wraps = mask(T::one() - less_than(x, y_compl));
+
+    (x - (y_compl & wraps)) + (y & !wraps)
+}
+
+/// Subtraction for `x` and `y` smaller than `modu`.
+fn sub<T: UInt>(x: T, y: T, modu: T) -> T {
+    let wraps = mask(less_than(x, y));
+
+    (x + ((modu - y) & wraps)) - (y & !wraps)
+}
+
+/// Multiplication for `x` and `y` smaller than `modu` by double-and-add over every bit of `y`.
+fn mult<T: UInt>(mut x: T, y: T, modu: T) -> T {
+    let mut res = T::zero();
+
+    for i in 0..bits::<T>() {
+        let bit = mask((y >> i) & T::one());
+
+        res = add(res, x & bit, modu);
+        x = add(x, x, modu);
+    }
+
+    res
+}
+
+/// Exponentiation for `base` smaller than `modu` and `modu` larger than one.
+fn exp<T: UInt>(base: T, ex: T, modu: T) -> T {
+    let (mut res, mut acc) = (T::one(), base);
+
+    for i in (0..bits::<T>()).rev() {
+        let bit = mask((ex >> i) & T::one());
+
+        swap(bit, &mut res, &mut acc);
+        acc = mult(res, acc, modu);
+        res = mult(res, res, modu);
+        swap(bit, &mut res, &mut acc);
+    }
+
+    res
+}
+
+/// Halving of `x` smaller than odd `modu`.
+fn half<T: UInt>(x: T, modu: T) -> T {
+    let odd = mask(x & T::one());
+
+    // For odd x, (x + modu) / 2 computed without overflow
+    (x >> 1) + (((modu >> 1) + T::one()) & odd)
+}
+
+/// Whether all of `xs` are smaller than `modu`, without branching on them.
+fn are_reduced<T: UInt>(xs: &[T], modu: T) -> bool {
+    xs.iter()
+        .fold(T::one(), |reduced, &x| reduced & less_than(x, modu))
+        == T::one()
+}
+
+/// Constant-time modular addition, `x` + `y` (mod `modu`).
+///
+/// Operands must be smaller than the modulo. Otherwise or if the modulo is zero,
+/// None is returned.
+///
+/// # Examples
+///
+/// ```
+/// use modular_equations::add_mod_ct;
+///
+/// assert_eq!(add_mod_ct::<u128>(u128::MAX - 1, u128::MAX - 1, u128::MAX), Some(u128::MAX - 2));
+/// assert_eq!(add_mod_ct::<u8>(200, 100, 150), None);
+/// ```
+pub fn add_mod_ct<T: UInt>(x: T, y: T, modu: T) -> Option<T> {
+    are_reduced(&[x, y], modu).then(|| add(x, y, modu))
+}
+
+/// Constant-time modular subtraction, `x` - `y` (mod `modu`).
+///
+/// Operands must be smaller than the modulo. Otherwise or if the modulo is zero,
+/// None is returned.
+///
+/// # Examples
+///
+/// ```
+/// use modular_equations::sub_mod_ct;
+///
+/// assert_eq!(sub_mod_ct::<u32>(3, 5, 7), Some(5));
+/// ```
+pub fn sub_mod_ct<T: UInt>(x: T, y: T, modu: T) -> Option<T> {
+    are_reduced(&[x, y], modu).then(|| sub(x, y, modu))
+}
+
+/// Constant-time modular multiplication, `x` * `y` (mod `modu`).
+///
+/// Operands must be smaller than the modulo. Otherwise or if the modulo is zero,
+/// None is returned.
+///
+/// # Examples
+///
+/// ```
+/// use modular_equations::mult_mod_ct;
+///
+/// let modu = u128::MAX;
+///
+/// assert_eq!(mult_mod_ct::<u128>(modu - 1, modu - 1, modu), Some(1));
+/// ```
+pub fn mult_mod_ct<T: UInt>(x: T, y: T, modu: T) -> Option<T> {
+    are_reduced(&[x, y], modu).then(|| mult(x, y, modu))
+}
+
+/// Constant-time modular exponentiation, `base` ^ `ex` (mod `modu`).
+///
+/// Base must be smaller than the modulo, whereas the exponent can be any value of the
+/// type. Otherwise or if the modulo is zero, None is returned. For modulo one the
+/// result is always zero.
+///
+/// # Examples
+///
+/// ```
+/// use modular_equations::exp_mod_ct;
+///
+/// assert_eq!(exp_mod_ct::<u64>(3, 200, 1_000_000_007), Some(136_318_165));
+/// assert_eq!(exp_mod_ct::<u64>(3, 200, 3), None);
+/// ```
+pub fn exp_mod_ct<T: UInt>(base: T, ex: T, modu: T) -> Option<T> {
+    if !are_reduced(&[base], modu) {
+        return None;
+    }
+    if modu == T::one() {
+        return Some(T::zero());
+    }
+
+    Some(exp(base, ex, modu))
+}
+
+/// Constant-time multiplicative inverse of `x` modulo `modu`.
+///
+/// Modulo must be odd, e.g. a prime or an RSA modulus, and `x` smaller than it. Otherwise
+/// or if the inverse doesn't exist, i.e. gcd(x, modu) > 1, None is returned.
+///
+/// # Examples
+///
+/// ```
+/// use modular_equations::multip_inv_ct;
+///
+/// assert_eq!(multip_inv_ct::<u16>(3, 7), Some(5));
+/// assert_eq!(multip_inv_ct::<u16>(3, 9), None);
+/// assert_eq!(multip_inv_ct::<u16>(3, 8), None);
+/// ```
+pub fn multip_inv_ct<T: UInt>(x: T, modu: T) -> Option<T> {
+    if modu & T::one() == T::zero() || !are_reduced(&[x], modu) {
+        return None;
+    }
+    if modu == T::one() {
+        return Some(T::zero());
+    }
+
+    // Invariants a = u * x and b = v * x (mod modu), b staying odd
+    let (mut a, mut b) = (x, modu);
+    let (mut u, mut v) = (T::one(), T::zero());
+
+    // Product a * b < 2^(2 * bits) at least halves on every iteration
+    for _ in 0..2 * bits::<T>() {
+        let odd = mask(a & T::one());
+        let swaps = odd & mask(less_than(a, b));
+
+        swap(swaps, &mut a, &mut b);
+        swap(swaps, &mut u, &mut v);
+
+        a = a - (b & odd);
+        u = sub(u, v & odd, modu);
+
+        a = a >> 1;
+        u = half(u, modu);
+    }
+
+    // Now a = 0 and b = gcd(x, modu)
+    (b == T::one()).then_some(v)
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,113 @@
+use crate::arith::Arith;
+use crate::ct::{add_mod_ct, exp_mod_ct, less_than, mult_mod_ct, multip_inv_ct, sub_mod_ct};
+
+#[test]
+fn less_than_small_type() {
+    for x in 0..=u8::MAX {
+        for y in 0..=u8::MAX {
+            assert_eq!(less_than(x, y), (x < y) as u8, "x: {}, y: {}", x, y);
+        }
+    }
+}
+
+#[test]
+fn less_than_large_type() {
+    let values = [0, 1, 2, u64::MAX as u128, 1 << 64, u128::MAX - 1, u128::MAX];
+
+    for &x in values.iter() {
+        for &y in values.iter() {
+            assert_eq!(less_than(x, y), (x < y) as u128, "x: {}, y: {}", x, y);
+        }
+    }
+}
+
+#[test]
+fn small_type_operations() {
+    for modu in 1..=u8::MAX {
+        for x in 0..modu {
+            for y in 0..modu {
+                assert_eq!(add_mod_ct(x, y, modu), Some(u8::add_mod(x, y, modu)));
+                assert_eq!(sub_mod_ct(x, y, modu), Some(u8::sub_mod(x, y, modu)));
+                assert_eq!(mult_mod_ct(x, y, modu), Some(u8::mult_mod(x, y, modu)));
+            }
+        }
+    }
+}
+
+#[test]
+fn large_type_operations() {
+    let modu = u128::MAX - 158;
+    let operands = [0, 1, 2, modu / 3, modu / 2 + 1, modu - 2, modu - 1];
+
+    for &x in operands.iter() {
+        for &y in operands.iter() {
+            assert_eq!(add_mod_ct(x, y, modu), Some(u128::add_mod(x, y, modu)));
+            assert_eq!(sub_mod_ct(x, y, modu), Some(u128::sub_mod(x, y, modu)));
+            assert_eq!(mult_mod_ct(x, y, modu), Some(u128::mult_mod(x, y, modu)));
+            assert_eq!(exp_mod_ct(x, y, modu), Some(u128::exp_mod(x, y, modu)));
+        }
+    }
+}
+
+#[test]
+fn unreduced_operands() {
+    assert_eq!(add_mod_ct::<u8>(7, 1, 7), None);
+    assert_eq!(sub_mod_ct::<u8>(1, 7, 7), None);
+    assert_eq!(mult_mod_ct::<u32>(u32::MAX, 1, 5), None);
+    assert_eq!(exp_mod_ct::<u64>(10, 3, 10), None);
+    assert_eq!(multip_inv_ct::<u64>(11, 11), None);
+    assert_eq!(add_mod_ct::<u16>(0, 0, 0), None);
+    assert_eq!(exp_mod_ct::<u16>(0, 5, 1), Some(0));
+}
+
+#[test]
+fn exp_small_type() {
+    for modu in 2..=u8::MAX {
+        for base in 0..modu {
+            for ex in [0, 1, 2, 3, 127, 128, u8::MAX] {
+                assert_eq!(
+                    exp_mod_ct(base, ex, modu),
+                    Some(u8::exp_mod(base, ex, modu)),
+                    "base: {}, ex: {}, modu: {}",
+                    base,
+                    ex,
+                    modu
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn multip_inv_small_type() {
+    for modu in (1..=u8::MAX).step_by(2) {
+        for x in 0..modu {
+            let inv = match modu {
+                1 => Some(0),
+                _ => Some(u8::multip_inv(x, modu)).filter(|&inv| inv > 0),
+            };
+
+            assert_eq!(multip_inv_ct(x, modu), inv, "x: {}, modu: {}", x, modu);
+        }
+    }
+
+    assert_eq!(multip_inv_ct::<u8>(3, 10), None);
+}
+
+#[test]
+fn multip_inv_large_type() {
+    // Largest prime below 2^128
+    let modu = u128::MAX - 158;
+
+    for x in [1, 2, 3, modu / 2, modu - 2, modu - 1] {
+        let inv = multip_inv_ct(x, modu).unwrap();
+
+        assert_eq!(u128::mult_mod(x, inv, modu), 1, "x: {}", x);
+    }
+
+    // Odd composite modulo divisible by three
+    let modu = 3 * ((1u128 << 126) - 1);
+
+    assert_eq!(multip_inv_ct(6, modu), None);
+    assert_eq!(multip_inv_ct(2, modu), Some(u128::multip_inv(2, modu)));
+}
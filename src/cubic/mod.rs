@@ -0,0 +1,233 @@
+//! Implements a solver for cubic modular equations.
+//!
+//! Modular cubic equations are of the form ax^3 + bx^2 + cx + d = e (mod n) where
+//! every coefficient or term is a residue class \[*\] belonging to the ring of
+//! integers Z/nZ. Modulo `n` must be a positive integer. Modulo one is trivial,
+//! every integer being congruent to 0, and [0] is its only solution.
+//!
+//! Equation is solved modulo every prime power factor of n by the root finding of
+//! module `poly` and the solutions are combined by the Chinese remainder theorem,
+//! as done for polynomial equations of any degree by type `PolyEq`.
+//! If the cubic term vanishes, the equation is solved as a quadratic equation.
+//!
+//! Solutions x, if any, are given as residue classes \[x\] such that
+//! each class is represented by smallest nonnegative integer (modulo n).
+//!
+use crate::{
+    arith::{Arith, SignCast},
+    factor::{FactorConfig, FactorError},
+    poly, Int, QuadEq, UInt,
+};
+
+/// Type for cubic equations with unsigned terms only.
+///
+/// Cubic modular equations are of the form ax^3 + bx^2 + cx + d = e (mod modu) where
+/// coefficients `a`, `b`, `c`, `d` and `e` must be nonnegative for this type. Modulo
+/// `modu` must have the same unsigned type and positive as its value.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CubicEq<T: UInt> {
+    pub a: T,
+    pub b: T,
+    pub c: T,
+    pub d: T,
+    pub e: T,
+    pub modu: T,
+}
+
+/// Type for cubic equations with unsigned modulo and signed other terms.
+///
+/// Cubic modular equations are of the form ax^3 + bx^2 + cx + d = e (mod modu) where
+/// coefficients `a`, `b`, `c`, `d` and `e` are signed for this type. Modulo `modu`
+/// must be an unsigned type at least as wide as the signed type, e.g. u32, u64 or
+/// u128 if the signed type is i32, and positive as its value.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CubicEqSigned<S: Int, T: UInt> {
+    pub a: S,
+    pub b: S,
+    pub c: S,
+    pub d: S,
+    pub e: S,
+    pub modu: T,
+}
+
+impl<T: 'static + UInt> CubicEq<T> {
+    /// Solve cubic modular equation ax^3 + bx^2 + cx + d = e (mod modu).
+    ///
+    /// Modulo a prime there are at most three solutions, but for composite modulo
+    /// the solution count can be much larger. As for quadratic equations, composite
+    /// modulo must first be factorized.
+    ///
+    /// If a % modu == 0, the equation is solved as the quadratic equation
+    /// bx^2 + cx + d = e (mod modu). Hence, if also b and c are divisible by the
+    /// modulo, every residue class is a solution if d = e (mod modu) and otherwise
+    /// there are no solutions. As for `LinEq`, every residue class is enumerated only
+    /// for moduli up to 2^20.
+    ///
+    /// If there aren't solutions, None is returned.
+    ///
+    /// # Examples
+    ///
+    /// Solve equation x^3 = 8 (mod 31)
+    ///
+    /// ```
+    /// use modular_equations::CubicEq;
+    ///
+    /// let cubic_eq = CubicEq::<u32> {a: 1, b: 0, c: 0, d: 0, e: 8, modu: 31};
+    ///
+    /// // Cube roots of unity modulo 31 are 1, 5 and 25
+    /// assert_eq!(cubic_eq.solve(), Some(vec![2, 10, 19]));
+    /// ```
+    pub fn solve(&self) -> Option<Vec<T>> {
+        // Default configuration has no timeout, hence factorization cannot fail
+        self.solve_with_config(&FactorConfig::default())
+            .unwrap_or(None)
+    }
+
+    /// Solve cubic modular equation with factorization limits `config`.
+    ///
+    /// Like `QuadEq::solve_with_config`, returns `FactorError::Timeout` or
+    /// `FactorError::Cancelled` if the factorization of the modulo doesn't complete within
+    /// the limits. Otherwise the result equals that of `solve`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use modular_equations::{CubicEq, FactorConfig};
+    ///
+    /// let config = FactorConfig {
+    ///     timeout: Some(Duration::from_secs(10)),
+    ///     ..FactorConfig::default()
+    /// };
+    /// let cubic_eq = CubicEq::<u64> {a: 1, b: 0, c: 0, d: 0, e: 1, modu: 1_000_000_007 * 91};
+    ///
+    /// assert_eq!(cubic_eq.solve_with_config(&config), Ok(cubic_eq.solve()));
+    /// ```
+    pub fn solve_with_config(&self, config: &FactorConfig) -> Result<Option<Vec<T>>, FactorError> {
+        if self.modu == T::zero() {
+            return Ok(None);
+        }
+
+        if self.a % self.modu == T::zero() {
+            let quad_eq = QuadEq {
+                a: self.b,
+                b: self.c,
+                c: self.d,
+                d: self.e,
+                modu: self.modu,
+            };
+            return quad_eq.solve_with_config(config);
+        }
+
+        let coefs = [
+            T::sub_mod(self.d, self.e, self.modu),
+            self.c % self.modu,
+            self.b % self.modu,
+            self.a % self.modu,
+        ];
+
+        poly::roots_mod(&coefs, self.modu, config)
+    }
+
+    /// Evaluate the left and right hand sides of cubic modular equation
+    /// ax^3 + bx^2 + cx + d = e (mod modu) at `x`, both reduced modulo `modu`.
+    ///
+    /// Value `x` doesn't need to be reduced modulo `modu`. For modulo zero, None is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use modular_equations::CubicEq;
+    ///
+    /// let cubic_eq = CubicEq::<u32> {a: 1, b: 0, c: 0, d: 0, e: 8, modu: 31};
+    ///
+    /// assert_eq!(cubic_eq.sides(10), Some((8, 8)));
+    /// assert_eq!(cubic_eq.sides(3), Some((27, 8)));
+    /// ```
+    pub fn sides(&self, x: T) -> Option<(T, T)> {
+        if self.modu == T::zero() {
+            return None;
+        }
+
+        let coefs = [self.d, self.c, self.b, self.a];
+
+        Some((poly::evaluate(&coefs, x, self.modu), self.e % self.modu))
+    }
+}
+
+impl<T, S> CubicEqSigned<S, T>
+where
+    S: Int + SignCast<S, T>,
+    T: 'static + UInt + TryFrom<S>,
+{
+    /// Solve cubic modular equation for signed type terms.
+    ///
+    /// Coefficients are cast to unsigned type such that they represent the smallest
+    /// nonnegative integers of their residue classes, after which the `solve` method of
+    /// the struct `CubicEq` is called. If some of the casts fails, None is returned.
+    ///
+    /// # Examples
+    ///
+    /// Solve equation x^3 - x = 0 (mod 105)
+    ///
+    /// ```
+    /// use modular_equations::CubicEqSigned;
+    ///
+    /// let cubic_eq = CubicEqSigned::<i32, u32> {a: 1, b: 0, c: -1, d: 0, e: 0, modu: 105};
+    ///
+    /// // Three roots modulo each of the primes 3, 5 and 7
+    /// assert_eq!(cubic_eq.solve().map(|x| x.len()), Some(27));
+    /// ```
+    pub fn solve(&self) -> Option<Vec<T>> {
+        self.to_unsigned()?.solve()
+    }
+
+    /// Solve cubic modular equation for signed type terms with factorization limits
+    /// `config`, casting the coefficients as in `solve`.
+    pub fn solve_with_config(&self, config: &FactorConfig) -> Result<Option<Vec<T>>, FactorError> {
+        match self.to_unsigned() {
+            Some(cubic_eq) => cubic_eq.solve_with_config(config),
+            None => Ok(None),
+        }
+    }
+
+    /// Evaluate the left and right hand sides of cubic modular equation for signed
+    /// type terms at signed `x` as the `sides` method of `CubicEq`.
+    ///
+    /// Coefficients and `x` are cast to unsigned type as in `solve`. If some of the casts
+    /// fails or modulo is zero, None is returned.
+    pub fn sides(&self, x: S) -> Option<(T, T)> {
+        if self.modu == T::zero() {
+            return None;
+        }
+
+        self.to_unsigned()?
+            .sides(S::cast_to_unsigned(x, self.modu)?)
+    }
+
+    fn to_unsigned(self) -> Option<CubicEq<T>> {
+        let mut coefs_us = [T::zero(); 5];
+
+        for (coef_us, &coef) in coefs_us
+            .iter_mut()
+            .zip([self.a, self.b, self.c, self.d, self.e].iter())
+        {
+            // Cast fails only for S::min_value()
+            *coef_us = S::cast_to_unsigned(coef, self.modu)?;
+        }
+
+        Some(CubicEq {
+            a: coefs_us[0],
+            b: coefs_us[1],
+            c: coefs_us[2],
+            d: coefs_us[3],
+            e: coefs_us[4],
+            modu: self.modu,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests;
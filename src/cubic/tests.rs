@@ -0,0 +1,257 @@
+use std::time::Duration;
+
+use crate::cubic::{CubicEq, CubicEqSigned};
+use crate::factor::{FactorConfig, FactorError};
+
+fn brute_force_sols(coefs: [u32; 5], modu: u32) -> Option<Vec<u32>> {
+    let modu_64 = modu as u64;
+    let [a, b, c, d, e] = coefs.map(|coef| coef as u64 % modu_64);
+
+    let sols: Vec<u32> = (0..modu_64)
+        .filter(|&x| {
+            let lhs =
+                ((a * x % modu_64 * x % modu_64 * x) + b * x % modu_64 * x + c * x + d) % modu_64;
+            lhs == e
+        })
+        .map(|x| x as u32)
+        .collect();
+
+    if sols.is_empty() {
+        None
+    } else {
+        Some(sols)
+    }
+}
+
+#[test]
+fn cubic_small_type_brute_force() {
+    let eqs: [[u32; 5]; 6] = [
+        [1, 0, 0, 0, 1],
+        [1, 0, 0, 0, 0],
+        [2, 3, 5, 7, 11],
+        [1, 6, 12, 8, 0],
+        [3, 0, 1, 0, 2],
+        [6, 0, 0, 2, 0],
+    ];
+
+    for modu in 2..250u32 {
+        for coefs in eqs.iter() {
+            let cubic_eq = CubicEq {
+                a: coefs[0],
+                b: coefs[1],
+                c: coefs[2],
+                d: coefs[3],
+                e: coefs[4],
+                modu,
+            };
+
+            assert_eq!(
+                cubic_eq.solve(),
+                brute_force_sols(*coefs, modu),
+                "coefs: {:?}, modu: {}",
+                coefs,
+                modu
+            );
+        }
+    }
+}
+
+#[test]
+fn cubic_vanishing_terms() {
+    // 9x^3 + 2x = 4 (mod 9) is linear, x = 2
+    let cubic_eq = CubicEq::<u8> {
+        a: 9,
+        b: 0,
+        c: 2,
+        d: 0,
+        e: 4,
+        modu: 9,
+    };
+    assert_eq!(cubic_eq.solve(), Some(vec![2]));
+
+    // Unknown vanishes completely, every residue is a solution
+    let cubic_eq = CubicEq::<u8> {
+        a: 9,
+        b: 18,
+        c: 0,
+        d: 1,
+        e: 1,
+        modu: 9,
+    };
+    assert_eq!(cubic_eq.solve(), Some((0..9).collect()));
+
+    let cubic_eq = CubicEq::<u8> {
+        a: 9,
+        b: 18,
+        c: 0,
+        d: 1,
+        e: 2,
+        modu: 9,
+    };
+    assert_eq!(cubic_eq.solve(), None);
+
+    let cubic_eq = CubicEq::<u8> {
+        a: 1,
+        b: 1,
+        c: 1,
+        d: 1,
+        e: 1,
+        modu: 1,
+    };
+    assert_eq!(cubic_eq.solve(), Some(vec![0]));
+
+    let cubic_eq = CubicEq::<u8> {
+        a: 1,
+        b: 1,
+        c: 1,
+        d: 1,
+        e: 1,
+        modu: 0,
+    };
+    assert_eq!(cubic_eq.solve(), None);
+}
+
+#[test]
+fn cubic_large_type() {
+    // x^3 = 8 (mod 1_000_000_009 * 999_999_937), cube roots of unity exist for both primes
+    let modu: u64 = 1_000_000_009 * 999_999_937;
+
+    let cubic_eq = CubicEq::<u64> {
+        a: 1,
+        b: 0,
+        c: 0,
+        d: 0,
+        e: 8,
+        modu,
+    };
+
+    match cubic_eq.solve() {
+        Some(sols) => {
+            assert_eq!(sols.len(), 9);
+
+            for x in sols.iter() {
+                let x = *x as u128;
+                assert_eq!(x * x % modu as u128 * x % modu as u128, 8);
+            }
+        }
+        None => panic!("no solutions"),
+    }
+
+    // (x - 1)(x - 2)(x - 3) with a large prime modulo
+    let modu = 170_141_183_460_469_231_731_687_303_715_884_105_727u128;
+
+    let cubic_eq = CubicEqSigned::<i128, u128> {
+        a: 1,
+        b: -6,
+        c: 11,
+        d: -6,
+        e: 0,
+        modu,
+    };
+
+    assert_eq!(cubic_eq.solve(), Some(vec![1, 2, 3]));
+}
+
+#[test]
+fn cubic_signed_type() {
+    // -x^3 + x = 0 (mod 2^10)
+    let cubic_eq = CubicEqSigned::<i16, u16> {
+        a: -1,
+        b: 0,
+        c: 1,
+        d: 0,
+        e: 0,
+        modu: 1_024,
+    };
+
+    let corr_sols = brute_force_sols([1_023, 0, 1, 0, 0], 1_024)
+        .map(|sols| sols.into_iter().map(|x| x as u16).collect::<Vec<u16>>());
+
+    assert_eq!(cubic_eq.solve(), corr_sols);
+
+    let cubic_eq = CubicEqSigned::<i16, u16> {
+        a: i16::MIN,
+        b: 0,
+        c: 1,
+        d: 0,
+        e: 0,
+        modu: 1_024,
+    };
+
+    assert_eq!(cubic_eq.solve(), None);
+}
+
+#[test]
+fn cubic_sides() {
+    for modu in 1..60u32 {
+        let coefs = [2, 3, 5, 7, 11];
+        let cubic_eq = CubicEq {
+            a: coefs[0],
+            b: coefs[1],
+            c: coefs[2],
+            d: coefs[3],
+            e: coefs[4],
+            modu,
+        };
+        let sols = brute_force_sols(coefs, modu).unwrap_or_default();
+
+        for x in 0..2 * modu {
+            let (lhs, rhs) = cubic_eq.sides(x).unwrap();
+            assert_eq!(lhs == rhs, sols.contains(&(x % modu)), "x: {}", x);
+        }
+    }
+
+    let cubic_eq = CubicEqSigned::<i16, u16> {
+        a: -1,
+        b: 0,
+        c: 1,
+        d: 0,
+        e: 5,
+        modu: 1_024,
+    };
+
+    assert_eq!(cubic_eq.sides(-2), Some((6, 5)));
+    assert_eq!(cubic_eq.sides(i16::MIN), None);
+    assert_eq!(
+        CubicEqSigned {
+            modu: 0,
+            ..cubic_eq
+        }
+        .sides(1),
+        None
+    );
+}
+
+#[test]
+fn cubic_with_factor_config_timeout() {
+    let config = FactorConfig {
+        timeout: Some(Duration::ZERO),
+        ..FactorConfig::default()
+    };
+
+    let cubic_eq = CubicEqSigned::<i128, u128> {
+        a: 1,
+        b: 0,
+        c: 0,
+        d: 1,
+        e: 0,
+        modu: 90_124_258_835_295_998_242_413_094_252_351,
+    };
+
+    assert_eq!(
+        cubic_eq.solve_with_config(&config),
+        Err(FactorError::Timeout)
+    );
+
+    // Vanishing cubic term is solved as a quadratic equation with the same limits
+    let cubic_eq = CubicEqSigned::<i128, u128> {
+        a: 0,
+        b: 1,
+        ..cubic_eq
+    };
+
+    assert_eq!(
+        cubic_eq.solve_with_config(&config),
+        Err(FactorError::Timeout)
+    );
+}
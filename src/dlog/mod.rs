@@ -0,0 +1,346 @@
+//! Implements discrete logarithm computation.
+//!
+//! Discrete logarithm of h to the base g modulo n is an exponent x s.t.
+//! g^x = h (mod n) holds. Such exponent exists only if h belongs to the
+//! cyclic subgroup generated by g in the unit group (Z/nZ)*.
+//!
+//! Baby-step giant-step algorithm finds the logarithm in a cyclic group of
+//! order N with O(sqrt(N)) group operations and memory.
+//!
+//! Modulo a prime power p^k, the order of g divides φ(p^k) = p^(k-1)(p-1) and the
+//! Pohlig-Hellman reduction splits the logarithm to its residues modulo the prime
+//! power factors r^e of the order. Each of these residues is lifted r-adically digit
+//! by digit, every digit being a logarithm in a subgroup of order r. For a composite
+//! modulo, logarithms modulo its prime power factors are combined by the Chinese
+//! remainder theorem. Powers of two need no special treatment although their unit
+//! groups are not cyclic, as the computation takes place in the cyclic subgroup <g>.
+//!
+use std::collections::HashMap;
+
+use num::integer;
+
+use crate::{
+    arith::{Arith, CoreArith},
+    crt,
+    factor::{FactorConfig, FactorError, Factors},
+    UInt,
+};
+
+/// Type for discrete logarithm problems g^x = h (mod modu).
+///
+/// Base `g` and the target `h` must be coprime to the modulo `modu`, which
+/// must be strictly larger than one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DiscreteLog<T: UInt> {
+    pub g: T,
+    pub h: T,
+    pub modu: T,
+}
+
+impl<T: 'static + UInt> DiscreteLog<T> {
+    /// Solve g^x = h (mod modu) for the smallest nonnegative exponent x.
+    ///
+    /// Group order φ(p^k) for each prime power factor p^k of the modulo is factorized
+    /// and the logarithm is found by the Pohlig-Hellman reduction, baby-step giant-step
+    /// algorithm solving the logarithms in the prime order subgroups.
+    ///
+    /// If g or h isn't coprime to the modulo or h doesn't belong to the subgroup
+    /// generated by g, None is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use modular_equations::DiscreteLog;
+    ///
+    /// let dlog = DiscreteLog::<u64> {g: 2, h: 1024, modu: 1_000_000_007};
+    ///
+    /// assert_eq!(dlog.solve(), Some(10));
+    /// ```
+    pub fn solve(&self) -> Option<T> {
+        self.solve_with_order().map(|(x, _)| x)
+    }
+
+    /// Solve g^x = h (mod modu) and return all the solutions as a pair (x, ord).
+    ///
+    /// Exponent x is the smallest solution and ord the multiplicative order of g
+    /// modulo `modu`, every solution being of the form x + t * ord for t >= 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use modular_equations::DiscreteLog;
+    ///
+    /// // 3^3 = 11 (mod 16) and order of 3 is 4
+    /// let dlog = DiscreteLog::<u32> {g: 3, h: 11, modu: 16};
+    ///
+    /// assert_eq!(dlog.solve_with_order(), Some((3, 4)));
+    /// ```
+    pub fn solve_with_order(&self) -> Option<(T, T)> {
+        // Default configuration has no timeout, hence factorization cannot fail
+        self.solve_with_order_config(&FactorConfig::default())
+            .unwrap_or(None)
+    }
+
+    /// Solve g^x = h (mod modu) like `solve_with_order` with factorization limits `config`.
+    ///
+    /// The modulo and the group orders of its prime power factors are factorized with
+    /// the limits of `config`. If some of these factorizations doesn't complete within
+    /// them, `FactorError::Timeout` or `FactorError::Cancelled` is returned. Otherwise
+    /// the result equals that of `solve_with_order`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use modular_equations::{DiscreteLog, FactorConfig};
+    ///
+    /// let config = FactorConfig {
+    ///     timeout: Some(Duration::from_secs(10)),
+    ///     ..FactorConfig::default()
+    /// };
+    /// let dlog = DiscreteLog::<u32> {g: 3, h: 11, modu: 16};
+    ///
+    /// assert_eq!(dlog.solve_with_order_config(&config), Ok(Some((3, 4))));
+    /// ```
+    pub fn solve_with_order_config(
+        &self,
+        config: &FactorConfig,
+    ) -> Result<Option<(T, T)>, FactorError> {
+        let modu = self.modu;
+
+        if modu <= T::one() {
+            return Ok(None);
+        }
+
+        let (g, h) = (self.g % modu, self.h % modu);
+
+        if T::gcd_mod(g, modu) != T::one() || T::gcd_mod(h, modu) != T::one() {
+            return Ok(None);
+        }
+
+        let (mut x, mut order) = (T::zero(), T::one());
+
+        for (prm, k) in factor_repr(modu, config)?.into_iter() {
+            let Some((x_prm, order_prm)) = discrete_log_prime_power(g, h, prm, k, config)? else {
+                return Ok(None);
+            };
+
+            // Orders divide λ(modu) < modu, hence their lcm fits
+            match crt::combine_pair(x, order, x_prm, order_prm) {
+                Some(pair) => (x, order) = pair,
+                None => return Ok(None),
+            }
+        }
+
+        Ok(Some((x, order)))
+    }
+}
+
+/// Discrete logarithm x of `h` to the base `g` modulo `modu`, g^x = h (mod modu).
+///
+/// Returned logarithm is the smallest nonnegative one, i.e. it's reduced modulo the
+/// multiplicative order of g. Both g and h must be coprime to the modulo and the modulo
+/// must be strictly larger than one. If these conditions don't hold or h doesn't belong
+/// to the subgroup generated by g, None is returned.
+///
+/// # Examples
+///
+/// ```
+/// use modular_equations::discrete_log;
+///
+/// // 3^4 = 81 = 1 (mod 16) and 3^3 = 27 = 11 (mod 16)
+/// assert_eq!(discrete_log::<u32>(3, 11, 16), Some(3));
+/// // 7 is not a power of 3 modulo 16
+/// assert_eq!(discrete_log::<u32>(3, 7, 16), None);
+/// ```
+pub fn discrete_log<T: 'static + UInt>(g: T, h: T, modu: T) -> Option<T> {
+    DiscreteLog { g, h, modu }.solve()
+}
+
+/// Prime factor representation of `num`, factorizing it with limits `config`.
+fn factor_repr<T: 'static + UInt>(
+    num: T,
+    config: &FactorConfig,
+) -> Result<Vec<(T, u8)>, FactorError> {
+    let mut factors = Factors::new(num);
+    factors.factorize_with_config(config)?;
+
+    Ok(factors.prime_factor_repr())
+}
+
+/// Discrete logarithm of `h` to the base `g` modulo `prm`^`k`.
+///
+/// Returns a pair (x, ord), where x is the smallest logarithm and ord
+/// the multiplicative order of g modulo `prm`^`k`. Both g and h must be
+/// coprime to the prime. Group order is factorized with limits `config`.
+pub fn discrete_log_prime_power<T: 'static + UInt>(
+    g: T,
+    h: T,
+    prm: T,
+    k: u8,
+    config: &FactorConfig,
+) -> Result<Option<(T, T)>, FactorError> {
+    let modu = prm.pow(k.into());
+    let (g, h) = (g % modu, h % modu);
+
+    if g % prm == T::zero() || h % prm == T::zero() {
+        return Ok(None);
+    }
+
+    // Prime factors of the group order φ(p^k) = p^(k-1)(p-1)
+    let mut group_order_factors = if prm > 2.into() {
+        factor_repr(prm - T::one(), config)?
+    } else {
+        vec![]
+    };
+    if k > 1 {
+        group_order_factors.push((prm, k - 1));
+    }
+
+    Ok(discrete_log_group(g, h, prm, modu, &group_order_factors))
+}
+
+/// Discrete logarithm modulo `modu` = `prm`^k knowing the prime factors of the group order.
+fn discrete_log_group<T: 'static + UInt>(
+    g: T,
+    h: T,
+    prm: T,
+    modu: T,
+    group_order_factors: &[(T, u8)],
+) -> Option<(T, T)> {
+    let group_order = modu / prm * (prm - T::one());
+    let order = multiplicative_order(g, modu, group_order, group_order_factors);
+
+    let (mut x, mut x_modu) = (T::zero(), T::one());
+
+    for &(r, _) in group_order_factors.iter() {
+        let mut r_power = T::one();
+        let mut e = 0;
+
+        while (order / r_power) % r == T::zero() {
+            r_power = r_power * r;
+            e += 1;
+        }
+
+        if e == 0 {
+            continue;
+        }
+
+        // Project to the subgroup of order r^e
+        let cofactor = order / r_power;
+        let g_r = T::exp_mod_unsafe(g, cofactor, modu);
+        let h_r = T::exp_mod_unsafe(h, cofactor, modu);
+
+        let x_r = discrete_log_order_prime_power(g_r, h_r, r, e, modu)?;
+
+        (x, x_modu) = crt::combine_pair(x, x_modu, x_r, r_power)?;
+    }
+
+    if T::exp_mod_unsafe(g, x, modu) != h {
+        return None;
+    }
+
+    Some((x, order))
+}
+
+/// Solve g^x = h (mod modu) when the order of `g` is `r`^`e`, `r` being a prime.
+///
+/// Exponent x = d_0 + d_1 * r + ... + d_(e-1) * r^(e-1) is lifted one digit at
+/// a time, each digit being a logarithm in the subgroup of order r.
+fn discrete_log_order_prime_power<T: UInt>(g: T, h: T, r: T, e: u8, modu: T) -> Option<T> {
+    let gamma = T::exp_mod_unsafe(g, r.pow((e - 1).into()), modu);
+    let g_inv = T::multip_inv(g, modu);
+
+    let mut x = T::zero();
+    let mut r_power = T::one();
+
+    for i in 0..e {
+        // h_i = (h * g^(-x))^(r^(e-1-i)) has order dividing r
+        let h_shifted = T::mult_mod_unsafe(h, T::exp_mod_unsafe(g_inv, x, modu), modu);
+        let h_i = T::exp_mod_unsafe(h_shifted, r.pow((e - 1 - i).into()), modu);
+
+        // Digit is zero if h_i is the identity, no need to build the baby steps
+        let digit = if h_i == T::one() {
+            T::zero()
+        } else {
+            baby_step_giant_step(gamma, h_i, r, modu)?
+        };
+
+        x = x + digit * r_power;
+        if i + 1 < e {
+            r_power = r_power * r;
+        }
+    }
+
+    Some(x)
+}
+
+/// Multiplicative order of `g` modulo `modu`, knowing the group order and its prime factors.
+fn multiplicative_order<T: UInt>(g: T, modu: T, group_order: T, factors: &[(T, u8)]) -> T {
+    let mut order = group_order;
+
+    for &(q, k) in factors.iter() {
+        for _ in 0..k {
+            if T::exp_mod_unsafe(g, order / q, modu) != T::one() {
+                break;
+            }
+            order = order / q;
+        }
+    }
+
+    order
+}
+
+/// Solve g^x = h (mod modu) for x in \[0, order), where `order` is
+/// the order of the element `g` or any multiple of it.
+///
+/// If h doesn't belong to the subgroup generated by g, None is returned.
+pub fn baby_step_giant_step<T: UInt>(g: T, h: T, order: T, modu: T) -> Option<T> {
+    let (g, h) = (g % modu, h % modu);
+
+    if order == T::zero() {
+        return None;
+    }
+
+    let mut m = integer::sqrt(order);
+    if m * m < order {
+        m = m + T::one();
+    }
+
+    let mut baby_steps: HashMap<T, T> = HashMap::new();
+    let mut gj = T::one();
+    let mut j = T::zero();
+
+    while j < m {
+        baby_steps.entry(gj).or_insert(j);
+        gj = T::mult_mod_unsafe(gj, g, modu);
+        j = j + T::one();
+    }
+
+    // Giant step g^(-m), doesn't exist if g isn't a unit
+    let giant_step = T::multip_inv(T::exp_mod_unsafe(g, m, modu), modu);
+    if giant_step == T::zero() {
+        return None;
+    }
+
+    let mut gamma = h;
+    let mut i = T::zero();
+
+    while i < m {
+        if let Some(j) = baby_steps.get(&gamma) {
+            // x = i * m + j < m^2, cannot overflow as m = ceil(sqrt(order))
+            let x = i * m + *j;
+            if x < order {
+                return Some(x);
+            }
+        }
+
+        gamma = T::mult_mod_unsafe(gamma, giant_step, modu);
+        i = i + T::one();
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests;
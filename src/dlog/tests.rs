@@ -0,0 +1,236 @@
+use std::collections::HashSet;
+use std::time::Duration;
+
+use crate::arith::Arith;
+use crate::dlog::{baby_step_giant_step, discrete_log, discrete_log_prime_power, DiscreteLog};
+use crate::factor::{FactorConfig, FactorError};
+
+#[test]
+fn bsgs_small_type() {
+    let modu = 101u8; // 2 is a primitive root mod 101
+
+    for x in 0..100u8 {
+        let h = u8::exp_mod(2, x, modu);
+
+        assert_eq!(baby_step_giant_step(2, h, 100, modu), Some(x), "h: {}", h);
+    }
+}
+
+#[test]
+fn bsgs_subgroup() {
+    let modu = 1_009u32;
+    let g = u32::exp_mod(11, 36, modu); // 11 is a primitive root, order of g is 28
+
+    for x in 0..28 {
+        let h = u32::exp_mod(g, x, modu);
+
+        assert_eq!(baby_step_giant_step(g, h, 28, modu), Some(x));
+    }
+
+    // 11 is not in the subgroup generated by g
+    assert_eq!(baby_step_giant_step(g, 11, 28, modu), None);
+}
+
+#[test]
+fn bsgs_large_type() {
+    let modu = 1_000_000_007u128; // 5 is a primitive root
+    let order = modu - 1;
+
+    for x in [0, 1, 2, 999_999, 123_456_789, order - 1].iter() {
+        let h = u128::exp_mod(5, *x, modu);
+
+        assert_eq!(baby_step_giant_step(5, h, order, modu), Some(*x));
+    }
+}
+
+#[test]
+fn bsgs_non_unit_base() {
+    assert_eq!(baby_step_giant_step(6u32, 5, 10, 12), None);
+}
+
+#[test]
+fn dlog_prime_power_small_type() {
+    // 2 is a primitive root modulo 3^k, order of 2 modulo 3^6 = 729 is 486
+    for x in 0..486u16 {
+        let h = u16::exp_mod(2, x, 729);
+
+        assert_eq!(
+            discrete_log_prime_power(2, h, 3, 6, &FactorConfig::default()),
+            Ok(Some((x, 486))),
+            "h: {}",
+            h
+        );
+    }
+}
+
+#[test]
+fn dlog_power_of_two() {
+    let modu = 1_024u32;
+
+    for g in [3u32, 5, 7, 1_023].iter() {
+        let (_, order) = discrete_log_prime_power(*g, 1, 2, 10, &FactorConfig::default())
+            .unwrap()
+            .unwrap();
+
+        for x in 0..order {
+            let h = u32::exp_mod(*g, x, modu);
+
+            assert_eq!(discrete_log(*g, h, modu), Some(x), "g: {}, h: {}", g, h);
+        }
+    }
+
+    // -1 is not a power of 5 modulo 2^k, k >= 3
+    assert_eq!(discrete_log(5u32, modu - 1, modu), None);
+}
+
+#[test]
+fn dlog_composite_brute_force() {
+    for modu in 2..200u32 {
+        for g in 2..modu {
+            if u32::gcd_mod(g, modu) != 1 {
+                continue;
+            }
+
+            let mut powers: Vec<u32> = vec![];
+            let mut h = 1;
+
+            while !powers.contains(&h) {
+                powers.push(h);
+                h = (h * g) % modu;
+            }
+
+            for h in 1..modu {
+                let corr_x = powers.iter().position(|&p| p == h).map(|x| x as u32);
+
+                if u32::gcd_mod(h, modu) != 1 {
+                    assert_eq!(discrete_log(g, h, modu), None);
+                } else {
+                    assert_eq!(
+                        discrete_log(g, h, modu),
+                        corr_x,
+                        "g: {}, h: {}, modu: {}",
+                        g,
+                        h,
+                        modu
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn dlog_large_type() {
+    // 7^10 * 2^40 * (1e9+7), 3 is coprime to the modulo
+    let modu: u128 = 7_u128.pow(10) * 2_u128.pow(40) * 1_000_000_007;
+    let x: u128 = 123_456_789_123_456_789;
+
+    let h = u128::exp_mod(3, x, modu);
+
+    match discrete_log(3, h, modu) {
+        Some(y) => assert_eq!(u128::exp_mod(3, y, modu), h),
+        None => panic!("dlog: None"),
+    }
+}
+
+#[test]
+fn dlog_invalid_arguments() {
+    assert_eq!(discrete_log(2u32, 4, 1), None);
+    assert_eq!(discrete_log(2u32, 4, 12), None);
+    assert_eq!(discrete_log(5u32, 6, 12), None);
+}
+
+#[test]
+fn dlog_type_with_order() {
+    for modu in 2..200u32 {
+        for g in 1..modu {
+            if u32::gcd_mod(g, modu) != 1 {
+                continue;
+            }
+
+            // Order of g by brute force
+            let mut order = 1;
+            while u32::exp_mod(g, order, modu) != 1 {
+                order += 1;
+            }
+
+            for x in 0..order {
+                let dlog = DiscreteLog {
+                    g,
+                    h: u32::exp_mod(g, x, modu),
+                    modu,
+                };
+
+                assert_eq!(
+                    dlog.solve_with_order(),
+                    Some((x, order)),
+                    "g: {}, x: {}, modu: {}",
+                    g,
+                    x,
+                    modu
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn dlog_type_large_type() {
+    // Prime 2^64 - 59, the largest prime factor of the group order is 5_594_472_617_641
+    let modu = u64::MAX - 58;
+    let x = 1_234_567_890_123u64;
+    let dlog = DiscreteLog {
+        g: 3,
+        h: u64::exp_mod(3, x, modu),
+        modu,
+    };
+
+    match dlog.solve_with_order() {
+        Some((x_dlog, order)) => {
+            assert_eq!(x_dlog, x % order);
+            assert_eq!(u64::exp_mod(3, order, modu), 1);
+        }
+        None => panic!("no solution"),
+    }
+}
+
+#[test]
+fn dlog_type_with_factor_config_timeout() {
+    // Modulo (2^64 - 59) * (2^61 - 1) cannot be factorized instantly
+    let modu = 18_446_744_073_709_551_557u128 * 2_305_843_009_213_693_951;
+    let dlog = DiscreteLog { g: 3, h: 9, modu };
+    let config = FactorConfig {
+        timeout: Some(Duration::ZERO),
+        ..FactorConfig::default()
+    };
+
+    assert_eq!(
+        dlog.solve_with_order_config(&config),
+        Err(FactorError::Timeout)
+    );
+    assert_eq!(
+        DiscreteLog::<u32> {
+            g: 3,
+            h: 11,
+            modu: 16
+        }
+        .solve_with_order_config(&config),
+        Ok(Some((3, 4)))
+    );
+}
+
+#[test]
+fn dlog_type_is_copyable_and_hashable() {
+    let dlog = DiscreteLog::<u32> {
+        g: 3,
+        h: 11,
+        modu: 16,
+    };
+    let dlog_copy = dlog;
+
+    assert_eq!(dlog, dlog_copy);
+    assert_eq!(dlog.solve(), dlog_copy.solve());
+
+    let problems: HashSet<DiscreteLog<u32>> = [dlog, dlog_copy].into_iter().collect();
+    assert_eq!(problems.len(), 1);
+}
@@ -1,4 +1,5 @@
-use crate::elliptic::EllipticCurve;
+use crate::arith::Barrett;
+use crate::elliptic::{self, EllipticCurve};
 
 #[test]
 fn elliptic_add_small_type() {
@@ -8,7 +9,7 @@ fn elliptic_add_small_type() {
     let mut p = EllipticCurve { x: p0.x, z: p0.z };
     let q = EllipticCurve::<u8> { x: 13, z: 10 };
 
-    p.elliptic_add(&q, &p0, modu);
+    p.elliptic_add(&q, &p0, &Barrett::new(modu));
 
     assert_eq!(p.x, 23);
     assert_eq!(p.z, 17);
@@ -22,7 +23,7 @@ fn elliptic_add_mid_type() {
     let mut p = EllipticCurve { x: p0.x, z: p0.z };
     let q = EllipticCurve::<u32> { x: 13, z: 10 };
 
-    p.elliptic_add(&q, &p0, modu);
+    p.elliptic_add(&q, &p0, &Barrett::new(modu));
 
     assert_eq!(p.x, 23);
     assert_eq!(p.z, 17);
@@ -34,7 +35,7 @@ fn elliptic_double_small_type() {
 
     let mut p = EllipticCurve::<u8> { x: 11, z: 16 };
 
-    p.elliptic_double(7, modu);
+    p.elliptic_double(7, &Barrett::new(modu));
 
     assert_eq!(p.x, 13);
     assert_eq!(p.z, 10);
@@ -46,8 +47,46 @@ fn elliptic_double_mid_type() {
 
     let mut p = EllipticCurve::<u32> { x: 11, z: 16 };
 
-    p.elliptic_double(7, modu);
+    p.elliptic_double(7, &Barrett::new(modu));
 
     assert_eq!(p.x, 13);
     assert_eq!(p.z, 10);
 }
+
+#[test]
+fn maybe_factor_with_sigma_reproducible() {
+    let modu = 1_000_003u64 * 1_000_033;
+    let multipliers = elliptic::stage_one_multipliers(10_000);
+
+    let factors: Vec<u64> = (6..40)
+        .map(|sigma| EllipticCurve::compute_maybe_factor_with_sigma(modu, sigma, &multipliers))
+        .collect();
+
+    assert!(factors
+        .iter()
+        .any(|&factor| factor == 1_000_003 || factor == 1_000_033));
+
+    for (sigma, factor) in (6..40).zip(factors) {
+        assert_eq!(
+            EllipticCurve::compute_maybe_factor_with_sigma(modu, sigma, &multipliers),
+            factor
+        );
+    }
+}
+
+#[test]
+fn stage_one_multipliers() {
+    assert_eq!(elliptic::stage_one_multipliers(0), vec![]);
+    assert_eq!(elliptic::stage_one_multipliers(1), vec![]);
+    assert_eq!(elliptic::stage_one_multipliers(10), vec![2520]);
+    assert_eq!(elliptic::stage_one_multipliers(30), vec![2_329_089_562_800]);
+
+    // lcm(1,...,50) doesn't fit into u64
+    let multipliers = elliptic::stage_one_multipliers(50);
+
+    assert_eq!(multipliers.len(), 2);
+    assert_eq!(
+        multipliers.iter().map(|&k| k as u128).product::<u128>(),
+        3_099_044_504_245_996_706_400
+    );
+}
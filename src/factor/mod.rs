@@ -1,43 +1,750 @@
 //! Implements integer factorization.
 //!
 //! The complete factorization algorithm consists of
-//! - Trial division with few of the smallest primes.
+//! - Trial division with the smallest primes, by default up to 251.
 //! - Fermat's factorization method, useful if the integer is of the form n=(a+b)*(a-b).
 //! - Primality testing, module `prime` implements Miller-Rabin and strong Baillie-PSW tests.
+//! - Shanks' square forms factorization (SQUFOF) for integers smaller than 2^62.
+//! - Pollard's rho method with Brent's cycle detection for integers fitting into 64 bits.
 //! - Lenstra elliptic-curve factorization with multiple of worker threads. Module `elliptic`
 //! implements elliptic curve arithmetic needed during factorization.
 //!
-//! Constant `MAX_WORKERS` defines the maximal thread count. This value must be at least two
-//! and preferably between three and six (by rough empirical testing). First thread will
-//! actually run the wheel factorization targeting smaller prime factors whereas other threads
-//! run the actual elliptic-curve factorization method. Thus, if the thread count has been
-//! set to one, only the wheel factorization will run.
+//! Field `workers` of `FactorConfig` defines the thread count. First thread will actually
+//! run the wheel factorization with basis {2, 3, 5, 7, 11}, targeting smaller prime
+//! factors, whereas other threads run the actual elliptic-curve factorization method. Thus
+//! the count must be at least two and smaller values are raised to it. By default the count
+//! is read from the environment variable `MODULAR_EQUATIONS_WORKERS` and if that's not set,
+//! it equals the available parallelism. Threads are kept in a pool shared by all
+//! factorizations, thus they are spawned only when none of the earlier threads is idle.
+//! Workers still running after their factorization round has finished are cancelled to free
+//! the threads.
 //!
-use std::sync::{mpsc, Arc, Mutex};
+//! With the feature `wasm` factorization is single-threaded: field `workers` is ignored and
+//! every round runs one elliptic-curve worker on the calling thread, without the wheel.
+//!
+//! Struct `FactorConfig` allows bounding the work done: count of elliptic curves per worker
+//! and factorization round, first stage bound of each curve, upper bound for the wheel
+//! factorization and a timeout after which the factorization gives up. Factorization can
+//! also be cancelled from another thread with a `CancelToken`. By default only the curve
+//! count is bounded. Finished factorizations can be kept in a `FactorCache`, shared by
+//! the configurations using it. Statistics of a factorization, such as the time spent in each
+//! stage and the method that found each prime factor, are returned by `factorize_with_stats`.
+//!
+use std::collections::HashMap;
+use std::env;
+use std::error::Error;
+use std::fmt;
+use std::mem;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{mpsc, Arc, Mutex, OnceLock};
 use std::thread;
+use std::time::Duration;
+#[cfg(not(all(feature = "wasm", target_arch = "wasm32")))]
+use std::time::Instant;
+
+#[cfg(all(feature = "wasm", target_arch = "wasm32"))]
+use crate::wasm::Instant;
+
+use num::{integer, NumCast};
+
+use crate::{
+    arith::{Arith, Barrett},
+    elliptic::{self, EllipticCurve},
+    prime, UInt,
+};
 
-use num::integer;
+/// Default bound of the trial division with the smallest primes.
+const TRIAL_DIVISION_BOUND: u32 = 251;
 
-use crate::{arith::Arith, elliptic::EllipticCurve, prime, UInt};
+/// Smallest bound of the trial division, the wheel factorization leaving out multiples of
+/// 2, 3, 5, 7 and 11 and starting after the trial division.
+const MIN_TRIAL_DIVISION_BOUND: u32 = 11;
 
-/// Thread count for elliptic curve factorization.
-/// Set between 3 and 6 (best efficiency by rough empirical testing).
-const MAX_WORKERS: usize = 5;
+/// Modulus of the wheel factorization, product of the basis primes {2, 3, 5, 7, 11}.
+const WHEEL_MODULUS: u32 = 2 * 3 * 5 * 7 * 11;
+
+/// Primes of the trial division smaller than this bound are computed once and reused.
+const SMALL_PRIMES_LIMIT: u32 = 1 << 16;
+
+/// Smallest thread count: one wheel worker and at least one elliptic curve worker.
+const MIN_WORKERS: usize = 2;
+
+/// Environment variable overriding the default thread count.
+const WORKERS_ENV_VAR: &str = "MODULAR_EQUATIONS_WORKERS";
 
 /// Max count of elliptic curves during single elliptic factorization run.
 const MAX_ELLIPTIC_CURVES: usize = 125;
 
+/// Bound of the first stage of elliptic-curve factorization, the point of a curve being
+/// multiplied by lcm(1,...,ELLIPTIC_CURVE_BOUND).
+const ELLIPTIC_CURVE_BOUND: u64 = 10_000;
+
+/// Square forms factorization is tried only for integers smaller than this bound.
+const SQUFOF_MAX_NUM: u128 = 1 << 62;
+
+/// Multipliers k of the square forms factorization, each run factorizing k*n.
+const SQUFOF_MULTIPLIERS: [u128; 16] = [
+    1,
+    3,
+    5,
+    7,
+    11,
+    3 * 5,
+    3 * 7,
+    3 * 11,
+    5 * 7,
+    5 * 11,
+    7 * 11,
+    3 * 5 * 7,
+    3 * 5 * 11,
+    3 * 7 * 11,
+    5 * 7 * 11,
+    3 * 5 * 7 * 11,
+];
+
+/// Pollard's rho method is tried only for integers smaller than this bound.
+const RHO_MAX_NUM: u128 = u64::MAX as u128;
+
+/// Max count of iterations during single Pollard's rho run.
+const RHO_MAX_ITERATIONS: usize = 1 << 20;
+
+/// Count of iterations whose differences are multiplied together before taking gcd.
+const RHO_BATCH_LEN: usize = 128;
+
+/// Pollard's rho runs are made with polynomials x^2 + c, c = 1,...,RHO_MAX_RUNS.
+const RHO_MAX_RUNS: u8 = 3;
+
+/// Configuration for integer factorization.
+///
+/// Field `max_curves` is the count of elliptic curves each worker tries during a single
+/// factorization round, `curve_bound` the first stage bound B1 of each curve,
+/// `small_prime_bound` and `trial_bound` the bounds of trial division explained below,
+/// `timeout` the time after which factorization is given up, `workers` the count of threads,
+/// including the wheel worker, `cancel` a token that stops the factorization when cancelled,
+/// `progress` a callback receiving the progress events of the factorization and `cache`
+/// a cache of earlier factorizations.
+///
+/// Trial division happens in two phases. First the number is divided by the small primes up
+/// to `small_prime_bound` (at least 11) before any other method is tried. After that the
+/// wheel worker continues trial division from this bound, in parallel with elliptic curves,
+/// by divisors up to `trial_bound` (None meaning no bound).
+///
+/// Elliptic curves rarely split numbers having only small prime factors, hence a low trial
+/// bound without a timeout may make factorization of such numbers run indefinitely.
+///
+/// Larger curve bound increases the work per curve but also the size of prime factors that
+/// a single curve is likely to find. The default bound 10_000 suits well for factors up to
+/// 20 decimal digits, larger factors of 128-bit numbers may benefit from e.g. bound 50_000.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FactorConfig {
+    pub max_curves: usize,
+    pub curve_bound: u64,
+    pub small_prime_bound: u32,
+    pub trial_bound: Option<u128>,
+    pub timeout: Option<Duration>,
+    pub workers: usize,
+    pub cancel: Option<CancelToken>,
+    pub progress: Option<ProgressCallback>,
+    pub cache: Option<FactorCache>,
+}
+
+impl Default for FactorConfig {
+    fn default() -> Self {
+        Self {
+            max_curves: MAX_ELLIPTIC_CURVES,
+            curve_bound: ELLIPTIC_CURVE_BOUND,
+            small_prime_bound: TRIAL_DIVISION_BOUND,
+            trial_bound: None,
+            timeout: None,
+            workers: default_workers(),
+            cancel: None,
+            progress: None,
+            cache: None,
+        }
+    }
+}
+
+/// Least recently used cache of factorizations.
+///
+/// Clones of the cache share their entries, hence a single cache can be set to several
+/// configurations and used from multiple threads. Factorizations of the same number are
+/// shared by all integer types. When the cache is full, the least recently used entry is
+/// replaced. Factorizations taken from the cache aren't reported to the progress callback.
+///
+/// # Examples
+///
+/// ```
+/// use modular_equations::{FactorCache, FactorConfig, QuadEq};
+///
+/// let config = FactorConfig {
+///     cache: Some(FactorCache::new(16)),
+///     ..FactorConfig::default()
+/// };
+/// let quad_eq = QuadEq::<u64> {a: 1, b: 0, c: 0, d: 4, modu: 1_000_000_007 * 91};
+///
+/// // Second solve reuses the factorization of the modulo
+/// assert_eq!(quad_eq.solve_with_config(&config), Ok(quad_eq.solve()));
+/// assert_eq!(quad_eq.solve_with_config(&config), Ok(quad_eq.solve()));
+/// ```
+#[derive(Debug, Clone)]
+pub struct FactorCache {
+    entries: Arc<Mutex<CacheEntries>>,
+}
+
+#[derive(Debug)]
+struct CacheEntries {
+    capacity: usize,
+    // Prime factors and the time of last use of each cached number
+    map: HashMap<u128, (Vec<u128>, u64)>,
+    time: u64,
+}
+
+impl FactorCache {
+    /// Construct an empty cache holding at most `capacity` factorizations.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            entries: Arc::new(Mutex::new(CacheEntries {
+                capacity,
+                map: HashMap::new(),
+                time: 0,
+            })),
+        }
+    }
+
+    /// Count of cached factorizations.
+    pub fn len(&self) -> usize {
+        self.entries.lock().map_or(0, |entries| entries.map.len())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn clear(&self) {
+        if let Ok(mut entries) = self.entries.lock() {
+            entries.map.clear();
+        }
+    }
+
+    fn get<T: UInt>(&self, num: T) -> Option<Vec<T>> {
+        let mut entries = self.entries.lock().ok()?;
+        entries.time += 1;
+
+        let time = entries.time;
+        let (factors, last_used) = entries.map.get_mut(&num.into())?;
+        *last_used = time;
+
+        // Factors are at most `num`, thus they fit into the type T
+        factors
+            .iter()
+            .map(|&factor| <T as NumCast>::from(factor))
+            .collect()
+    }
+
+    fn insert<T: UInt>(&self, num: T, factors: &[T]) {
+        let Ok(mut entries) = self.entries.lock() else {
+            return;
+        };
+
+        if entries.capacity == 0 {
+            return;
+        }
+
+        entries.time += 1;
+
+        let num = num.into();
+
+        if entries.map.len() >= entries.capacity && !entries.map.contains_key(&num) {
+            let lru_num = entries
+                .map
+                .iter()
+                .min_by_key(|(_, (_, last_used))| *last_used)
+                .map(|(lru_num, _)| *lru_num);
+
+            if let Some(lru_num) = lru_num {
+                entries.map.remove(&lru_num);
+            }
+        }
+
+        let time = entries.time;
+        let factors = factors.iter().map(|&factor| factor.into()).collect();
+        entries.map.insert(num, (factors, time));
+    }
+}
+
+impl PartialEq for FactorCache {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.entries, &other.entries)
+    }
+}
+
+impl Eq for FactorCache {}
+
+/// Token for cancelling factorizations from another thread.
+///
+/// Clones of the token share their state, cancelling one of them cancels all. A cancelled
+/// token stays cancelled, thus a new token is needed for further factorizations.
+///
+/// # Examples
+///
+/// ```
+/// use modular_equations::{CancelToken, FactorConfig, FactorError, Factors};
+///
+/// let token = CancelToken::new();
+/// let config = FactorConfig {
+///     cancel: Some(token.clone()),
+///     ..FactorConfig::default()
+/// };
+///
+/// token.cancel();
+///
+/// let mut factors = Factors::<u128>::new(90_124_258_835_295_998_242_413_094_252_351);
+/// assert_eq!(factors.factorize_with_config(&config), Err(FactorError::Cancelled));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancelToken {
+    /// Construct a token that hasn't been cancelled.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancel the factorizations using this token or any of its clones.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+}
+
+impl PartialEq for CancelToken {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.cancelled, &other.cancelled)
+    }
+}
+
+impl Eq for CancelToken {}
+
+/// Progress events of a factorization.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FactorEvent {
+    /// Trial division with the smallest primes is done, `cofactor` being left to factorize.
+    TrialDivisionDone { cofactor: u128 },
+    /// Elliptic curve worker `worker` has tried its `curve`th curve of the current round.
+    CurveDone { worker: usize, curve: usize },
+    /// Prime factor `factor` has been found.
+    FactorFound { factor: u128 },
+}
+
+/// Callback receiving the progress events of factorizations.
+///
+/// Events of the elliptic curve workers are reported from their threads, hence the callback
+/// must be thread-safe. Prime factors are reported once they are found, a factor of
+/// multiplicity k being reported k times.
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::{Arc, Mutex};
+/// use modular_equations::{FactorConfig, FactorEvent, Factors, ProgressCallback};
+///
+/// let events = Arc::new(Mutex::new(vec![]));
+/// let events_clone = Arc::clone(&events);
+///
+/// let config = FactorConfig {
+///     progress: Some(ProgressCallback::new(move |event| {
+///         events_clone.lock().unwrap().push(event);
+///     })),
+///     ..FactorConfig::default()
+/// };
+///
+/// let mut factors = Factors::<u64>::new(2 * 1_000_000_007);
+/// factors.factorize_with_config(&config).unwrap();
+///
+/// assert_eq!(
+///     *events.lock().unwrap(),
+///     vec![
+///         FactorEvent::FactorFound { factor: 2 },
+///         FactorEvent::TrialDivisionDone { cofactor: 1_000_000_007 },
+///         FactorEvent::FactorFound { factor: 1_000_000_007 },
+///     ]
+/// );
+/// ```
+#[derive(Clone)]
+pub struct ProgressCallback {
+    callback: Arc<dyn Fn(FactorEvent) + Send + Sync>,
+}
+
+impl ProgressCallback {
+    pub fn new<F>(callback: F) -> Self
+    where
+        F: Fn(FactorEvent) + Send + Sync + 'static,
+    {
+        Self {
+            callback: Arc::new(callback),
+        }
+    }
+
+    fn report(&self, event: FactorEvent) {
+        (self.callback)(event);
+    }
+}
+
+impl fmt::Debug for ProgressCallback {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "ProgressCallback")
+    }
+}
+
+impl PartialEq for ProgressCallback {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.callback, &other.callback)
+    }
+}
+
+impl Eq for ProgressCallback {}
+
+/// Factorization methods, telling which method found a prime factor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FactorMethod {
+    TrialDivision,
+    Fermat,
+    /// Cofactor left by the other methods was proven prime.
+    PrimalityTest,
+    Squfof,
+    Rho,
+    Wheel,
+    EllipticCurve,
+    /// Factorization was taken from the `FactorCache`.
+    Cache,
+}
+
+/// Statistics and diagnostics of a single factorization.
+///
+/// Field `factors` lists the prime factors in ascending order, each paired with the method
+/// that found it. Times of the stages are summed over all their runs, `worker_time` being
+/// the wall-clock time of the `worker_rounds` rounds of the wheel and elliptic curve
+/// workers and `worker_busy_time` the time the `worker_threads` threads spent working
+/// during the rounds. Field `curves` is the count of elliptic curves tried by all workers.
+///
+/// # Examples
+///
+/// ```
+/// use modular_equations::{FactorConfig, FactorMethod, Factors};
+///
+/// let mut factors = Factors::<u64>::new(2 * 1_000_000_007);
+/// let stats = factors.factorize_with_stats(&FactorConfig::default()).unwrap();
+///
+/// assert_eq!(
+///     stats.factors,
+///     vec![
+///         (2, FactorMethod::TrialDivision),
+///         (1_000_000_007, FactorMethod::PrimalityTest),
+///     ]
+/// );
+/// assert_eq!(stats.worker_rounds, 0);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FactorStats {
+    pub total_time: Duration,
+    pub trial_division_time: Duration,
+    pub fermat_time: Duration,
+    pub squfof_time: Duration,
+    pub rho_time: Duration,
+    pub worker_time: Duration,
+    pub worker_busy_time: Duration,
+    pub worker_rounds: usize,
+    pub worker_threads: usize,
+    pub curves: usize,
+    pub factors: Vec<(u128, FactorMethod)>,
+}
+
+impl FactorStats {
+    /// Share of the worker threads' time spent working during the worker rounds,
+    /// zero if there were no rounds.
+    pub fn worker_utilization(&self) -> f64 {
+        let available = self.worker_time.as_secs_f64() * self.worker_threads as f64;
+
+        if available > 0.0 {
+            (self.worker_busy_time.as_secs_f64() / available).min(1.0)
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Differences between the consecutive integers coprime to `WHEEL_MODULUS`, starting from 1.
+fn wheel_increments() -> &'static [u8] {
+    static INCREMENTS: OnceLock<Vec<u8>> = OnceLock::new();
+
+    INCREMENTS.get_or_init(|| {
+        let coprimes: Vec<u32> = (1..=WHEEL_MODULUS + 1)
+            .filter(|&k| integer::gcd(k, WHEEL_MODULUS) == 1)
+            .collect();
+
+        // Gaps between the coprimes are at most 14
+        coprimes.windows(2).map(|w| (w[1] - w[0]) as u8).collect()
+    })
+}
+
+/// Default thread count, resolved once from the environment or the available parallelism.
+fn default_workers() -> usize {
+    static WORKERS: OnceLock<usize> = OnceLock::new();
+
+    *WORKERS.get_or_init(|| {
+        let parallelism = thread::available_parallelism().map_or(MIN_WORKERS, |n| n.get());
+
+        resolve_workers(env::var(WORKERS_ENV_VAR).ok().as_deref(), parallelism)
+    })
+}
+
+/// Thread count from the value of the environment variable, falling back to `parallelism`.
+fn resolve_workers(env_value: Option<&str>, parallelism: usize) -> usize {
+    env_value
+        .and_then(|value| value.trim().parse::<usize>().ok())
+        .filter(|&workers| workers > 0)
+        .unwrap_or(parallelism)
+        .max(MIN_WORKERS)
+}
+
+/// Errors of integer factorization.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FactorError {
+    /// Factorization didn't complete within the configured timeout.
+    Timeout,
+    /// Factorization was cancelled with the configured `CancelToken`.
+    Cancelled,
+}
+
+impl fmt::Display for FactorError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FactorError::Timeout => write!(f, "factorization timed out"),
+            FactorError::Cancelled => write!(f, "factorization was cancelled"),
+        }
+    }
+}
+
+impl Error for FactorError {}
+
+/// Limits of a single factorization derived from `FactorConfig`.
+#[derive(Clone)]
+struct Limits {
+    max_curves: usize,
+    curve_bound: u64,
+    small_prime_bound: u32,
+    workers: usize,
+    trial_bound: Option<u128>,
+    deadline: Option<Instant>,
+    cancel: Option<CancelToken>,
+    progress: Option<Arc<Progress>>,
+}
+
+/// Progress reporting and statistics of a single factorization.
+struct Progress {
+    callback: Option<ProgressCallback>,
+    stats: Option<Mutex<FactorStats>>,
+    // Part of the factorized number whose prime factors haven't been reported yet
+    unreported: Mutex<u128>,
+}
+
+impl Limits {
+    fn from_config<T: UInt>(config: &FactorConfig, num: T, collect_stats: bool) -> Self {
+        let progress = (config.progress.is_some() || collect_stats).then(|| {
+            Arc::new(Progress {
+                callback: config.progress.clone(),
+                stats: collect_stats.then(|| Mutex::new(FactorStats::default())),
+                unreported: Mutex::new(num.into()),
+            })
+        });
+
+        Self {
+            max_curves: config.max_curves,
+            curve_bound: config.curve_bound,
+            small_prime_bound: config.small_prime_bound.max(MIN_TRIAL_DIVISION_BOUND),
+            workers: if cfg!(feature = "wasm") {
+                // Single-threaded configuration runs one worker on the calling thread
+                1
+            } else {
+                config.workers.max(MIN_WORKERS)
+            },
+            trial_bound: config.trial_bound,
+            deadline: config.timeout.map(|timeout| Instant::now() + timeout),
+            cancel: config.cancel.clone(),
+            progress,
+        }
+    }
+
+    fn report(&self, event: FactorEvent) {
+        if let Some(callback) = self.progress.as_ref().and_then(|p| p.callback.as_ref()) {
+            callback.report(event);
+        }
+    }
+
+    /// Update the statistics if they are collected.
+    fn update_stats<F: FnOnce(&mut FactorStats)>(&self, update: F) {
+        if let Some(stats) = self.progress.as_ref().and_then(|p| p.stats.as_ref()) {
+            if let Ok(mut stats) = stats.lock() {
+                update(&mut stats);
+            }
+        }
+    }
+
+    fn take_stats(&self) -> Option<FactorStats> {
+        let stats = self.progress.as_ref()?.stats.as_ref()?;
+        stats
+            .lock()
+            .ok()
+            .map(|mut stats| std::mem::take(&mut *stats))
+    }
+
+    /// Report prime factors `factors` found by `method`, leaving out those found already
+    /// by another worker.
+    fn report_factors<T: UInt>(&self, factors: &[T], method: FactorMethod) {
+        let Some(progress) = &self.progress else {
+            return;
+        };
+
+        for factor in factors.iter() {
+            let factor = (*factor).into();
+
+            let is_new = match progress.unreported.lock() {
+                Ok(mut unreported) if *unreported % factor == 0 => {
+                    *unreported /= factor;
+                    true
+                }
+                _ => false,
+            };
+
+            if is_new {
+                self.report(FactorEvent::FactorFound { factor });
+                self.update_stats(|stats| stats.factors.push((factor, method)));
+            }
+        }
+    }
+
+    /// Error if the factorization has been cancelled or its deadline has passed.
+    fn check(&self) -> Result<(), FactorError> {
+        if matches!(&self.cancel, Some(token) if token.is_cancelled()) {
+            Err(FactorError::Cancelled)
+        } else if matches!(self.deadline, Some(deadline) if Instant::now() >= deadline) {
+            Err(FactorError::Timeout)
+        } else {
+            Ok(())
+        }
+    }
+
+    fn is_stopped(&self) -> bool {
+        self.check().is_err()
+    }
+}
+
+type Job = Box<dyn FnOnce() + Send>;
+
+/// Pool of threads reused across factorizations.
+///
+/// A new thread is spawned only if a job is submitted while all threads are busy, hence the
+/// pool grows to the largest count of simultaneously running jobs.
+struct WorkerPool {
+    sender: Mutex<mpsc::Sender<Job>>,
+    receiver: Arc<Mutex<mpsc::Receiver<Job>>>,
+    // Count of idle threads minus the count of jobs waiting in the channel
+    idle: Arc<AtomicUsize>,
+}
+
+impl WorkerPool {
+    fn new() -> Self {
+        let (sender, receiver) = mpsc::channel();
+
+        Self {
+            sender: Mutex::new(sender),
+            receiver: Arc::new(Mutex::new(receiver)),
+            idle: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    /// Pool shared by all factorizations.
+    fn global() -> &'static Self {
+        static POOL: OnceLock<WorkerPool> = OnceLock::new();
+
+        POOL.get_or_init(Self::new)
+    }
+
+    /// Run `job` on an idle thread of the pool or on a new thread if none is idle.
+    fn execute(&self, job: Job) {
+        let reserved = self
+            .idle
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |idle| {
+                idle.checked_sub(1)
+            })
+            .is_ok();
+
+        if !reserved {
+            self.spawn_thread();
+        }
+
+        if let Ok(sender) = self.sender.lock() {
+            if sender.send(job).is_err() {}
+        }
+    }
+
+    fn spawn_thread(&self) {
+        let receiver = Arc::clone(&self.receiver);
+        let idle = Arc::clone(&self.idle);
+
+        thread::spawn(move || loop {
+            let job = match receiver.lock() {
+                Ok(receiver_guard) => receiver_guard.recv(),
+                _ => break,
+            };
+
+            match job {
+                Ok(job) => job(),
+                _ => break,
+            }
+
+            idle.fetch_add(1, Ordering::SeqCst);
+        });
+    }
+}
+
 struct MaybeFactors<T: UInt> {
     num: T,
-    factors: Vec<(T, bool)>,
+    // Factors, whether they are surely primes and the methods that found them
+    factors: Vec<(T, bool, FactorMethod)>,
 }
 
+/// Prime factorization of a natural number `num`.
+///
+/// Field `factors` contains the prime factors of `num`, each factor repeated according
+/// to its multiplicity, after a call to `factorize` or `factorize_with_config`.
+///
+/// # Examples
+///
+/// ```
+/// use modular_equations::Factors;
+///
+/// let mut factors = Factors::<u64>::new(2 * 3 * 3 * 1_000_000_007);
+/// factors.factorize();
+///
+/// assert_eq!(factors.factors, vec![2, 3, 3, 1_000_000_007]);
+/// assert_eq!(
+///     factors.iter().collect::<Vec<_>>(),
+///     vec![(2, 1), (3, 2), (1_000_000_007, 1)]
+/// );
+/// ```
 pub struct Factors<T: UInt> {
     pub num: T,
     pub factors: Vec<T>,
 }
 
 impl<T: 'static + UInt> Factors<T> {
+    /// Construct factorization of `num`, its factors are found by calling `factorize`.
     pub fn new(num: T) -> Factors<T> {
         Self {
             num,
@@ -53,7 +760,55 @@ impl<T: 'static + UInt> Factors<T> {
     ///
     /// Resulted factors can be used to recover the original natural
     /// number `num` via the prime factor representation.
+    ///
+    /// # Panics
+    ///
+    /// If `self.num` is smaller than two.
     pub fn factorize(&mut self) {
+        // Default configuration has no timeout, hence this cannot fail
+        if self
+            .factorize_with_config(&FactorConfig::default())
+            .is_err()
+        {
+            unreachable!("factorization without timeout completes");
+        }
+    }
+
+    /// Factor `self.num` to its prime factors within the limits of `config`.
+    ///
+    /// If the timeout of the configuration is exceeded, `FactorError::Timeout`
+    /// is returned and the `factors` field is left in an incomplete state. Similarly,
+    /// `FactorError::Cancelled` is returned if the cancel token gets cancelled.
+    ///
+    /// # Panics
+    ///
+    /// If `self.num` is smaller than two.
+    pub fn factorize_with_config(&mut self, config: &FactorConfig) -> Result<(), FactorError> {
+        self.factorize_with_limits(config, false).map(|_| ())
+    }
+
+    /// Factor `self.num` like `factorize_with_config`, returning statistics of the
+    /// factorization.
+    ///
+    /// Statistics of a factorization taken from the cache have only the total time and
+    /// the factors, all of them found by `FactorMethod::Cache`.
+    ///
+    /// # Panics
+    ///
+    /// If `self.num` is smaller than two.
+    pub fn factorize_with_stats(
+        &mut self,
+        config: &FactorConfig,
+    ) -> Result<FactorStats, FactorError> {
+        self.factorize_with_limits(config, true)
+            .map(|stats| stats.unwrap_or_default())
+    }
+
+    fn factorize_with_limits(
+        &mut self,
+        config: &FactorConfig,
+        collect_stats: bool,
+    ) -> Result<Option<FactorStats>, FactorError> {
         if self.num <= T::one() {
             // Should never go here if program logic ok
             panic!("Cannot factorize natural number smaller than two");
@@ -61,12 +816,50 @@ impl<T: 'static + UInt> Factors<T> {
 
         self.factors.clear();
 
-        let num = self.factorize_trial(self.num);
+        let start = Instant::now();
+
+        if let Some(factors) = config.cache.as_ref().and_then(|cache| cache.get(self.num)) {
+            self.factors = factors;
+
+            let stats = collect_stats.then(|| FactorStats {
+                total_time: start.elapsed(),
+                factors: self
+                    .factors
+                    .iter()
+                    .map(|&factor| (factor.into(), FactorMethod::Cache))
+                    .collect(),
+                ..FactorStats::default()
+            });
+            return Ok(stats);
+        }
+
+        let limits = Limits::from_config(config, self.num, collect_stats);
+
+        let num = self.factorize_trial(self.num, limits.small_prime_bound);
+
+        limits.update_stats(|stats| stats.trial_division_time = start.elapsed());
+        limits.report_factors(&self.factors, FactorMethod::TrialDivision);
+        limits.report(FactorEvent::TrialDivisionDone {
+            cofactor: num.into(),
+        });
 
-        self.factorize_until_completed(num);
+        self.factorize_until_completed(num, &limits)?;
 
         // Factorize_elliptic step might have resulted extra factors, prune them now
-        self.prune_duplicate_factors()
+        self.prune_duplicate_factors();
+
+        if let Some(cache) = &config.cache {
+            cache.insert(self.num, &self.factors);
+        }
+
+        let stats = limits.take_stats().map(|mut stats| {
+            stats.total_time = start.elapsed();
+            stats.worker_threads = limits.workers;
+            stats.factors.sort_by_key(|&(factor, _)| factor);
+            stats
+        });
+
+        Ok(stats)
     }
 
     /// Get the prime factor representation for the natural number `num`:
@@ -112,41 +905,320 @@ impl<T: 'static + UInt> Factors<T> {
         prm_factor_repr
     }
 
-    fn factorize_until_completed(&mut self, mut num: T) {
+    /// Iterator over the pairs (prm_i, k_i) of the prime factor representation.
+    ///
+    /// Pairs are the same as returned by `prime_factor_repr`, hence the `factorize`
+    /// method must have been called prior calling this.
+    pub fn iter(&self) -> std::vec::IntoIter<(T, u8)> {
+        self.prime_factor_repr().into_iter()
+    }
+
+    /// Count of the positive divisors τ(n) = (k_1+1) * ... * (k_m+1) of `num`.
+    ///
+    /// Like `prime_factor_repr`, requires that the `factorize` method has been called.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use modular_equations::Factors;
+    ///
+    /// // 360 = 2^3 * 3^2 * 5
+    /// let mut factors = Factors::<u32>::new(360);
+    /// factors.factorize();
+    ///
+    /// assert_eq!(factors.tau(), 24);
+    /// assert_eq!(factors.sigma(1), Some(1170));
+    /// assert_eq!(factors.radical(), 30);
+    /// ```
+    pub fn tau(&self) -> T {
+        // Divisor count never exceeds `num`, thus the product doesn't overflow
+        self.iter()
+            .fold(T::one(), |tau, (_, k)| tau * (k + 1).into())
+    }
+
+    /// Sum of the `k`th powers of the positive divisors σ_k(n) of `num`.
+    ///
+    /// Computed as the product of the sums 1 + p_i^k + ... + p_i^(k*k_i) over the prime
+    /// factors, σ_0 being the divisor count τ(n) and σ_1 the sum of the divisors. Returns
+    /// None if the value doesn't fit into the type T. Requires that the `factorize` method
+    /// has been called.
+    pub fn sigma(&self, k: u32) -> Option<T> {
+        self.iter().try_fold(T::one(), |sigma, (prm, count)| {
+            let prm_power = num::checked_pow(prm, k as usize)?;
+
+            let mut term = T::one();
+            let mut sum = T::one();
+
+            for _ in 0..count {
+                term = term.checked_mul(&prm_power)?;
+                sum = sum.checked_add(&term)?;
+            }
+
+            sigma.checked_mul(&sum)
+        })
+    }
+
+    /// Radical rad(n) = p_1 * ... * p_m of `num`, the product of its distinct prime factors.
+    ///
+    /// Requires that the `factorize` method has been called.
+    pub fn radical(&self) -> T {
+        self.iter().fold(T::one(), |rad, (prm, _)| rad * prm)
+    }
+
+    fn factorize_until_completed(
+        &mut self,
+        mut num: T,
+        limits: &Limits,
+    ) -> Result<(), FactorError> {
         while num > T::one() {
+            limits.check()?;
+
+            let factor_count = self.factors.len();
+            let start = Instant::now();
             num = self.factorize_fermat(num, 2);
 
+            limits.update_stats(|stats| stats.fermat_time += start.elapsed());
+            limits.report_factors(&self.factors[factor_count..], FactorMethod::Fermat);
+
             if num == T::one() {
                 break;
             }
 
             if prime::is_odd_prime(num) {
                 self.factors.push(num);
+                limits.report_factors(&[num], FactorMethod::PrimalityTest);
                 break;
             }
 
-            num = self.factorize_elliptic(num);
+            if num.into() < SQUFOF_MAX_NUM {
+                let start = Instant::now();
+                let factor = Self::squfof(num);
+                limits.update_stats(|stats| stats.squfof_time += start.elapsed());
+
+                if let Some(factor) = factor {
+                    self.push_factor(factor, false, FactorMethod::Squfof, limits)?;
+                    self.push_factor(num / factor, false, FactorMethod::Squfof, limits)?;
+                    break;
+                }
+            }
+
+            if num.into() <= RHO_MAX_NUM {
+                let start = Instant::now();
+                let factor = (1..=RHO_MAX_RUNS).find_map(|c| Self::rho(num, c.into()));
+                limits.update_stats(|stats| stats.rho_time += start.elapsed());
+
+                if let Some(factor) = factor {
+                    self.push_factor(factor, false, FactorMethod::Rho, limits)?;
+                    self.push_factor(num / factor, false, FactorMethod::Rho, limits)?;
+                    break;
+                }
+            }
+
+            num = self.factorize_elliptic(num, limits)?;
         }
+
+        Ok(())
     }
 
-    fn factorize_trial(&mut self, mut num: T) -> T {
-        static PRIMES: [u8; 54] = [
-            2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61, 67, 71, 73, 79, 83,
-            89, 97, 101, 103, 107, 109, 113, 127, 131, 137, 139, 149, 151, 157, 163, 167, 173, 179,
-            181, 191, 193, 197, 199, 211, 223, 227, 229, 233, 239, 241, 251,
-        ];
+    /// Shanks' square forms factorization of an odd composite `num` that isn't a square.
+    ///
+    /// Continued fraction expansion of sqrt(k*num) is iterated until a square form is found,
+    /// after which the reduction cycle of its square root form gives a factor of `num`.
+    /// Returns a nontrivial factor of `num` or None if none of the multipliers k succeeded.
+    fn squfof(num: T) -> Option<T> {
+        let n: u128 = num.into();
+        let max_iterations = 6 * integer::sqrt(2 * integer::sqrt(n));
+
+        for k in SQUFOF_MULTIPLIERS.iter() {
+            let d = k * n;
+            let p0 = integer::sqrt(d);
+
+            let (mut p, mut p_prev) = (p0, p0);
+            let (mut q, mut q_prev) = (d - p0 * p0, 1u128);
+
+            if q == 0 {
+                // k*num is a square, num itself isn't one
+                continue;
+            }
 
-        for prm in PRIMES.iter() {
-            let prime = (*prm).into();
+            let mut r = 0;
+            let mut found = false;
+
+            // Forward cycle: search a square form, terms P_i and Q_i stay below 2*sqrt(d)
+            for j in 2..max_iterations {
+                let b = (p0 + p) / q;
+                p = b * q - p;
+
+                let q_next = q_prev.wrapping_add(b.wrapping_mul(p_prev.wrapping_sub(p)));
+                (q_prev, q) = (q, q_next);
+
+                r = integer::sqrt(q);
+
+                if j & 1 == 0 && r * r == q {
+                    found = true;
+                    break;
+                }
+                p_prev = p;
+            }
+
+            if !found {
+                continue;
+            }
+
+            // Reverse cycle: start from the square root form and iterate until P repeats
+            let b = (p0 - p) / r;
+            p += b * r;
+            q_prev = r;
+            q = (d - p * p) / r;
+
+            for _ in 0..max_iterations {
+                let b = (p0 + p) / q;
+                p_prev = p;
+                p = b * q - p;
+
+                let q_next = q_prev.wrapping_add(b.wrapping_mul(p_prev.wrapping_sub(p)));
+                (q_prev, q) = (q, q_next);
+
+                if p == p_prev {
+                    break;
+                }
+            }
+
+            let factor = integer::gcd(n, q_prev);
+
+            if factor > 1 && factor < n {
+                // Factor divides `num`, thus it fits into the type T
+                return <T as NumCast>::from(factor);
+            }
+        }
+
+        None
+    }
+
+    /// Pollard's rho method with Brent's cycle detection and pseudorandom polynomial x^2 + `c`.
+    ///
+    /// Returns a nontrivial factor of `num` or None if the search failed, either because the
+    /// iteration bound was exceeded or all factors were found at once. Wider types whose
+    /// `num` fits into u64 are run as u64, as Barrett reduction is fast only up to 64 bits.
+    fn rho(num: T, c: T) -> Option<T> {
+        if mem::size_of::<T>() > mem::size_of::<u64>() {
+            if let (Some(num), Some(c)) = (num.to_u64(), c.to_u64()) {
+                return Factors::<u64>::rho(num, c).and_then(<T as NumCast>::from);
+            }
+        }
+
+        let barrett = Barrett::new(num);
+        let next = |x: T| T::add_mod(barrett.mult_mod(x, x), c, num);
+        let diff = |x: T, y: T| if x > y { x - y } else { y - x };
+
+        let mut y: T = 2.into();
+        let (mut x, mut y_saved) = (y, y);
+        let (mut prod, mut gcd) = (T::one(), T::one());
+        let mut r = 1;
+
+        while gcd == T::one() {
+            if r > RHO_MAX_ITERATIONS {
+                return None;
+            }
+            x = y;
+
+            for _ in 0..r {
+                y = next(y);
+            }
+
+            let mut k = 0;
+
+            while k < r && gcd == T::one() {
+                y_saved = y;
+
+                for _ in 0..RHO_BATCH_LEN.min(r - k) {
+                    y = next(y);
+                    prod = barrett.mult_mod(prod, diff(x, y));
+                }
+
+                gcd = T::gcd_mod(prod, num);
+                k += RHO_BATCH_LEN;
+            }
+
+            r <<= 1;
+        }
+
+        if gcd == num {
+            // Batch went past the factor, repeat it one iteration at a time
+            gcd = T::one();
+
+            for _ in 0..RHO_BATCH_LEN {
+                y_saved = next(y_saved);
+                gcd = T::gcd_mod(diff(x, y_saved), num);
+
+                if gcd > T::one() {
+                    break;
+                }
+            }
+        }
+
+        if gcd > T::one() && gcd < num {
+            Some(gcd)
+        } else {
+            None
+        }
+    }
+
+    /// Push `factor` found by `method`, factorizing it first if it isn't a prime.
+    fn push_factor(
+        &mut self,
+        factor: T,
+        is_sure_prime: bool,
+        method: FactorMethod,
+        limits: &Limits,
+    ) -> Result<(), FactorError> {
+        if is_sure_prime || prime::is_odd_prime(factor) {
+            self.factors.push(factor);
+            limits.report_factors(&[factor], method);
+        } else {
+            // Factor must be a power of prime or product of several primes
+            let mut factors_inner = Factors::new(factor);
+            factors_inner.factorize_until_completed(factor, limits)?;
+
+            for new_factor in factors_inner.factors {
+                self.factors.push(new_factor);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Divide `num` with the primes up to `bound`, returning the part left undivided.
+    fn factorize_trial(&mut self, mut num: T, bound: u32) -> T {
+        static SMALL_PRIMES: OnceLock<Vec<u32>> = OnceLock::new();
+
+        let small_primes =
+            SMALL_PRIMES.get_or_init(|| prime::primes_in_range(2..SMALL_PRIMES_LIMIT).collect());
+
+        let primes = small_primes
+            .iter()
+            .copied()
+            .take_while(|&prm| prm <= bound)
+            .chain(prime::primes_in_range(
+                SMALL_PRIMES_LIMIT..bound.saturating_add(1),
+            ));
+
+        for prm in primes {
+            let prime = match <T as NumCast>::from(prm) {
+                Some(prime) if prime <= num / prime => prime,
+                _ => {
+                    // No prime factors smaller than `prm`, hence `num` is one or a prime
+                    if num > T::one() {
+                        self.factors.push(num);
+                    }
+                    return T::one();
+                }
+            };
 
             while num % prime == T::zero() {
                 self.factors.push(prime);
                 num = num / prime;
             }
-
-            if num == T::one() {
-                break;
-            }
         }
 
         num
@@ -207,51 +1279,94 @@ impl<T: 'static + UInt> Factors<T> {
         num
     }
 
-    fn factorize_elliptic(&mut self, mut num: T) -> T {
-        let mut ec_factors: Vec<(T, bool)> = Vec::new();
+    fn factorize_elliptic(&mut self, mut num: T, limits: &Limits) -> Result<T, FactorError> {
+        let mut ec_factors: Vec<(T, bool, FactorMethod)> = Vec::new();
 
-        num = self.spawn_workers(num, &mut ec_factors);
+        num = self.spawn_workers(num, &mut ec_factors, limits);
 
-        for (ec_factor, is_sure_prime) in ec_factors {
-            if is_sure_prime || prime::is_odd_prime(ec_factor) {
-                self.factors.push(ec_factor);
-            } else {
-                // Factor must be a power of prime or product of several primes
-                let mut factors_inner = Factors::new(ec_factor);
-                factors_inner.factorize_until_completed(ec_factor);
-
-                for new_factor in factors_inner.factors {
-                    self.factors.push(new_factor);
-                }
-            }
+        for (ec_factor, is_sure_prime, method) in ec_factors {
+            self.push_factor(ec_factor, is_sure_prime, method, limits)?;
         }
 
-        num
+        Ok(num)
     }
 
-    fn spawn_workers(&self, num: T, factors: &mut Vec<(T, bool)>) -> T {
+    fn spawn_workers(
+        &self,
+        num: T,
+        factors: &mut Vec<(T, bool, FactorMethod)>,
+        limits: &Limits,
+    ) -> T {
         let (sender, receiver) = mpsc::channel();
+        let start = Instant::now();
 
         let maybe_factors_mtx = Arc::new(Mutex::new(MaybeFactors {
             num,
             factors: Vec::new(),
         }));
+        let cancelled = Arc::new(AtomicBool::new(false));
+        // Summed running time and count of the workers that have finished
+        let finished = Arc::new(Mutex::new((Duration::ZERO, 0)));
+
+        // Without threads the wheel worker is left out as it would run until completion
+        let first_worker: usize = if cfg!(feature = "wasm") { 1 } else { 0 };
 
-        for worker in 0..MAX_WORKERS {
+        for worker in first_worker..first_worker + limits.workers {
             let sender = sender.clone();
             let maybe_factors_mtx_clone = Arc::clone(&maybe_factors_mtx);
+            let cancelled = Arc::clone(&cancelled);
+            let finished = Arc::clone(&finished);
+            let limits = limits.clone();
+
+            let job: Job = Box::new(move || {
+                let start = Instant::now();
 
-            thread::spawn(move || {
                 if worker == 0 {
                     // Try to find smaller factors with wheel factorization
-                    Self::wheel_worker(maybe_factors_mtx_clone, num, sender);
+                    Self::wheel_worker(maybe_factors_mtx_clone, num, sender, &cancelled, limits);
                 } else {
-                    Self::elliptic_worker(maybe_factors_mtx_clone, num, sender);
+                    Self::elliptic_worker(
+                        maybe_factors_mtx_clone,
+                        num,
+                        sender,
+                        &cancelled,
+                        worker,
+                        limits,
+                    );
+                }
+
+                if let Ok(mut finished) = finished.lock() {
+                    finished.0 += start.elapsed();
+                    finished.1 += 1;
                 }
             });
+
+            if cfg!(feature = "wasm") {
+                job();
+            } else {
+                WorkerPool::global().execute(job);
+            }
+        }
+
+        let result = receiver.recv();
+
+        // Result of this round is ready, release the threads of the remaining workers
+        cancelled.store(true, Ordering::Relaxed);
+
+        let elapsed = start.elapsed();
+
+        if let Ok(finished) = finished.lock() {
+            // Workers still running have been busy during the whole round
+            let running = limits.workers.saturating_sub(finished.1) as u32;
+
+            limits.update_stats(|stats| {
+                stats.worker_rounds += 1;
+                stats.worker_time += elapsed;
+                stats.worker_busy_time += finished.0 + elapsed * running;
+            });
         }
 
-        match receiver.recv() {
+        match result {
             Ok(completed) => {
                 let maybe_factors_guard = match maybe_factors_mtx.lock() {
                     Ok(mtx_guard) => mtx_guard,
@@ -288,11 +1403,19 @@ impl<T: 'static + UInt> Factors<T> {
         maybe_factors: Arc<Mutex<MaybeFactors<T>>>,
         mut num: T,
         sender: mpsc::Sender<bool>,
+        cancelled: &AtomicBool,
+        worker: usize,
+        limits: Limits,
     ) {
+        let multipliers = elliptic::stage_one_multipliers(limits.curve_bound);
         let mut curve_count = 1;
 
-        while num > T::one() && curve_count <= MAX_ELLIPTIC_CURVES {
-            let maybe_factor = EllipticCurve::compute_maybe_factor_from_curve(num);
+        while num > T::one()
+            && curve_count <= limits.max_curves
+            && !limits.is_stopped()
+            && !cancelled.load(Ordering::Relaxed)
+        {
+            let maybe_factor = EllipticCurve::compute_maybe_factor_from_curve(num, &multipliers);
 
             if maybe_factor > T::one() && maybe_factor < num {
                 let mut factors_guard = match maybe_factors.lock() {
@@ -308,10 +1431,14 @@ impl<T: 'static + UInt> Factors<T> {
                 } else {
                     num = num / maybe_factor;
                     factors_guard.num = num;
-                    factors_guard.factors.push((maybe_factor, false));
+                    factors_guard
+                        .factors
+                        .push((maybe_factor, false, FactorMethod::EllipticCurve));
 
                     if prime::is_odd_prime(num) {
-                        factors_guard.factors.push((num, true));
+                        factors_guard
+                            .factors
+                            .push((num, true, FactorMethod::EllipticCurve));
                         num = T::one();
                         factors_guard.num = num;
                     }
@@ -328,7 +1455,9 @@ impl<T: 'static + UInt> Factors<T> {
                 if maybe_factor == factors_guard.num {
                     num = T::one();
                     factors_guard.num = num;
-                    factors_guard.factors.push((maybe_factor, true));
+                    factors_guard
+                        .factors
+                        .push((maybe_factor, true, FactorMethod::EllipticCurve));
                 } else {
                     num = factors_guard.num;
                 }
@@ -339,6 +1468,11 @@ impl<T: 'static + UInt> Factors<T> {
                 }
             }
 
+            limits.report(FactorEvent::CurveDone {
+                worker,
+                curve: curve_count,
+            });
+            limits.update_stats(|stats| stats.curves += 1);
             curve_count += 1;
         }
 
@@ -349,21 +1483,33 @@ impl<T: 'static + UInt> Factors<T> {
         maybe_factors: Arc<Mutex<MaybeFactors<T>>>,
         mut num: T,
         sender: mpsc::Sender<bool>,
+        cancelled: &AtomicBool,
+        limits: Limits,
     ) {
-        // Use basis {2, 3, 5, 7}
-        let wheel_inc: [u8; 48] = [
-            2, 4, 2, 4, 6, 2, 6, 4, 2, 4, 6, 6, 2, 6, 4, 2, 6, 4, 6, 8, 4, 2, 4, 2, 4, 8, 6, 4, 6,
-            2, 4, 6, 2, 6, 6, 4, 2, 4, 6, 2, 6, 4, 2, 4, 2, 10, 2, 10,
-        ];
+        // Start search after the trial division, from the first wheel candidate larger than
+        // WHEEL_MODULUS*m + 1 not exceeding the trial division bound
+        let wheel_start = WHEEL_MODULUS * ((limits.small_prime_bound - 1) / WHEEL_MODULUS) + 1;
 
-        let mut k = 221.into(); // Start search from 48th prime 223 (221 + first wheel inc)
+        let mut k: T = match <T as NumCast>::from(wheel_start) {
+            Some(k) => k,
+            None => return,
+        };
 
-        for wheel in wheel_inc.iter().cycle() {
+        for (j, wheel) in wheel_increments().iter().cycle().enumerate() {
             k = k + (*wheel).into();
 
+            if matches!(limits.trial_bound, Some(bound) if Into::<u128>::into(k) > bound)
+                || (j & 4095 == 0 && (limits.is_stopped() || cancelled.load(Ordering::Relaxed)))
+            {
+                // Leave the rest for elliptic workers, they will report the result
+                return;
+            }
+
             if k > num / k {
                 if let Ok(mut factors_guard) = maybe_factors.lock() {
-                    factors_guard.factors.push((num, false));
+                    factors_guard
+                        .factors
+                        .push((num, false, FactorMethod::Wheel));
                     num = T::one();
                     factors_guard.num = num;
                 }
@@ -386,7 +1532,7 @@ impl<T: 'static + UInt> Factors<T> {
                     num = num / k;
 
                     factors_guard.num = num;
-                    factors_guard.factors.push((k, true));
+                    factors_guard.factors.push((k, true, FactorMethod::Wheel));
 
                     if num % k != T::zero() {
                         break;
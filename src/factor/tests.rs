@@ -1,4 +1,8 @@
-use std::sync::{mpsc, Arc, Mutex};
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc, Barrier, Mutex};
+use std::thread;
+use std::time::Duration;
 
 use crate::{factor, UInt};
 
@@ -67,7 +71,7 @@ fn factorize_trial_small_composites() {
         factors.num = *num;
         factors.factors.clear();
 
-        let num_back = factors.factorize_trial(*num);
+        let num_back = factors.factorize_trial(*num, 251);
 
         assert_eq!(num_back, 1);
         compare_arrays(&factors.factors, corr_facs);
@@ -109,13 +113,46 @@ fn factorize_trial_large_composites() {
         factors.num = *num;
         factors.factors.clear();
 
-        let num_back = factors.factorize_trial(*num);
+        let num_back = factors.factorize_trial(*num, 251);
 
         assert_eq!(num_back, 1);
         compare_arrays(&factors.factors, corr_facs);
     }
 }
 
+#[test]
+fn factorize_trial_with_bound() {
+    let mut factors = factor::Factors::new(2u64);
+
+    // 2^3 * 257 * 263 * 997 * 65_537 * 70_001
+    let num = 8 * 257 * 263 * 997 * 65_537 * 70_001;
+
+    let test_cases: [(u32, u64, &[u64]); 4] = [
+        (251, num / 8, &[2, 2, 2]),
+        (1_000, 65_537 * 70_001, &[2, 2, 2, 257, 263, 997]),
+        (70_000, 1, &[2, 2, 2, 257, 263, 997, 65_537, 70_001]),
+        (u32::MAX, 1, &[2, 2, 2, 257, 263, 997, 65_537, 70_001]),
+    ];
+
+    for (bound, corr_num_back, corr_factors) in test_cases.iter() {
+        factors.num = num;
+        factors.factors.clear();
+
+        assert_eq!(factors.factorize_trial(num, *bound), *corr_num_back);
+        compare_arrays(&factors.factors, corr_factors);
+    }
+
+    let config = factor::FactorConfig {
+        small_prime_bound: 0,
+        ..factor::FactorConfig::default()
+    };
+
+    // Bound is raised to the smallest allowed one
+    factors.num = 3 * 5 * 7 * 11 * 13 * 1_000_003;
+    assert_eq!(factors.factorize_with_config(&config), Ok(()));
+    compare_arrays(&factors.factors, &[3, 5, 7, 11, 13, 1_000_003]);
+}
+
 #[test]
 fn factorize_fermat_composites() {
     let mut factors = factor::Factors::new(2u128);
@@ -227,12 +264,20 @@ fn wheel_factorization_as_worker() {
 
     let maybe_factors = Arc::new(Mutex::new(factor::MaybeFactors {
         num: test_num,
-        factors: Vec::<(u64, bool)>::new(),
+        factors: Vec::<(u64, bool, factor::FactorMethod)>::new(),
     }));
 
     let maybe_factors_cln = Arc::clone(&maybe_factors);
 
-    factor::Factors::wheel_worker(maybe_factors_cln, test_num, tx);
+    let limits = factor::Limits::from_config(&factor::FactorConfig::default(), test_num, false);
+
+    factor::Factors::wheel_worker(
+        maybe_factors_cln,
+        test_num,
+        tx,
+        &AtomicBool::new(false),
+        limits,
+    );
 
     match rx.recv() {
         Ok(true) => {
@@ -326,6 +371,64 @@ fn factorize_semiprimes() {
     }
 }
 
+#[test]
+fn squfof_semiprimes() {
+    let test_cases: [(u64, [u64; 2]); 5] = [
+        (244_334_639, [9199, 26_561]),
+        (2_854_159_729_781, [718_433, 3_972_757]),
+        (25_645_121_643_901_801, [5_394_769, 4_753_701_529]),
+        (998_244_359_987_710_471, [998_244_353, 1_000_000_007]),
+        (4_030_000_044_400_000_081, [1_300_000_003, 3_100_000_027]),
+    ];
+
+    for (num, corr_factors) in test_cases.iter() {
+        match factor::Factors::squfof(*num) {
+            Some(factor) => assert!(corr_factors.contains(&factor), "{}: {}", num, factor),
+            None => panic!("squfof failed for {}", num),
+        }
+    }
+}
+
+#[test]
+fn rho_semiprimes() {
+    let test_cases: [(u64, [u64; 2]); 4] = [
+        (244_334_639, [9199, 26_561]),
+        (998_244_359_987_710_471, [998_244_353, 1_000_000_007]),
+        (9_223_372_021_822_390_277, [2_147_483_647, 4_294_967_291]),
+        (18_446_743_979_220_271_189, [4_294_967_279, 4_294_967_291]),
+    ];
+
+    for (num, corr_factors) in test_cases.iter() {
+        let factor = (1..=3)
+            .find_map(|c| factor::Factors::rho(*num, c))
+            .unwrap_or_else(|| panic!("rho failed for {}", num));
+
+        assert!(corr_factors.contains(&factor), "{}: {}", num, factor);
+    }
+
+    for (num, corr_factors) in test_cases.iter() {
+        let factor = (1..=3)
+            .find_map(|c| factor::Factors::rho(*num as u128, c))
+            .unwrap_or_else(|| panic!("rho failed for {}", num));
+
+        assert!(
+            corr_factors.contains(&(factor as u64)),
+            "{}: {}",
+            num,
+            factor
+        );
+    }
+
+    let mut factors = factor::Factors::new(2u64);
+
+    for (num, corr_factors) in test_cases.iter() {
+        factors.num = *num;
+        factors.factorize();
+
+        compare_arrays(&factors.factors, corr_factors);
+    }
+}
+
 #[test]
 fn factorize_large_semiprimes() {
     let mut factors = factor::Factors::new(2u128);
@@ -469,3 +572,386 @@ fn prime_factor_repr() {
         compare_arrays_of_tuples(&factor_repr, corr_repr);
     }
 }
+
+#[test]
+fn factorize_with_config_limits() {
+    let mut factors = factor::Factors::new(2u128);
+
+    let test_num: [u128; 3] = [
+        2_854_159_729_781,
+        25_645_121_643_901_801,
+        3_746_238_285_234_848_709_827,
+    ];
+
+    let correct_factors: [[u128; 2]; 3] = [
+        [718_433, 3_972_757],
+        [5_394_769, 4_753_701_529],
+        [103_979, 36_028_797_018_963_913],
+    ];
+
+    let config = factor::FactorConfig {
+        max_curves: 10,
+        curve_bound: 20_000,
+        small_prime_bound: 1_000,
+        trial_bound: Some(1_000),
+        timeout: Some(Duration::from_secs(600)),
+        workers: 3,
+        cancel: None,
+        progress: None,
+        cache: None,
+    };
+
+    let it = test_num.iter().zip(correct_factors.iter());
+
+    for (num, corr_factors) in it {
+        factors.num = *num;
+
+        match factors.factorize_with_config(&config) {
+            Ok(()) => compare_arrays(&factors.factors, corr_factors),
+            Err(err) => panic!("num: {}, err: {}", num, err),
+        }
+    }
+}
+
+#[test]
+fn factorize_with_config_timeout() {
+    let mut factors = factor::Factors::new(90_124_258_835_295_998_242_413_094_252_351u128);
+
+    let config = factor::FactorConfig {
+        timeout: Some(Duration::ZERO),
+        ..factor::FactorConfig::default()
+    };
+
+    assert_eq!(
+        factors.factorize_with_config(&config),
+        Err(factor::FactorError::Timeout)
+    );
+}
+
+#[test]
+fn factorize_with_config_cancel() {
+    let token = factor::CancelToken::new();
+
+    let config = factor::FactorConfig {
+        cancel: Some(token.clone()),
+        ..factor::FactorConfig::default()
+    };
+
+    let canceller = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(100));
+        token.cancel();
+    });
+
+    // Product of 63- and 64-bit primes, too hard to factorize before the cancellation
+    let mut factors = factor::Factors::new(170_141_183_460_469_230_726_339_751_698_713_544_131u128);
+
+    assert_eq!(
+        factors.factorize_with_config(&config),
+        Err(factor::FactorError::Cancelled)
+    );
+
+    canceller.join().unwrap();
+}
+
+#[test]
+fn factorize_with_config_progress() {
+    let events = Arc::new(Mutex::new(vec![]));
+    let events_clone = Arc::clone(&events);
+
+    let config = factor::FactorConfig {
+        progress: Some(factor::ProgressCallback::new(move |event| {
+            events_clone.lock().unwrap().push(event);
+        })),
+        ..factor::FactorConfig::default()
+    };
+
+    let mut factors = factor::Factors::new(5 * 3_746_238_285_234_848_709_827u128);
+
+    assert_eq!(factors.factorize_with_config(&config), Ok(()));
+    compare_arrays(&factors.factors, &[5, 103_979, 36_028_797_018_963_913]);
+
+    let events = events.lock().unwrap();
+
+    assert_eq!(
+        events[..2],
+        [
+            factor::FactorEvent::FactorFound { factor: 5 },
+            factor::FactorEvent::TrialDivisionDone {
+                cofactor: 3_746_238_285_234_848_709_827
+            }
+        ]
+    );
+
+    let mut found_factors: Vec<u128> = events
+        .iter()
+        .filter_map(|event| match event {
+            factor::FactorEvent::FactorFound { factor } => Some(*factor),
+            _ => None,
+        })
+        .collect();
+    found_factors.sort();
+
+    assert_eq!(found_factors, vec![5, 103_979, 36_028_797_018_963_913]);
+}
+
+#[test]
+fn factorize_with_config_cache() {
+    let cache = factor::FactorCache::new(2);
+
+    let config = factor::FactorConfig {
+        cache: Some(cache.clone()),
+        ..factor::FactorConfig::default()
+    };
+
+    let test_cases: [(u128, &[u128]); 3] = [
+        (
+            3_746_238_285_234_848_709_827,
+            &[103_979, 36_028_797_018_963_913],
+        ),
+        (2 * 2 * 1_000_000_007, &[2, 2, 1_000_000_007]),
+        (9_804_659_461_513_846_513, &[4_641_991, 2_112_166_839_943]),
+    ];
+
+    for (num, corr_factors) in test_cases.iter() {
+        let mut factors = factor::Factors::new(*num);
+
+        // Second factorization is taken from the cache
+        for _ in 0..2 {
+            assert_eq!(factors.factorize_with_config(&config), Ok(()));
+            compare_arrays(&factors.factors, corr_factors);
+        }
+    }
+
+    assert_eq!(cache.len(), 2);
+    assert_eq!(cache.get(test_cases[0].0), None);
+
+    // Cached factorization is shared by all integer types
+    assert_eq!(cache.get(4_000_000_028u64), Some(vec![2, 2, 1_000_000_007]));
+    assert_eq!(
+        cache.get(4_000_000_028u128),
+        Some(vec![2, 2, 1_000_000_007])
+    );
+
+    cache.clear();
+    assert!(cache.is_empty());
+}
+
+#[test]
+fn factorize_with_stats() {
+    let config = factor::FactorConfig {
+        cache: Some(factor::FactorCache::new(1)),
+        ..factor::FactorConfig::default()
+    };
+
+    let mut factors = factor::Factors::new(1_300_000_003u64 * 3_100_000_027);
+    let stats = factors.factorize_with_stats(&config).unwrap();
+
+    assert_eq!(
+        stats.factors,
+        vec![
+            (1_300_000_003, factor::FactorMethod::Squfof),
+            (3_100_000_027, factor::FactorMethod::Squfof)
+        ]
+    );
+    assert_eq!((stats.worker_rounds, stats.curves), (0, 0));
+    assert_eq!(stats.worker_utilization(), 0.0);
+    assert!(stats.total_time >= stats.squfof_time + stats.trial_division_time);
+
+    let stats = factors.factorize_with_stats(&config).unwrap();
+
+    assert_eq!(
+        stats.factors,
+        vec![
+            (1_300_000_003, factor::FactorMethod::Cache),
+            (3_100_000_027, factor::FactorMethod::Cache)
+        ]
+    );
+
+    let mut factors = factor::Factors::new(5 * 3_746_238_285_234_848_709_827u128);
+    let stats = factors
+        .factorize_with_stats(&factor::FactorConfig::default())
+        .unwrap();
+
+    compare_arrays(&factors.factors, &[5, 103_979, 36_028_797_018_963_913]);
+
+    assert_eq!(stats.factors.len(), 3);
+    assert_eq!(stats.factors[0], (5, factor::FactorMethod::TrialDivision));
+
+    for (factor, method) in stats.factors[1..].iter() {
+        assert!([103_979, 36_028_797_018_963_913].contains(factor));
+        assert!(matches!(
+            method,
+            factor::FactorMethod::Wheel
+                | factor::FactorMethod::EllipticCurve
+                | factor::FactorMethod::PrimalityTest
+        ));
+    }
+
+    assert!(stats.worker_rounds >= 1);
+    assert!(stats.worker_time > Duration::ZERO && stats.worker_time <= stats.total_time);
+    assert!((0.0..=1.0).contains(&stats.worker_utilization()));
+}
+
+#[test]
+fn resolve_worker_count() {
+    let test_cases = [
+        (None, 8, 8),
+        (None, 1, 2),
+        (Some("4"), 8, 4),
+        (Some(" 16 "), 2, 16),
+        (Some("1"), 8, 2),
+        (Some("0"), 8, 8),
+        (Some("many"), 8, 8),
+    ];
+
+    for (env_value, parallelism, corr_workers) in test_cases.iter() {
+        assert_eq!(
+            factor::resolve_workers(*env_value, *parallelism),
+            *corr_workers,
+            "env value: {:?}",
+            env_value
+        );
+    }
+}
+
+#[test]
+fn factorize_with_config_single_worker() {
+    // Worker count is raised to two, otherwise only the wheel worker would run
+    let config = factor::FactorConfig {
+        workers: 1,
+        ..factor::FactorConfig::default()
+    };
+
+    let mut factors = factor::Factors::new(3_746_238_285_234_848_709_827u128);
+
+    assert_eq!(factors.factorize_with_config(&config), Ok(()));
+    compare_arrays(&factors.factors, &[103_979, 36_028_797_018_963_913]);
+}
+
+#[test]
+fn factors_iter() {
+    let test_cases: [(u64, &[(u64, u8)]); 4] = [
+        (2, &[(2, 1)]),
+        (1_024, &[(2, 10)]),
+        (360, &[(2, 3), (3, 2), (5, 1)]),
+        (
+            u64::MAX,
+            &[
+                (3, 1),
+                (5, 1),
+                (17, 1),
+                (257, 1),
+                (641, 1),
+                (65_537, 1),
+                (6_700_417, 1),
+            ],
+        ),
+    ];
+
+    for (num, corr_repr) in test_cases.iter() {
+        let mut factors = factor::Factors::new(*num);
+        factors.factorize();
+
+        assert_eq!(factors.iter().collect::<Vec<_>>(), corr_repr.to_vec());
+        assert_eq!(
+            factors
+                .iter()
+                .fold(1, |acc, (prm, k)| acc * prm.pow(k.into())),
+            *num
+        );
+    }
+}
+
+#[test]
+fn divisor_functions() {
+    let test_cases: [(u64, u64, [Option<u64>; 3], u64); 4] = [
+        (360, 24, [Some(24), Some(1170), Some(201_110)], 30),
+        (
+            1_000_000_007,
+            2,
+            [
+                Some(2),
+                Some(1_000_000_008),
+                Some(1_000_000_014_000_000_050),
+            ],
+            1_000_000_007,
+        ),
+        (1 << 40, 41, [Some(41), Some((1 << 41) - 1), None], 2),
+        (u64::MAX, 128, [Some(128), None, None], u64::MAX),
+    ];
+
+    for (num, corr_tau, corr_sigma, corr_radical) in test_cases.iter() {
+        let mut factors = factor::Factors::new(*num);
+        factors.factorize();
+
+        assert_eq!(factors.tau(), *corr_tau);
+        assert_eq!(factors.radical(), *corr_radical);
+
+        for (k, corr) in corr_sigma.iter().enumerate() {
+            assert_eq!(factors.sigma(k as u32), *corr, "sigma_{}({})", k, num);
+        }
+    }
+
+    let mut factors = factor::Factors::new(1_024u16);
+    factors.factorize();
+
+    assert_eq!(factors.sigma(1), Some(2_047));
+    assert_eq!(factors.sigma(2), None);
+}
+
+#[test]
+fn worker_pool_reuses_threads() {
+    let pool = factor::WorkerPool::new();
+    let (sender, receiver) = mpsc::channel();
+
+    let mut thread_ids = vec![];
+
+    for _ in 0..3 {
+        let sender = sender.clone();
+        pool.execute(Box::new(move || {
+            sender.send(thread::current().id()).unwrap();
+        }));
+
+        thread_ids.push(receiver.recv().unwrap());
+
+        // Wait until the thread is idle again
+        while pool.idle.load(Ordering::SeqCst) == 0 {
+            thread::yield_now();
+        }
+    }
+
+    assert!(thread_ids.iter().all(|&id| id == thread_ids[0]));
+}
+
+#[test]
+fn worker_pool_grows_when_busy() {
+    let pool = factor::WorkerPool::new();
+    let barrier = Arc::new(Barrier::new(3));
+    let (sender, receiver) = mpsc::channel();
+
+    // Jobs wait for each other, hence they must run on different threads
+    for _ in 0..3 {
+        let sender = sender.clone();
+        let barrier = Arc::clone(&barrier);
+
+        pool.execute(Box::new(move || {
+            barrier.wait();
+            sender.send(thread::current().id()).unwrap();
+        }));
+    }
+
+    let thread_ids: HashSet<_> = (0..3).map(|_| receiver.recv().unwrap()).collect();
+
+    assert_eq!(thread_ids.len(), 3);
+}
+
+#[test]
+fn wheel_increments() {
+    let increments = factor::wheel_increments();
+
+    // Count of integers coprime to 2 * 3 * 5 * 7 * 11 in a single turn
+    assert_eq!(increments.len(), 480);
+    assert_eq!(increments.iter().map(|&inc| inc as u32).sum::<u32>(), 2310);
+    assert_eq!(increments[..5], [12, 4, 2, 4, 6]);
+    assert_eq!(increments[479], 2);
+}
@@ -0,0 +1,210 @@
+//! Implements arithmetic of Gaussian integers modulo a prime.
+//!
+//! Gaussian integers are complex numbers a + bi where both a and b are integers.
+//! Taking the real and imaginary parts modulo p forms the ring Z\[i\]/pZ\[i\]
+//! of p^2 elements. For a prime p = 3 (mod 4) this ring is actually a field,
+//! a quadratic extension of Z/pZ, and for p = 1 (mod 4) it's isomorphic to the
+//! product ring Z/pZ x Z/pZ as -1 has a square root modulo p.
+//!
+//! Norm of a Gaussian integer a + bi is a^2 + b^2 and the norm map is
+//! multiplicative, N(zw) = N(z)N(w). Norm equations a^2 + b^2 = d (mod p)
+//! are solvable for every d when p is an odd prime.
+//!
+use std::ops::{Add, Mul, Neg, Sub};
+
+use num::iter;
+
+use crate::{
+    arith::{Arith, CoreArith},
+    prime,
+    quad::QuadEq,
+    UInt,
+};
+
+/// Type for Gaussian integers `re` + `im`*i modulo `modu`.
+///
+/// Parts `re` and `im` are kept as the smallest nonnegative representatives of
+/// their residue classes when created with `new`. Modulo `modu` is expected to be
+/// a prime, but the ring operations are well-defined for any modulo larger than one.
+/// Operations between two Gaussian integers require them to have the same modulo.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GaussianMod<T: UInt> {
+    pub re: T,
+    pub im: T,
+    pub modu: T,
+}
+
+impl<T: UInt> GaussianMod<T> {
+    /// Create a new Gaussian integer `re` + `im`*i modulo `modu`.
+    pub fn new(re: T, im: T, modu: T) -> Self {
+        Self {
+            re: re % modu,
+            im: im % modu,
+            modu,
+        }
+    }
+
+    /// Complex conjugate `re` - `im`*i.
+    pub fn conjugate(&self) -> Self {
+        Self {
+            re: self.re,
+            im: T::sub_mod(T::zero(), self.im, self.modu),
+            modu: self.modu,
+        }
+    }
+
+    /// Norm `re`^2 + `im`^2 (mod `modu`).
+    pub fn norm(&self) -> T {
+        T::add_mod_unsafe(
+            T::mult_mod(self.re, self.re, self.modu),
+            T::mult_mod(self.im, self.im, self.modu),
+            self.modu,
+        )
+    }
+
+    /// Exponentiation `self` ^ `ex` by repeated squaring.
+    pub fn pow(&self, mut ex: T) -> Self {
+        let mut base = *self;
+        let mut res = Self::new(T::one(), T::zero(), self.modu);
+
+        while ex > T::zero() {
+            if ex & T::one() == T::one() {
+                res = res * base;
+            }
+
+            ex = ex.unsigned_shr(1);
+            base = base * base;
+        }
+
+        res
+    }
+
+    /// Multiplicative inverse, computed as the conjugate divided by the norm.
+    ///
+    /// If the norm doesn't have a multiplicative inverse, None is returned.
+    pub fn inverse(&self) -> Option<Self> {
+        let norm_inv = T::multip_inv(self.norm(), self.modu);
+
+        if norm_inv == T::zero() {
+            return None;
+        }
+
+        let conj = self.conjugate();
+
+        Some(Self {
+            re: T::mult_mod(conj.re, norm_inv, self.modu),
+            im: T::mult_mod(conj.im, norm_inv, self.modu),
+            modu: self.modu,
+        })
+    }
+
+    /// Solve norm equation a^2 + b^2 = d (mod modu), where modu is a prime.
+    ///
+    /// Returns one Gaussian integer a + bi having the norm `d`. For an odd
+    /// prime modulo there is always a solution, for modulo two the solution is
+    /// found trivially and for other moduli None is returned.
+    ///
+    /// Search walks through a = 0, 1, 2, ... until d - a^2 is a quadratic residue,
+    /// which on average takes only a couple of steps as half of the nonzero
+    /// residues are quadratic residues.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use modular_equations::GaussianMod;
+    ///
+    /// let z = GaussianMod::<u32>::solve_norm(5, 1_000_003).unwrap();
+    ///
+    /// assert_eq!(z.norm(), 5);
+    /// ```
+    pub fn solve_norm(d: T, modu: T) -> Option<Self>
+    where
+        T: 'static,
+    {
+        let d = d % modu;
+
+        if modu == 2.into() {
+            return Some(Self::new(d, T::zero(), modu));
+        }
+        if !prime::is_odd_prime(modu) {
+            return None;
+        }
+
+        for a in iter::range(T::zero(), modu) {
+            let b2 = T::sub_mod_unsafe(d, T::mult_mod_unsafe(a, a, modu), modu);
+
+            let quad_eq = QuadEq {
+                a: T::one(),
+                b: T::zero(),
+                c: T::zero(),
+                d: b2,
+                modu,
+            };
+
+            if let Some(b) = quad_eq.solve() {
+                return Some(Self::new(a, b[0], modu));
+            }
+        }
+
+        None
+    }
+}
+
+impl<T: UInt> Add for GaussianMod<T> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self {
+            re: T::add_mod(self.re, other.re, self.modu),
+            im: T::add_mod(self.im, other.im, self.modu),
+            modu: self.modu,
+        }
+    }
+}
+
+impl<T: UInt> Sub for GaussianMod<T> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self {
+            re: T::sub_mod(self.re, other.re, self.modu),
+            im: T::sub_mod(self.im, other.im, self.modu),
+            modu: self.modu,
+        }
+    }
+}
+
+impl<T: UInt> Neg for GaussianMod<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self {
+            re: T::sub_mod(T::zero(), self.re, self.modu),
+            im: T::sub_mod(T::zero(), self.im, self.modu),
+            modu: self.modu,
+        }
+    }
+}
+
+impl<T: UInt> Mul for GaussianMod<T> {
+    type Output = Self;
+
+    /// Product (a + bi)(c + di) = (ac - bd) + (ad + bc)i.
+    fn mul(self, other: Self) -> Self {
+        let modu = self.modu;
+
+        let ac = T::mult_mod(self.re, other.re, modu);
+        let bd = T::mult_mod(self.im, other.im, modu);
+        let ad = T::mult_mod(self.re, other.im, modu);
+        let bc = T::mult_mod(self.im, other.re, modu);
+
+        Self {
+            re: T::sub_mod_unsafe(ac, bd, modu),
+            im: T::add_mod_unsafe(ad, bc, modu),
+            modu,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
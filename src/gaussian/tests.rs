@@ -0,0 +1,121 @@
+use crate::arith::Arith;
+use crate::gaussian::GaussianMod;
+
+#[test]
+fn add_sub_small_type() {
+    let modu = 7u8;
+
+    let z = GaussianMod::new(3, 5, modu);
+    let w = GaussianMod::new(6, 4, modu);
+
+    assert_eq!(z + w, GaussianMod::new(2, 2, modu));
+    assert_eq!(z - w, GaussianMod::new(4, 1, modu));
+    assert_eq!(w - z, GaussianMod::new(3, 6, modu));
+    assert_eq!(-z, GaussianMod::new(4, 2, modu));
+    assert_eq!(z + (-z), GaussianMod::new(0, 0, modu));
+}
+
+#[test]
+fn mult_small_type() {
+    let modu = 11u8;
+
+    // [re_1, im_1, re_2, im_2, re, im]: (re_1 + im_1*i)(re_2 + im_2*i) = re + im*i
+    let test_cases: [[u8; 6]; 5] = [
+        [0, 0, 5, 7, 0, 0],
+        [1, 0, 5, 7, 5, 7],
+        [0, 1, 0, 1, 10, 0],
+        [2, 3, 4, 5, 4, 0],
+        [10, 10, 10, 1, 2, 0],
+    ];
+
+    for test in test_cases.iter() {
+        let z = GaussianMod::new(test[0], test[1], modu);
+        let w = GaussianMod::new(test[2], test[3], modu);
+
+        assert_eq!(
+            z * w,
+            GaussianMod::new(test[4], test[5], modu),
+            "{:?}",
+            test
+        );
+        assert_eq!(w * z, z * w);
+    }
+}
+
+#[test]
+fn norm_is_multiplicative() {
+    let modu = 1_000_000_007u64;
+
+    let z = GaussianMod::new(123_456_789, 987_654_321, modu);
+    let w = GaussianMod::new(modu - 1, 31_415_926, modu);
+
+    assert_eq!(
+        (z * w).norm(),
+        u64::mult_mod(z.norm(), w.norm(), modu),
+        "z: {:?}, w: {:?}",
+        z,
+        w
+    );
+    assert_eq!(z * z.conjugate(), GaussianMod::new(z.norm(), 0, modu));
+}
+
+#[test]
+fn pow_and_inverse() {
+    let modu = 10_007u32; // 10_007 = 3 (mod 4), Z[i]/p is a field
+
+    let z = GaussianMod::new(2, 9, modu);
+    let one = GaussianMod::new(1, 0, modu);
+
+    // Multiplicative group of the field has order p^2 - 1
+    assert_eq!(z.pow(modu * modu - 1), one);
+    assert_eq!(z.pow(0), one);
+    assert_eq!(z.pow(3), z * z * z);
+
+    match z.inverse() {
+        Some(z_inv) => assert_eq!(z * z_inv, one),
+        None => panic!("z: {:?}, inverse: None", z),
+    }
+
+    assert_eq!(GaussianMod::new(0, 0, modu).inverse(), None);
+}
+
+#[test]
+fn inverse_zero_divisor() {
+    let modu = 13u8; // 13 = 1 (mod 4), 5^2 = -1 (mod 13)
+
+    let z = GaussianMod::new(5, 1, modu);
+
+    assert_eq!(z.norm(), 0);
+    assert_eq!(z.inverse(), None);
+}
+
+#[test]
+fn solve_norm_small_primes() {
+    let primes: [u16; 8] = [2, 3, 5, 7, 13, 17, 257, 65_521];
+
+    for modu in primes.iter() {
+        for d in 0..50 {
+            match GaussianMod::solve_norm(d, *modu) {
+                Some(z) => assert_eq!(z.norm(), d % *modu, "d: {}, mod: {}", d, modu),
+                None => panic!("d: {}, mod: {}, solution: None", d, modu),
+            }
+        }
+    }
+}
+
+#[test]
+fn solve_norm_large_prime() {
+    let modu = 340_282_366_920_938_463_463_374_607_431_768_211_297u128;
+
+    for d in [1, 2, 3, 7, 1_000_000_000_000, modu - 1].iter() {
+        match GaussianMod::solve_norm(*d, modu) {
+            Some(z) => assert_eq!(z.norm(), *d, "d: {}", d),
+            None => panic!("d: {}, solution: None", d),
+        }
+    }
+}
+
+#[test]
+fn solve_norm_composite_modulo() {
+    assert_eq!(GaussianMod::<u32>::solve_norm(3, 15), None);
+}
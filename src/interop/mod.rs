@@ -0,0 +1,179 @@
+//! Implements interoperability with the `num-modular` crate.
+//!
+//! Module is compiled only with the feature `num-modular`. It bridges the two crates
+//! in both directions. Type `ModArith` wraps an unsigned integer and implements the
+//! modular operation traits of `num-modular` (`ModularCoreOps`, `ModularUnaryOps`,
+//! `ModularPow` and `ModularSymbols`, hence also `ModularOps`) by the arithmetic of
+//! this crate, which is overflow-safe for every unsigned type up to u128.
+//!
+//! In the other direction, linear and quadratic equations can be constructed from
+//! values implementing `ModularInteger`, e.g. `MontgomeryInt` or `ReducedInt`, and
+//! their solutions converted back to the same ring type.
+//!
+use num_modular::{ModularCoreOps, ModularInteger, ModularPow, ModularSymbols, ModularUnaryOps};
+
+use crate::{arith::Arith, character, prime, LinEq, QuadEq, UInt};
+
+/// Unsigned integer whose modular operations are computed by this crate.
+///
+/// Operands, modulus and results of the operations are all of this type, thus
+/// it satisfies a plain `ModularOps` bound of generic code.
+///
+/// # Examples
+///
+/// ```
+/// use modular_equations::ModArith;
+/// use num_modular::{ModularCoreOps, ModularPow, ModularUnaryOps};
+///
+/// let (x, m) = (ModArith(u128::MAX - 1), ModArith(u128::MAX));
+///
+/// assert_eq!(x.mulm(x, m), ModArith(1));
+/// assert_eq!(ModArith(3u32).powm(ModArith(4), ModArith(7)), ModArith(4));
+/// assert_eq!(ModArith(3u32).invm(ModArith(7)), Some(ModArith(5)));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ModArith<T: UInt>(pub T);
+
+impl<T: UInt> ModularCoreOps for ModArith<T> {
+    type Output = Self;
+
+    fn addm(self, rhs: Self, m: Self) -> Self {
+        ModArith(T::add_mod(self.0, rhs.0, m.0))
+    }
+
+    fn subm(self, rhs: Self, m: Self) -> Self {
+        ModArith(T::sub_mod(self.0, rhs.0, m.0))
+    }
+
+    fn mulm(self, rhs: Self, m: Self) -> Self {
+        ModArith(T::mult_mod(self.0, rhs.0, m.0))
+    }
+}
+
+impl<T: UInt> ModularUnaryOps for ModArith<T> {
+    type Output = Self;
+
+    fn negm(self, m: Self) -> Self {
+        ModArith(T::sub_mod(T::zero(), self.0, m.0))
+    }
+
+    fn invm(self, m: Self) -> Option<Self> {
+        if m.0 == T::one() {
+            return Some(ModArith(T::zero()));
+        }
+
+        match T::multip_inv(self.0, m.0) {
+            inv if inv == T::zero() => None,
+            inv => Some(ModArith(inv)),
+        }
+    }
+
+    fn dblm(self, m: Self) -> Self {
+        ModArith(T::add_mod(self.0, self.0, m.0))
+    }
+
+    fn sqm(self, m: Self) -> Self {
+        ModArith(T::mult_mod(self.0, self.0, m.0))
+    }
+}
+
+impl<T: UInt> ModularPow for ModArith<T> {
+    type Output = Self;
+
+    fn powm(self, exp: Self, m: Self) -> Self {
+        ModArith(T::exp_mod(self.0, exp.0, m.0))
+    }
+}
+
+impl<T: UInt> ModularSymbols for ModArith<T> {
+    fn checked_legendre(&self, n: Self) -> Option<i8> {
+        if !prime::is_odd_prime(n.0) {
+            return None;
+        }
+
+        Some(T::jacobi_symbol(self.0, n.0))
+    }
+
+    fn checked_jacobi(&self, n: Self) -> Option<i8> {
+        if n.0 & T::one() == T::zero() {
+            return None;
+        }
+
+        Some(T::jacobi_symbol(self.0, n.0))
+    }
+
+    fn kronecker(&self, n: Self) -> i8 {
+        character::kronecker_symbol(self.0, n.0)
+    }
+}
+
+impl<T: UInt> LinEq<T> {
+    /// Construct linear equation ax + b = c from values of the same modular ring.
+    ///
+    /// Modulo of the equation is the modulus of the ring. If the moduli of the
+    /// values differ, None is returned.
+    pub fn from_modular<M: ModularInteger<Base = T>>(a: &M, b: &M, c: &M) -> Option<Self> {
+        let modu = a.modulus();
+
+        if b.modulus() != modu || c.modulus() != modu {
+            return None;
+        }
+
+        Some(LinEq {
+            a: a.residue(),
+            b: b.residue(),
+            c: c.residue(),
+            modu,
+        })
+    }
+}
+
+impl<T: UInt> QuadEq<T> {
+    /// Construct quadratic equation ax^2 + bx + c = d from values of the same modular ring.
+    ///
+    /// Modulo of the equation is the modulus of the ring. If the moduli of the
+    /// values differ, None is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use modular_equations::{solutions_to_modular, QuadEq};
+    /// use num_modular::{ModularInteger, MontgomeryInt};
+    ///
+    /// let one = MontgomeryInt::new(1u64, &1_000_000_007);
+    /// let two = one.convert(2);
+    /// let zero = one.convert(0);
+    ///
+    /// // x^2 = 2 in Z/1_000_000_007Z
+    /// let quad_eq = QuadEq::from_modular(&one, &zero, &zero, &two).unwrap();
+    /// let x = solutions_to_modular(&quad_eq.solve().unwrap(), &one);
+    ///
+    /// assert!(x.into_iter().all(|x| x.square() == two));
+    /// ```
+    pub fn from_modular<M: ModularInteger<Base = T>>(a: &M, b: &M, c: &M, d: &M) -> Option<Self> {
+        let modu = a.modulus();
+
+        if b.modulus() != modu || c.modulus() != modu || d.modulus() != modu {
+            return None;
+        }
+
+        Some(QuadEq {
+            a: a.residue(),
+            b: b.residue(),
+            c: c.residue(),
+            d: d.residue(),
+            modu,
+        })
+    }
+}
+
+/// Convert solutions `sols` to elements of the modular ring of `ring_elem`.
+pub fn solutions_to_modular<T: UInt, M: ModularInteger<Base = T>>(
+    sols: &[T],
+    ring_elem: &M,
+) -> Vec<M> {
+    sols.iter().map(|&x| ring_elem.convert(x)).collect()
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,120 @@
+use num_modular::{
+    ModularCoreOps, ModularInteger, ModularOps, ModularPow, ModularSymbols, ModularUnaryOps,
+    MontgomeryInt, ReducedInt, Vanilla,
+};
+
+use crate::interop::{solutions_to_modular, ModArith};
+use crate::{LinEq, QuadEq};
+
+/// Generic over the ecosystem trait, thus the adapter must implement `ModularOps`.
+fn sum_of_inverse_squares<T: ModularOps + Copy>(x: T, y: T, m: T) -> Option<T> {
+    let x_inv = x.invm(m)?;
+    let y_inv = y.invm(m)?;
+
+    Some(x_inv.sqm(m).addm(y_inv.sqm(m), m))
+}
+
+#[test]
+fn mod_arith_matches_num_modular() {
+    let moduli: [u64; 4] = [2, 97, 1_000_000_007, u64::MAX];
+
+    for &m in moduli.iter() {
+        let m_arith = ModArith(m);
+
+        for &x in [0, 1, 2, 96, 12_345_678_901, u64::MAX - 1].iter() {
+            let x_arith = ModArith(x);
+
+            for &y in [0, 3, 1_000_000_006, u64::MAX].iter() {
+                let y_arith = ModArith(y);
+
+                assert_eq!(x_arith.addm(y_arith, m_arith).0, x.addm(y, &m));
+                assert_eq!(x_arith.subm(y_arith, m_arith).0, x.subm(y, &m));
+                assert_eq!(x_arith.mulm(y_arith, m_arith).0, x.mulm(y, &m));
+                assert_eq!(
+                    x_arith.powm(y_arith, m_arith).0,
+                    x.powm(y, &m),
+                    "x: {}, y: {}, m: {}",
+                    x,
+                    y,
+                    m
+                );
+            }
+
+            assert_eq!(x_arith.negm(m_arith).0, x.negm(&m));
+            assert_eq!(x_arith.dblm(m_arith).0, x.dblm(&m));
+            assert_eq!(x_arith.sqm(m_arith).0, x.sqm(&m));
+            assert_eq!(
+                x_arith.invm(m_arith).map(|inv| inv.0),
+                x.invm(&m),
+                "x: {}, m: {}",
+                x,
+                m
+            );
+        }
+    }
+}
+
+#[test]
+fn mod_arith_symbols() {
+    for n in (3..200u32).step_by(2) {
+        for a in 0..50u32 {
+            assert_eq!(
+                ModArith(a).checked_jacobi(ModArith(n)),
+                a.checked_jacobi(&n)
+            );
+            assert_eq!(ModArith(a).kronecker(ModArith(n)), a.kronecker(&n));
+        }
+    }
+
+    assert_eq!(ModArith(5u32).checked_legendre(ModArith(15)), None);
+    assert_eq!(ModArith(5u32).checked_jacobi(ModArith(16)), None);
+    assert_eq!(ModArith(2u32).legendre(ModArith(7)), 1);
+}
+
+#[test]
+fn mod_arith_as_modular_ops() {
+    let m = ModArith(1_000_000_007u64);
+
+    match sum_of_inverse_squares(ModArith(2u64), ModArith(3), m) {
+        // 1/4 + 1/9 = 13/36
+        Some(x) => assert_eq!(x.mulm(ModArith(36), m), ModArith(13)),
+        None => panic!("inverses should exist"),
+    }
+
+    assert_eq!(
+        sum_of_inverse_squares(ModArith(2u64), ModArith(3), ModArith(6)),
+        None
+    );
+}
+
+#[test]
+fn equations_from_modular() {
+    let modu = 1_000_000_007u64;
+    let one = MontgomeryInt::new(1u64, &modu);
+
+    // 3x + 5 = 2
+    let lin_eq = LinEq::from_modular(&one.convert(3), &one.convert(5), &one.convert(2)).unwrap();
+    let x = solutions_to_modular(&lin_eq.solve().unwrap(), &one);
+
+    assert_eq!(x.len(), 1);
+    assert_eq!((x[0] * one.convert(3) + one.convert(5)).residue(), 2);
+
+    // x^2 + x + 1 = 7, roots 2 and -3
+    let quad_eq = QuadEq::from_modular(&one, &one, &one, &one.convert(7)).unwrap();
+    let x = solutions_to_modular(&quad_eq.solve().unwrap(), &one);
+
+    assert_eq!(x.len(), 2);
+
+    for x_i in x.into_iter() {
+        assert_eq!((x_i.square() + x_i + one).residue(), 7);
+    }
+}
+
+#[test]
+fn equations_from_modular_mismatched_moduli() {
+    let x = ReducedInt::<u32, Vanilla<u32>>::new(3, &7);
+    let y = ReducedInt::<u32, Vanilla<u32>>::new(3, &11);
+
+    assert!(LinEq::from_modular(&x, &y, &x).is_none());
+    assert!(QuadEq::from_modular(&x, &x, &x, &y).is_none());
+}
@@ -72,8 +72,8 @@
 //! in the ring can be turned to the smallest nonnegative representative of the
 //! corresponding residue class \[x\]. Related to this fact there are few technical
 //! restrictions, the first being that the used signed type (e.g. i32) must have
-//! the arith::SignCast trait implemented and that trait requires the signed and
-//! unsigned types to be compatible (i.e., have the same size in bytes). In addition,
+//! the arith::SignCast trait implemented and that trait requires the unsigned type
+//! to be at least as wide as the signed type (e.g. i32 with u32, u64 or u128). In addition,
 //! as the smallest negative integer of each type doesn't have an absolute value in
 //! two's complement, they will trigger immediate None return value if used as coefficients
 //! in linear or quadratic equations.
@@ -145,6 +145,47 @@
 //! }
 //! ```
 //!
+//! Cubic equations ax^3 + bx^2 + cx + d = e (mod n) are solved similarly by the
+//! types `CubicEq` and `CubicEqSigned`, finding the roots modulo each prime power
+//! factor of the modulo before combining them.
+//!
+//! ```
+//! use modular_equations::CubicEqSigned;
+//!
+//! // (x - 1)(x - 2)(x - 3) = 0 (mod 1_000_003)
+//! let cubic_eq = CubicEqSigned::<i64, u64> {a: 1, b: -6, c: 11, d: -6, e: 0, modu: 1_000_003};
+//!
+//! assert_eq!(cubic_eq.solve(), Some(vec![1, 2, 3]));
+//! ```
+//!
+//! Besides the equation solvers, the library provides functions to query the
+//! structure of the ring Z/nZ. For example, `idempotents` returns all solutions of
+//! x^2 = x (mod n) and `nilpotents` all the nilpotent elements, these being multiples
+//! of the nilradical generator returned by `nilradical`. Solutions of x^k = 1 (mod n)
+//! are found from the cyclic structure of the unit group by `roots_of_unity`.
+//!
+//! ```
+//! use modular_equations::{idempotents, nilradical};
+//!
+//! assert_eq!(idempotents::<u32>(30), Some(vec![0, 1, 6, 10, 15, 16, 21, 25]));
+//! assert_eq!(nilradical::<u32>(360), Some(30));
+//! ```
+//!
+//! Dirichlet characters modulo n can be evaluated with `DirichletCharacter`, which
+//! represents the character values exactly as exponents of a root of unity. Quadratic
+//! characters are also available directly as the Kronecker symbol `kronecker_symbol` and,
+//! modulo an odd prime, as the Legendre symbol `legendre`.
+//!
+//! ```
+//! use modular_equations::{kronecker_symbol, DirichletCharacter};
+//!
+//! let chi = DirichletCharacter::<u32>::new(9, &[2]).unwrap();
+//!
+//! // 2 generates the units modulo 9, hence chi(4) = exp(2πi * 4 / 6)
+//! assert_eq!(chi.value(4), Some(4));
+//! assert_eq!(kronecker_symbol::<u32>(5, 12), -1);
+//! ```
+//!
 //! As a warning note, some equations have a huge amount of solutions and in these cases
 //! the solver might slow down considerable or even panic when the solution count
 //! exceeds usize::MAX. But these are really special cases and usually not very
@@ -158,12 +199,31 @@ use std::marker::{Send, Sync};
 use num::{integer::Roots, PrimInt, Signed, Unsigned};
 
 mod arith;
+#[cfg(feature = "bigint")]
+mod bigint;
+mod character;
+mod crt;
+#[cfg(feature = "ct")]
+mod ct;
+mod cubic;
+mod dlog;
 mod elliptic;
 mod factor;
+mod gaussian;
+#[cfg(feature = "num-modular")]
+mod interop;
 mod lin;
+mod notation;
+mod numtheory;
+mod padic;
+mod poly;
 mod prime;
 mod quad;
+mod ring;
+mod rns;
 mod utils;
+#[cfg(feature = "wasm")]
+mod wasm;
 
 pub trait UInt:
     PrimInt + Unsigned + Roots + Display + Debug + From<u8> + Into<u128> + Hash + Send + Sync
@@ -182,6 +242,37 @@ pub trait Int: PrimInt + Signed + Display + Debug + From<i8> + Into<i128> {}
 
 impl<S> Int for S where S: PrimInt + Signed + Display + Debug + From<i8> + Into<i128> {}
 
+/// Common interface of the single-variable equation types with unsigned terms.
+///
+/// Trait is object safe, hence equations of different kinds can be stored together
+/// e.g. as `Vec<Box<dyn ModularEquation<u64>>>` and solved uniformly.
+///
+/// # Examples
+///
+/// ```
+/// use modular_equations::{LinEq, ModularEquation, QuadEq};
+///
+/// let eqs: Vec<Box<dyn ModularEquation<u64>>> = vec![
+///     Box::new(LinEq {a: 3, b: 3, c: 1, modu: 1223}),
+///     Box::new(QuadEq {a: 1, b: 1, c: 3, d: 11, modu: 41}),
+/// ];
+///
+/// let sols: Vec<Option<Vec<u64>>> = eqs.iter().map(|eq| eq.solve()).collect();
+///
+/// assert_eq!(sols, vec![Some(vec![407]), Some(vec![9, 31])]);
+/// assert!(eqs.iter().all(|eq| eq.count_solutions() > 0));
+/// ```
+pub trait ModularEquation<T: UInt> {
+    /// Solve the equation, None meaning that there aren't solutions.
+    fn solve(&self) -> Option<Vec<T>>;
+
+    /// Count the solutions of the equation without enumerating them.
+    fn count_solutions(&self) -> T;
+
+    /// Check whether `x` is a solution of the equation.
+    fn is_solution(&self, x: T) -> bool;
+}
+
 impl arith::SignCast<i8, u8> for i8 {}
 impl arith::SignCast<i16, u16> for i16 {}
 impl arith::SignCast<i32, u32> for i32 {}
@@ -189,5 +280,57 @@ impl arith::SignCast<i64, u64> for i64 {}
 impl arith::SignCast<i128, u128> for i128 {}
 impl arith::SignCast<isize, usize> for isize {}
 
-pub use lin::{LinEq, LinEqSigned};
-pub use quad::{QuadEq, QuadEqSigned};
+impl arith::SignCast<i8, u16> for i8 {}
+impl arith::SignCast<i8, u32> for i8 {}
+impl arith::SignCast<i8, u64> for i8 {}
+impl arith::SignCast<i8, u128> for i8 {}
+impl arith::SignCast<i16, u32> for i16 {}
+impl arith::SignCast<i16, u64> for i16 {}
+impl arith::SignCast<i16, u128> for i16 {}
+impl arith::SignCast<i32, u64> for i32 {}
+impl arith::SignCast<i32, u128> for i32 {}
+impl arith::SignCast<i64, u128> for i64 {}
+
+pub use arith::{add_mod, exp_mod, mult_mod, multip_inv, multip_inv_batch, pow_mod, sub_mod};
+#[cfg(feature = "u256")]
+pub use bigint::solutions_to_u256;
+#[cfg(feature = "bigint")]
+pub use bigint::{BigIntError, LinEqBig, QuadEqBig};
+pub use character::{gauss_sum, kronecker_symbol, legendre, DirichletCharacter, GaussSum};
+pub use crt::{
+    crt, crt_garner, exp_mod_crt, mixed_radix_digits, CongruenceSystem, CrtBuilder, CrtError,
+};
+#[cfg(feature = "ct")]
+pub use ct::{add_mod_ct, exp_mod_ct, mult_mod_ct, multip_inv_ct, sub_mod_ct};
+pub use cubic::{CubicEq, CubicEqSigned};
+pub use dlog::{discrete_log, DiscreteLog};
+pub use factor::{
+    CancelToken, FactorCache, FactorConfig, FactorError, FactorEvent, FactorMethod, FactorStats,
+    Factors, ProgressCallback,
+};
+pub use gaussian::GaussianMod;
+#[cfg(feature = "num-modular")]
+pub use interop::{solutions_to_modular, ModArith};
+pub use lin::{
+    BuildError, LinEq, LinEqBuilder, LinEqSigned, MultiLinEq, MultiLinSolution, SolveError,
+};
+pub use notation::ParseEquationError;
+pub use numtheory::{
+    carmichael, factorial_valuation, is_blum_integer, is_squarefree, moebius, totient,
+    tower_exp_mod,
+};
+pub use padic::{lift_inverse_mod_pk, lift_sqrt_mod_pk};
+pub use poly::{PolyEq, PolyEqSigned};
+pub use prime::{
+    fibonacci_mod, is_mersenne_prime, is_prime, is_probable_prime, is_safe_prime,
+    is_sophie_germain, lucas_sequence, next_prime, pisano_period, prev_prime, primes_in_range,
+    PrimesInRange,
+};
+pub use quad::{
+    is_qr_with_factors, is_quadratic_residue, rabin_decrypt, sqrt_mod, sqrt_mod_prime_power,
+    QuadEq, QuadEqBuilder, QuadEqSigned, SolutionSet, SolutionSetIter, Solutions,
+};
+pub use ring::{idempotents, nilpotents, nilradical, roots_of_unity, units, Units};
+pub use rns::{QuadEqRns, RnsSolution};
+#[cfg(feature = "wasm")]
+pub use wasm::{solve_linear, solve_quadratic};
@@ -2,25 +2,151 @@
 //!
 //! Modular linear equations are of the form ax + b = c (mod n) where
 //! every coefficient or term is a residue class \[*\] belonging to
-//! the ring of integers Z/nZ. Modulo n must be a positive integer and
-//! strictly larger than one.
+//! the ring of integers Z/nZ. Modulo n must be a positive integer. Modulo
+//! one is trivial, every integer being congruent to 0, and [0] is its only solution.
 //!
 //! Solutions x, if any, are given as residue classes \[x\] such that
 //! each class is represented by smallest nonnegative integer (modulo n).
 //!
+//! Multivariate equations a_1 * x_1 + ... + a_k * x_k + b = c (mod n) are solved by
+//! reducing the vector (a_1, ..., a_k, n) to (g, 0, ..., 0) with Euclid's algorithm on
+//! its entries, g being gcd(a_1, ..., a_k, n). Applying the same column operations to the
+//! identity matrix yields a unimodular transformation U. Integer solutions of
+//! a_1 * x_1 + ... + a_k * x_k + n * y = c - b are then U * (c'/g, t_2, ..., t_(k+1))
+//! for arbitrary t_i, where c' = c - b, and the x part of them modulo n is kept.
+//!
+use std::{error::Error, fmt};
+
 use crate::{
     arith::{Arith, SignCast},
-    Int, UInt,
+    poly, Int, ModularEquation, UInt,
 };
 use num::iter;
 
+/// Every residue class is enumerated as a solution of an equation whose unknown vanishes
+/// only for moduli up to this bound.
+pub(crate) const ALL_RESIDUES_MAX_MODULO: u128 = 1 << 20;
+
+/// Reasons for an equation not having solutions, returned by the `try_solve` methods.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SolveError {
+    /// Equation is well-formed but has no solutions.
+    NoSolution,
+    /// Modulo is zero.
+    InvalidModulus,
+    /// Signed coefficient cannot be cast to unsigned type, i.e. it's S::min_value().
+    CastFailure,
+    /// Every coefficient of the unknown is divisible by the modulo and the remaining
+    /// terms differ, hence no residue is a solution.
+    VariableVanished,
+    /// Unknown vanishes and every residue class is a solution, but the modulo is too
+    /// large for them to be enumerated.
+    TooManySolutions,
+}
+
+impl fmt::Display for SolveError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SolveError::NoSolution => write!(f, "equation has no solutions"),
+            SolveError::InvalidModulus => write!(f, "modulus must be positive"),
+            SolveError::CastFailure => write!(f, "coefficient cannot be cast to unsigned type"),
+            SolveError::VariableVanished => write!(f, "unknown vanishes from the equation"),
+            SolveError::TooManySolutions => write!(f, "every residue class is a solution"),
+        }
+    }
+}
+
+impl Error for SolveError {}
+
+/// Reasons for an equation builder to reject the equation, returned by the `build` methods.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BuildError {
+    /// Modulo hasn't been set.
+    MissingModulus,
+    /// Modulo is zero.
+    InvalidModulus,
+    /// Signed coefficient cannot be cast to unsigned type, i.e. it's S::min_value().
+    CastFailure,
+}
+
+impl fmt::Display for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BuildError::MissingModulus => write!(f, "modulus is missing"),
+            BuildError::InvalidModulus => write!(f, "modulus must be positive"),
+            BuildError::CastFailure => write!(f, "coefficient cannot be cast to unsigned type"),
+        }
+    }
+}
+
+impl Error for BuildError {}
+
+/// Coefficient of an equation builder, kept as its sign and absolute value until
+/// the modulo is known.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Coef<T: UInt> {
+    abs: T,
+    negative: bool,
+}
+
+impl<T: UInt> Coef<T> {
+    pub fn unsigned(x: T) -> Self {
+        Self {
+            abs: x,
+            negative: false,
+        }
+    }
+
+    /// Coefficient for signed `x` or `BuildError::CastFailure` if it's S::min_value().
+    pub fn signed<S>(x: S) -> Result<Self, BuildError>
+    where
+        S: Int + SignCast<S, T>,
+        T: TryFrom<S>,
+    {
+        if x == S::min_value() {
+            return Err(BuildError::CastFailure);
+        }
+
+        match T::try_from(x.abs()) {
+            Ok(abs) => Ok(Self {
+                abs,
+                negative: x < S::zero(),
+            }),
+            Err(_) => Err(BuildError::CastFailure),
+        }
+    }
+
+    /// Smallest nonnegative representative of the coefficient modulo `modu`.
+    pub fn reduce(&self, modu: T) -> T {
+        if self.negative {
+            T::sub_mod(T::zero(), self.abs, modu)
+        } else {
+            self.abs % modu
+        }
+    }
+}
+
+/// Builder for linear equations, created by `LinEq::builder`.
+///
+/// Coefficients may be given as unsigned or signed values and they default to zero.
+/// Method `build` validates the equation and reduces every coefficient to the smallest
+/// nonnegative representative of its residue class.
+#[derive(Debug, Clone, Copy)]
+pub struct LinEqBuilder<T: UInt> {
+    a: Coef<T>,
+    b: Coef<T>,
+    c: Coef<T>,
+    modu: Option<T>,
+    error: Option<BuildError>,
+}
+
 /// Type for linear equations with unsigned terms only.
 ///
 /// Linear modular equations are of the form ax + b = c (mod modu) where
 /// coefficients `a`, `b` and `c` must be nonnegative for this type. Also
-/// `modu` must be the same unsigned type and strictly larger than one.
+/// `modu` must be the same unsigned type and positive.
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct LinEq<T: UInt> {
     pub a: T,
     pub b: T,
@@ -32,10 +158,10 @@ pub struct LinEq<T: UInt> {
 ///
 /// Linear modular equations are of the form ax + b = c (mod modu) where
 /// coefficients `a`, `b` and `c` are signed for this type. Modulo `modu`
-/// must be an unsigned type but compatible to the signed type (same byte count),
-/// e.g. u32 if the signed type is i32, and strictly larger than one as its value.
+/// must be an unsigned type at least as wide as the signed type, e.g. u32, u64 or
+/// u128 if the signed type is i32, and positive as its value.
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct LinEqSigned<S: Int, T: UInt> {
     pub a: S,
     pub b: S,
@@ -51,7 +177,10 @@ impl<T: UInt> LinEq<T> {
     /// equation. If gcd(a, modu) == 1, there will be a unique solution.
     ///
     /// If a % modu == 0 (0 is the smallest nonnegative representative of \[a\]),
-    /// there are no solutions since the variable x vanishes from the equation.
+    /// the variable x vanishes from the equation. Then every residue class is a
+    /// solution if b = c (mod modu) and otherwise there are no solutions. Every residue
+    /// class is enumerated only for moduli up to 2^20, for larger moduli None is returned
+    /// and `solve_as_congruence` gives the solutions as the single class (0, 1).
     ///
     /// If there aren't solutions, None is returned.
     ///
@@ -83,29 +212,206 @@ impl<T: UInt> LinEq<T> {
     /// assert!(mul_inv.is_some() && mul_inv.unwrap()[0] == 254);
     /// ```
     pub fn solve(&self) -> Option<Vec<T>> {
-        if self.modu <= T::one() || self.a % self.modu == T::zero() {
+        let (base_sol, new_modu) = solve_as_congruence(self)?;
+
+        if new_modu == T::one() && self.modu.into() > ALL_RESIDUES_MAX_MODULO {
             return None;
         }
 
-        let c = if self.b > T::zero() {
-            T::sub_mod(self.c, self.b, self.modu)
-        } else {
-            self.c
-        };
+        Some(iter::range_step(base_sol, self.modu, new_modu).collect())
+    }
+
+    /// Solve linear modular equation as `solve` but tell why there aren't solutions.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use modular_equations::{LinEq, SolveError};
+    ///
+    /// let lin_eq = LinEq::<u8> {a: 17, b: 0, c: 1, modu: 255};
+    /// assert_eq!(lin_eq.try_solve(), Err(SolveError::NoSolution));
+    ///
+    /// let lin_eq = LinEq::<u8> {a: 17, b: 0, c: 1, modu: 17};
+    /// assert_eq!(lin_eq.try_solve(), Err(SolveError::VariableVanished));
+    ///
+    /// // 17x + 1 = 1 (mod 17) holds for every residue class
+    /// let lin_eq = LinEq::<u8> {a: 17, b: 1, c: 1, modu: 17};
+    /// assert_eq!(lin_eq.try_solve(), Ok((0..17).collect()));
+    ///
+    /// let lin_eq = LinEq::<u32> {a: 0, b: 1, c: 1, modu: 1 << 30};
+    /// assert_eq!(lin_eq.try_solve(), Err(SolveError::TooManySolutions));
+    /// ```
+    pub fn try_solve(&self) -> Result<Vec<T>, SolveError> {
+        if self.modu == T::zero() {
+            return Err(SolveError::InvalidModulus);
+        }
+        if self.a % self.modu == T::zero() {
+            if self.b % self.modu != self.c % self.modu {
+                return Err(SolveError::VariableVanished);
+            }
+            if self.modu.into() > ALL_RESIDUES_MAX_MODULO {
+                return Err(SolveError::TooManySolutions);
+            }
+        }
+
+        self.solve().ok_or(SolveError::NoSolution)
+    }
+
+    /// Create a builder for linear equations.
+    ///
+    /// Builder validates the equation up front instead of the solvers returning None.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use modular_equations::{BuildError, LinEq};
+    ///
+    /// let lin_eq = LinEq::<u32>::builder().a(20).b_signed(-3).c(1).modu(17).build();
+    ///
+    /// match lin_eq {
+    ///     Ok(lin_eq) => {
+    ///         assert_eq!((lin_eq.a, lin_eq.b, lin_eq.c), (3, 14, 1));
+    ///         assert_eq!(lin_eq.solve(), Some(vec![7]));
+    ///     }
+    ///     Err(err) => panic!("{}", err),
+    /// }
+    ///
+    /// let lin_eq = LinEq::<u32>::builder().a_signed(i32::MIN).modu(17).build();
+    /// assert_eq!(lin_eq.err(), Some(BuildError::CastFailure));
+    /// ```
+    pub fn builder() -> LinEqBuilder<T> {
+        LinEqBuilder {
+            a: Coef::unsigned(T::zero()),
+            b: Coef::unsigned(T::zero()),
+            c: Coef::unsigned(T::zero()),
+            modu: None,
+            error: None,
+        }
+    }
 
-        let gcd_am = T::gcd_mod(self.a, self.modu);
+    /// Check whether `x` is a solution of linear modular equation ax + b = c (mod modu).
+    ///
+    /// Value `x` doesn't need to be reduced modulo `modu`. For modulo zero, false is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use modular_equations::LinEq;
+    ///
+    /// let lin_eq = LinEq::<u32> {a: 3, b: 3, c: 1, modu: 1223};
+    ///
+    /// assert!(lin_eq.is_solution(407));
+    /// assert!(lin_eq.is_solution(407 + 1223));
+    /// assert!(!lin_eq.is_solution(408));
+    /// ```
+    pub fn is_solution(&self, x: T) -> bool {
+        self.sides(x).is_some_and(|(lhs, rhs)| lhs == rhs)
+    }
 
-        if c % gcd_am > T::zero() {
+    /// Evaluate the left and right hand sides of linear modular equation ax + b = c (mod modu)
+    /// at `x`, both reduced modulo `modu`.
+    ///
+    /// Value `x` doesn't need to be reduced modulo `modu`. For modulo zero, None is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use modular_equations::LinEq;
+    ///
+    /// let lin_eq = LinEq::<u32> {a: 3, b: 3, c: 1, modu: 1223};
+    ///
+    /// assert_eq!(lin_eq.sides(407), Some((1, 1)));
+    /// assert_eq!(lin_eq.sides(408), Some((4, 1)));
+    /// ```
+    pub fn sides(&self, x: T) -> Option<(T, T)> {
+        if self.modu == T::zero() {
             return None;
         }
 
-        if gcd_am == T::one() {
-            Some(vec![LinEq::solve_unique(self.a, c, self.modu)])
-        } else {
-            let new_modu = self.modu / gcd_am;
-            let base_sol = LinEq::solve_unique(self.a / gcd_am, c / gcd_am, new_modu);
+        Some((
+            poly::evaluate(&[self.b, self.a], x, self.modu),
+            self.c % self.modu,
+        ))
+    }
+
+    /// Solve linear modular equation as `solve` but return the solutions as balanced
+    /// representatives of their residue classes, i.e. in range (-modu/2, modu/2].
+    ///
+    /// Solutions are returned in increasing order for the signed type S. If there aren't
+    /// solutions or some of them doesn't fit into S, None is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use modular_equations::LinEq;
+    ///
+    /// let lin_eq = LinEq::<u32> {a: 3, b: 0, c: 6, modu: 9};
+    ///
+    /// // Smallest nonnegative solutions are [2, 5, 8]
+    /// assert_eq!(lin_eq.solve_balanced::<i32>(), Some(vec![-4, -1, 2]));
+    /// ```
+    pub fn solve_balanced<S>(&self) -> Option<Vec<S>>
+    where
+        S: Int + SignCast<S, T>,
+        T: TryFrom<S>,
+    {
+        let mut sols = self
+            .solve()?
+            .into_iter()
+            .map(|x| S::cast_to_balanced(x, self.modu))
+            .collect::<Option<Vec<S>>>()?;
+        sols.sort_unstable();
+
+        Some(sols)
+    }
+
+    /// Find all integer solutions x of linear modular equation in range `lo` <= x < `hi`.
+    ///
+    /// Solutions aren't restricted to the smallest nonnegative representatives of their
+    /// residue classes but every integer of the range satisfying the equation is returned,
+    /// in increasing order. The range is walked by the step modu / gcd(a, modu) and
+    /// the solutions modulo `modu` aren't enumerated.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use modular_equations::LinEq;
+    ///
+    /// let lin_eq = LinEq::<u32> {a: 3, b: 0, c: 6, modu: 9};
+    ///
+    /// // Solutions modulo 9 are [2, 5, 8]
+    /// assert_eq!(lin_eq.solutions_in_range(10, 20), vec![11, 14, 17]);
+    /// ```
+    pub fn solutions_in_range(&self, lo: T, hi: T) -> Vec<T> {
+        match solve_as_congruence(self) {
+            Some((base_sol, new_modu)) => congruence_in_range(base_sol, new_modu, lo, hi),
+            None => vec![],
+        }
+    }
 
-            Some(iter::range_step(base_sol, self.modu, new_modu).collect())
+    /// Count the solutions of linear modular equation ax + b = c (mod modu).
+    ///
+    /// Solutions form one residue class modulo modu / gcd(a, modu), hence there are
+    /// either zero or gcd(a, modu) of them and they aren't enumerated. Count equals
+    /// the length of the vector returned by `solve`, zero meaning that there aren't solutions,
+    /// except when the unknown vanishes and the modulo is too large for `solve` to enumerate
+    /// every residue class.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use modular_equations::LinEq;
+    ///
+    /// let lin_eq = LinEq::<u64> {a: 1 << 40, b: 0, c: 1 << 41, modu: 1 << 60};
+    /// assert_eq!(lin_eq.count_solutions(), 1 << 40);
+    ///
+    /// let lin_eq = LinEq::<u64> {a: 1 << 40, b: 0, c: 1, modu: 1 << 60};
+    /// assert_eq!(lin_eq.count_solutions(), 0);
+    /// ```
+    pub fn count_solutions(&self) -> T {
+        match solve_as_congruence(self) {
+            Some((_, new_modu)) => self.modu / new_modu,
+            None => T::zero(),
         }
     }
 
@@ -114,6 +420,311 @@ impl<T: UInt> LinEq<T> {
     }
 }
 
+impl<T: UInt> LinEqBuilder<T> {
+    /// Set coefficient `a`.
+    pub fn a(mut self, a: T) -> Self {
+        self.a = Coef::unsigned(a);
+        self
+    }
+
+    /// Set coefficient `b`.
+    pub fn b(mut self, b: T) -> Self {
+        self.b = Coef::unsigned(b);
+        self
+    }
+
+    /// Set coefficient `c`.
+    pub fn c(mut self, c: T) -> Self {
+        self.c = Coef::unsigned(c);
+        self
+    }
+
+    /// Set coefficient `a` from a signed value.
+    pub fn a_signed<S>(mut self, a: S) -> Self
+    where
+        S: Int + SignCast<S, T>,
+        T: TryFrom<S>,
+    {
+        self.a = self.set_signed(a);
+        self
+    }
+
+    /// Set coefficient `b` from a signed value.
+    pub fn b_signed<S>(mut self, b: S) -> Self
+    where
+        S: Int + SignCast<S, T>,
+        T: TryFrom<S>,
+    {
+        self.b = self.set_signed(b);
+        self
+    }
+
+    /// Set coefficient `c` from a signed value.
+    pub fn c_signed<S>(mut self, c: S) -> Self
+    where
+        S: Int + SignCast<S, T>,
+        T: TryFrom<S>,
+    {
+        self.c = self.set_signed(c);
+        self
+    }
+
+    /// Set modulo `modu`.
+    pub fn modu(mut self, modu: T) -> Self {
+        self.modu = Some(modu);
+        self
+    }
+
+    /// Build the equation with reduced coefficients.
+    ///
+    /// Fails if some signed coefficient was S::min_value() or the modulo is missing or zero.
+    pub fn build(self) -> Result<LinEq<T>, BuildError> {
+        if let Some(err) = self.error {
+            return Err(err);
+        }
+
+        let modu = self.modu.ok_or(BuildError::MissingModulus)?;
+
+        if modu == T::zero() {
+            return Err(BuildError::InvalidModulus);
+        }
+
+        Ok(LinEq {
+            a: self.a.reduce(modu),
+            b: self.b.reduce(modu),
+            c: self.c.reduce(modu),
+            modu,
+        })
+    }
+
+    fn set_signed<S>(&mut self, x: S) -> Coef<T>
+    where
+        S: Int + SignCast<S, T>,
+        T: TryFrom<S>,
+    {
+        Coef::signed(x).unwrap_or_else(|err| {
+            self.error.get_or_insert(err);
+            Coef::unsigned(T::zero())
+        })
+    }
+}
+
+impl<T: UInt> ModularEquation<T> for LinEq<T> {
+    fn solve(&self) -> Option<Vec<T>> {
+        LinEq::solve(self)
+    }
+
+    fn count_solutions(&self) -> T {
+        LinEq::count_solutions(self)
+    }
+
+    fn is_solution(&self, x: T) -> bool {
+        LinEq::is_solution(self, x)
+    }
+}
+
+/// Solve linear equation `lin_eq` as a single congruence x = r (mod m).
+///
+/// All solutions of the equation form one residue class modulo m = modu / gcd(a, modu),
+/// returned as a pair (r, m) without enumerating the solutions modulo `modu`. If the
+/// unknown vanishes and b = c (mod modu), every residue is a solution, i.e. (0, 1).
+pub fn solve_as_congruence<T: UInt>(lin_eq: &LinEq<T>) -> Option<(T, T)> {
+    if lin_eq.modu == T::zero() {
+        return None;
+    }
+
+    let c = if lin_eq.b > T::zero() {
+        T::sub_mod(lin_eq.c, lin_eq.b, lin_eq.modu)
+    } else {
+        lin_eq.c % lin_eq.modu
+    };
+
+    if lin_eq.a % lin_eq.modu == T::zero() {
+        return if c == T::zero() {
+            Some((T::zero(), T::one()))
+        } else {
+            None
+        };
+    }
+
+    let gcd_am = T::gcd_mod(lin_eq.a, lin_eq.modu);
+
+    if c % gcd_am > T::zero() {
+        return None;
+    }
+
+    if gcd_am == T::one() {
+        Some((LinEq::solve_unique(lin_eq.a, c, lin_eq.modu), lin_eq.modu))
+    } else {
+        let new_modu = lin_eq.modu / gcd_am;
+
+        Some((
+            LinEq::solve_unique(lin_eq.a / gcd_am, c / gcd_am, new_modu),
+            new_modu,
+        ))
+    }
+}
+
+/// All integers x = `r` (mod `m`) in range `lo` <= x < `hi`, in increasing order.
+pub fn congruence_in_range<T: UInt>(r: T, m: T, lo: T, hi: T) -> Vec<T> {
+    // Smallest member of the residue class not less than `lo`
+    match lo.checked_add(&T::sub_mod(r % m, lo % m, m)) {
+        Some(first) => iter::range_step(first, hi, m).collect(),
+        None => vec![],
+    }
+}
+
+/// Type for multivariate linear equations with unsigned terms only.
+///
+/// Equations are of the form a_1 * x_1 + ... + a_k * x_k + b = c (mod modu), coefficients
+/// a_i being the elements of `coefs`. Modulo `modu` must be positive.
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MultiLinEq<T: UInt> {
+    pub coefs: Vec<T>,
+    pub b: T,
+    pub c: T,
+    pub modu: T,
+}
+
+/// Solutions of a multivariate linear equation modulo `modu`.
+///
+/// Every solution is `particular` + t_1 * v_1 + ... + t_m * v_m (mod modu) for some
+/// integers t_j, vectors v_j being the elements of `basis`, and every such vector is
+/// a solution. Basis vectors generate all solutions of the homogeneous equation but
+/// they aren't necessarily independent.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MultiLinSolution<T: UInt> {
+    pub particular: Vec<T>,
+    pub basis: Vec<Vec<T>>,
+    pub modu: T,
+}
+
+impl<T: UInt> MultiLinEq<T> {
+    /// Solve multivariate linear equation a_1 * x_1 + ... + a_k * x_k + b = c (mod modu).
+    ///
+    /// There are solutions if and only if gcd(a_1, ..., a_k, modu) divides c - b. If every
+    /// coefficient a_i is divisible by the modulo, the unknowns vanish from the equation
+    /// and, as for `LinEq`, every vector is a solution if b = c (mod modu).
+    ///
+    /// If there aren't solutions, None is returned.
+    ///
+    /// # Examples
+    ///
+    /// Solve equation 2x + 4y = 6 (mod 12)
+    ///
+    /// ```
+    /// use modular_equations::MultiLinEq;
+    ///
+    /// let lin_eq = MultiLinEq::<u32> {coefs: vec![2, 4], b: 0, c: 6, modu: 12};
+    ///
+    /// match lin_eq.solve() {
+    ///     Some(sol) => {
+    ///         // Solutions satisfy x + 2y = 3 (mod 6)
+    ///         for t in 0..12 {
+    ///             let x = sol.solution(&[t, 5]);
+    ///             assert_eq!((x[0] + 2 * x[1]) % 6, 3);
+    ///         }
+    ///     }
+    ///     None => panic!("no solutions"),
+    /// }
+    /// ```
+    pub fn solve(&self) -> Option<MultiLinSolution<T>> {
+        let modu = self.modu;
+
+        if modu == T::zero() {
+            return None;
+        }
+
+        let k = self.coefs.len();
+        let c = T::sub_mod(self.c, self.b, modu);
+
+        // Entries (a_1, ..., a_k, modu) and x rows of the transformation U (mod modu)
+        let mut vals: Vec<T> = self.coefs.iter().map(|&a| a % modu).collect();
+        vals.push(modu);
+
+        // Identity modulo `modu`, for modulo one all entries are zero
+        let one = T::one() % modu;
+
+        let mut transform: Vec<Vec<T>> = (0..k)
+            .map(|row| {
+                (0..=k)
+                    .map(|col| if row == col { one } else { T::zero() })
+                    .collect()
+            })
+            .collect();
+
+        while let Some(pivot) = (0..=k)
+            .filter(|&j| vals[j] > T::zero())
+            .min_by_key(|&j| vals[j])
+        {
+            let mut reduced = false;
+
+            for j in 0..=k {
+                if j == pivot || vals[j] == T::zero() {
+                    continue;
+                }
+
+                let q = vals[j] / vals[pivot];
+                vals[j] = vals[j] - q * vals[pivot];
+
+                for row in transform.iter_mut() {
+                    let sub = T::mult_mod(q, row[pivot], modu);
+                    row[j] = T::sub_mod(row[j], sub, modu);
+                }
+                reduced = true;
+            }
+
+            if !reduced {
+                break;
+            }
+        }
+
+        let pivot = (0..=k).find(|&j| vals[j] > T::zero())?;
+        let gcd = vals[pivot];
+
+        if c % gcd != T::zero() {
+            return None;
+        }
+
+        let multiplier = c / gcd;
+
+        let particular = transform
+            .iter()
+            .map(|row| T::mult_mod(row[pivot], multiplier, modu))
+            .collect();
+
+        let basis = (0..=k)
+            .filter(|&j| j != pivot)
+            .map(|j| transform.iter().map(|row| row[j]).collect::<Vec<T>>())
+            .filter(|v| v.iter().any(|&x| x > T::zero()))
+            .collect();
+
+        Some(MultiLinSolution {
+            particular,
+            basis,
+            modu,
+        })
+    }
+}
+
+impl<T: UInt> MultiLinSolution<T> {
+    /// Solution `particular` + t_1 * v_1 + ... + t_m * v_m (mod modu) for parameters `params`.
+    ///
+    /// Missing parameters are interpreted as zeros and extra parameters are ignored.
+    pub fn solution(&self, params: &[T]) -> Vec<T> {
+        let mut x = self.particular.clone();
+
+        for (v, &t) in self.basis.iter().zip(params.iter()) {
+            for (x_i, &v_i) in x.iter_mut().zip(v.iter()) {
+                *x_i = T::add_mod(*x_i, T::mult_mod(t, v_i, self.modu), self.modu);
+            }
+        }
+
+        x
+    }
+}
+
 impl<T, S> LinEqSigned<S, T>
 where
     S: Int + SignCast<S, T>,
@@ -132,6 +743,74 @@ where
     ///
     /// Please see the documentation of `LinEq` for examples.
     pub fn solve(&self) -> Option<Vec<T>> {
+        self.to_unsigned()?.solve()
+    }
+
+    /// Solve linear modular equation for signed type terms as `try_solve` of `LinEq`.
+    ///
+    /// If some of the coefficients cannot be cast to unsigned type, `SolveError::CastFailure`
+    /// is returned.
+    pub fn try_solve(&self) -> Result<Vec<T>, SolveError> {
+        self.to_unsigned()
+            .ok_or(SolveError::CastFailure)?
+            .try_solve()
+    }
+
+    /// Check whether signed `x` is a solution of linear modular equation for signed type terms.
+    ///
+    /// Coefficients and `x` are cast to unsigned type as in `solve` and the check is then
+    /// done by the `is_solution` method of `LinEq`. If some of the casts fails, false is returned.
+    pub fn is_solution(&self, x: S) -> bool {
+        if self.modu == T::zero() {
+            return false;
+        }
+
+        match (self.to_unsigned(), S::cast_to_unsigned(x, self.modu)) {
+            (Some(lin_eq), Some(x)) => lin_eq.is_solution(x),
+            _ => false,
+        }
+    }
+
+    /// Evaluate the left and right hand sides of linear modular equation for signed
+    /// type terms at signed `x` as the `sides` method of `LinEq`.
+    ///
+    /// Coefficients and `x` are cast to unsigned type as in `solve`. If some of the casts
+    /// fails or modulo is zero, None is returned.
+    pub fn sides(&self, x: S) -> Option<(T, T)> {
+        if self.modu == T::zero() {
+            return None;
+        }
+
+        self.to_unsigned()?
+            .sides(S::cast_to_unsigned(x, self.modu)?)
+    }
+
+    /// Solve linear modular equation for signed type terms, returning the solutions
+    /// as balanced representatives similarly to the `solve_balanced` method of `LinEq`.
+    pub fn solve_balanced(&self) -> Option<Vec<S>> {
+        self.to_unsigned()?.solve_balanced()
+    }
+
+    /// Find all integer solutions of linear modular equation for signed type terms in
+    /// range `lo` <= x < `hi` as the `solutions_in_range` method of `LinEq`.
+    ///
+    /// If some of the coefficients cannot be cast to unsigned type, an empty vector is returned.
+    pub fn solutions_in_range(&self, lo: T, hi: T) -> Vec<T> {
+        self.to_unsigned()
+            .map_or(vec![], |lin_eq| lin_eq.solutions_in_range(lo, hi))
+    }
+
+    /// Count the solutions of linear modular equation for signed type terms.
+    ///
+    /// Coefficients are cast to unsigned type as in `solve` and the solutions are then
+    /// counted by the `count_solutions` method of `LinEq`. If some of the casts fails,
+    /// zero is returned.
+    pub fn count_solutions(&self) -> T {
+        self.to_unsigned()
+            .map_or(T::zero(), |lin_eq| lin_eq.count_solutions())
+    }
+
+    fn to_unsigned(self) -> Option<LinEq<T>> {
         let a_us = match S::cast_to_unsigned(self.a, self.modu) {
             Some(a) => a,
             None => {
@@ -156,14 +835,12 @@ where
             }
         };
 
-        let lin_eq = LinEq {
+        Some(LinEq {
             a: a_us,
             b: b_us,
             c: c_us,
             modu: self.modu,
-        };
-
-        lin_eq.solve()
+        })
     }
 }
 
@@ -1,5 +1,7 @@
-use crate::lin::{LinEq, LinEqSigned};
-use crate::UInt;
+use std::collections::HashSet;
+
+use crate::lin::{self, BuildError, LinEq, LinEqSigned, MultiLinEq, SolveError};
+use crate::{arith::Arith, UInt};
 
 fn check_uniq_sol_correctness<T>(sol_cand: Option<Vec<T>>, sol_corr: T)
 where
@@ -212,6 +214,71 @@ fn eq_small_type_no_sol() {
     }
 }
 
+#[test]
+fn eq_vanishing_unknown() {
+    // 9x + 4 = 13 (mod 9) holds for every x, 9x + 4 = 5 (mod 9) for none
+    let lin_eq = LinEq::<u8> {
+        a: 9,
+        b: 4,
+        c: 13,
+        modu: 9,
+    };
+    assert_eq!(lin_eq.solve(), Some((0..9).collect()));
+    assert_eq!(lin_eq.try_solve(), Ok((0..9).collect()));
+
+    let lin_eq = LinEq::<u8> {
+        a: 9,
+        b: 4,
+        c: 5,
+        modu: 9,
+    };
+    assert_eq!(lin_eq.solve(), None);
+    assert_eq!(lin_eq.try_solve(), Err(SolveError::VariableVanished));
+
+    // Every residue holds but the modulo is too large for them to be enumerated
+    let lin_eq = LinEq::<u64> {
+        a: 1 << 40,
+        b: 1,
+        c: 1,
+        modu: 1 << 40,
+    };
+    assert_eq!(lin_eq.solve(), None);
+    assert_eq!(lin_eq.try_solve(), Err(SolveError::TooManySolutions));
+    assert_eq!(lin::solve_as_congruence(&lin_eq), Some((0, 1)));
+    assert_eq!(lin_eq.count_solutions(), 1 << 40);
+}
+
+#[test]
+fn eq_modulo_one() {
+    for (a, b, c) in [(0u32, 0, 0), (3, 1, 4), (u32::MAX, 2, 0)].iter() {
+        let lin_eq = LinEq {
+            a: *a,
+            b: *b,
+            c: *c,
+            modu: 1,
+        };
+        assert_eq!(
+            lin_eq.solve(),
+            Some(vec![0]),
+            "a: {}, b: {}, c: {}",
+            a,
+            b,
+            c
+        );
+    }
+
+    let lin_eq = MultiLinEq::<u32> {
+        coefs: vec![2, 5, 7],
+        b: 1,
+        c: 3,
+        modu: 1,
+    };
+    match lin_eq.solve() {
+        Some(sol) => assert_eq!(sol.solution(&[4, 2, 9]), vec![0, 0, 0]),
+        None => panic!("no solutions"),
+    }
+}
+
 #[test]
 fn eq_misc_uniq_sol() {
     // (a, b, c, modu, res): ax + b = c (mod modu), res solution
@@ -326,3 +393,497 @@ fn eq_large_signed_type_max_modu_uniq_sol() {
         check_uniq_sol_correctness(lin_eq.solve(), corr_sol);
     }
 }
+
+/// All solutions generated by the parametric description, found by enumerating parameters.
+fn span_of_multi_sols(lin_eq: &MultiLinEq<u32>) -> Option<HashSet<Vec<u32>>> {
+    let sol = lin_eq.solve()?;
+    let param_count = sol.basis.len();
+
+    let mut sols = HashSet::new();
+    let mut params = vec![0; param_count];
+
+    loop {
+        sols.insert(sol.solution(&params));
+
+        // Next parameter combination in range [0, modu)^param_count
+        match params.iter().position(|&t| t + 1 < lin_eq.modu) {
+            Some(idx) => {
+                params[idx] += 1;
+                params[..idx].iter_mut().for_each(|t| *t = 0);
+            }
+            None => break,
+        }
+    }
+
+    Some(sols)
+}
+
+#[test]
+fn multi_eq_brute_force() {
+    let coef_cases: [[u32; 2]; 6] = [[1, 0], [2, 4], [3, 5], [6, 10], [0, 9], [7, 14]];
+
+    for modu in 2..25u32 {
+        for coefs in coef_cases.iter() {
+            for c in 0..modu.min(6) {
+                let lin_eq = MultiLinEq {
+                    coefs: coefs.to_vec(),
+                    b: 1,
+                    c,
+                    modu,
+                };
+
+                let mut corr_sols = HashSet::new();
+
+                for x in 0..modu {
+                    for y in 0..modu {
+                        if (coefs[0] * x + coefs[1] * y + 1) % modu == c {
+                            corr_sols.insert(vec![x, y]);
+                        }
+                    }
+                }
+
+                let corr_sols = if corr_sols.is_empty() {
+                    None
+                } else {
+                    Some(corr_sols)
+                };
+
+                assert_eq!(
+                    span_of_multi_sols(&lin_eq),
+                    corr_sols,
+                    "coefs: {:?}, c: {}, modu: {}",
+                    coefs,
+                    c,
+                    modu
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn multi_eq_three_unknowns() {
+    // 6x + 10y + 15z = 1 (mod 30) has 30^2 solutions
+    let lin_eq = MultiLinEq::<u32> {
+        coefs: vec![6, 10, 15],
+        b: 0,
+        c: 1,
+        modu: 30,
+    };
+
+    match span_of_multi_sols(&lin_eq) {
+        Some(sols) => {
+            assert_eq!(sols.len(), 900);
+            assert!(sols
+                .iter()
+                .all(|x| (6 * x[0] + 10 * x[1] + 15 * x[2]) % 30 == 1));
+        }
+        None => panic!("no solutions"),
+    }
+}
+
+#[test]
+fn multi_eq_large_type() {
+    let modu = u128::MAX;
+    let lin_eq = MultiLinEq::<u128> {
+        coefs: vec![u128::MAX - 1, 3, 5],
+        b: 7,
+        c: 2,
+        modu,
+    };
+
+    match lin_eq.solve() {
+        Some(sol) => {
+            for params in [[0, 0], [1, 2], [u128::MAX - 1, 12_345]].iter() {
+                let x = sol.solution(params);
+                let lhs = lin_eq
+                    .coefs
+                    .iter()
+                    .zip(x.iter())
+                    .fold(lin_eq.b, |acc, (&a, &x_i)| {
+                        u128::add_mod(acc, u128::mult_mod(a, x_i, modu), modu)
+                    });
+
+                assert_eq!(lhs, lin_eq.c);
+            }
+        }
+        None => panic!("no solutions"),
+    }
+}
+
+#[test]
+fn multi_eq_vanishing_unknowns() {
+    // 8x + 16y = 0 (mod 8) holds for every pair (x, y)
+    let lin_eq = MultiLinEq::<u64> {
+        coefs: vec![8, 16],
+        b: 0,
+        c: 0,
+        modu: 8,
+    };
+
+    match lin_eq.solve() {
+        Some(sol) => {
+            let mut sols = HashSet::new();
+
+            for t_1 in 0..8 {
+                for t_2 in 0..8 {
+                    sols.insert(sol.solution(&[t_1, t_2]));
+                }
+            }
+            assert_eq!(sols.len(), 64);
+        }
+        None => panic!("no solutions"),
+    }
+}
+
+#[test]
+fn multi_eq_no_sol() {
+    let test_cases: [(Vec<u64>, u64, u64); 4] = [
+        (vec![2, 4, 6], 1, 8),
+        (vec![8, 16], 1, 8),
+        (vec![], 1, 8),
+        (vec![1, 1], 1, 0),
+    ];
+
+    for (coefs, c, modu) in test_cases.iter() {
+        let lin_eq = MultiLinEq {
+            coefs: coefs.clone(),
+            b: 0,
+            c: *c,
+            modu: *modu,
+        };
+
+        assert_eq!(lin_eq.solve(), None, "coefs: {:?}", coefs);
+    }
+}
+
+#[test]
+fn try_solve_errors() {
+    let test_cases = [
+        (
+            LinEqSigned::<i16, u16> {
+                a: 3,
+                b: 1,
+                c: 4,
+                modu: 9,
+            },
+            Ok(vec![1, 4, 7]),
+        ),
+        (
+            LinEqSigned {
+                a: 3,
+                b: 1,
+                c: 5,
+                modu: 9,
+            },
+            Err(SolveError::NoSolution),
+        ),
+        (
+            LinEqSigned {
+                a: 3,
+                b: 1,
+                c: 4,
+                modu: 1,
+            },
+            Ok(vec![0]),
+        ),
+        (
+            LinEqSigned {
+                a: 3,
+                b: 1,
+                c: 4,
+                modu: 0,
+            },
+            Err(SolveError::InvalidModulus),
+        ),
+        (
+            LinEqSigned {
+                a: -9,
+                b: 1,
+                c: 4,
+                modu: 9,
+            },
+            Err(SolveError::VariableVanished),
+        ),
+        (
+            LinEqSigned {
+                a: 3,
+                b: i16::MIN,
+                c: 4,
+                modu: 9,
+            },
+            Err(SolveError::CastFailure),
+        ),
+    ];
+
+    for (lin_eq, corr_res) in test_cases.iter() {
+        assert_eq!(lin_eq.try_solve(), *corr_res, "{:?}", lin_eq);
+    }
+}
+
+#[test]
+fn count_solutions_matches_solve() {
+    for modu in 0..60u16 {
+        for a in 0..modu + 2 {
+            for c in 0..modu + 2 {
+                let lin_eq = LinEq { a, b: 1, c, modu };
+                let sol_count = lin_eq.solve().map_or(0, |sols| sols.len());
+
+                assert_eq!(lin_eq.count_solutions() as usize, sol_count, "{:?}", lin_eq);
+            }
+        }
+    }
+}
+
+#[test]
+fn count_solutions_large_modulo() {
+    let lin_eq = LinEq::<u128> {
+        a: 1 << 100,
+        b: 5,
+        c: 5,
+        modu: 1 << 120,
+    };
+    assert_eq!(lin_eq.count_solutions(), 1 << 100);
+
+    let lin_eq = LinEqSigned::<i128, u128> {
+        a: 0,
+        b: -1,
+        c: u128::MAX as i128,
+        modu: u128::MAX,
+    };
+    assert_eq!(lin_eq.count_solutions(), u128::MAX);
+
+    let lin_eq = LinEqSigned::<i128, u128> {
+        a: i128::MIN,
+        b: 0,
+        c: 1,
+        modu: 7,
+    };
+    assert_eq!(lin_eq.count_solutions(), 0);
+}
+
+#[test]
+fn solutions_in_range() {
+    for modu in 1..40u16 {
+        for a in 0..modu {
+            for (lo, hi) in [
+                (0, 0),
+                (5, 3),
+                (0, 100),
+                (17, 250),
+                (u16::MAX - 90, u16::MAX),
+            ]
+            .iter()
+            {
+                let lin_eq = LinEq {
+                    a,
+                    b: 3,
+                    c: 9,
+                    modu,
+                };
+                let corr_sols: Vec<u16> = (*lo..*hi)
+                    .filter(|&x| (a as u32 * x as u32 + 3) % modu as u32 == 9 % modu as u32)
+                    .collect();
+
+                assert_eq!(
+                    lin_eq.solutions_in_range(*lo, *hi),
+                    corr_sols,
+                    "{:?}, lo: {}, hi: {}",
+                    lin_eq,
+                    lo,
+                    hi
+                );
+            }
+        }
+    }
+
+    let lin_eq = LinEqSigned::<i64, u64> {
+        a: -1,
+        b: 0,
+        c: 1,
+        modu: 1 << 40,
+    };
+    assert_eq!(
+        lin_eq.solutions_in_range(0, 3 << 40),
+        vec![(1 << 40) - 1, (2 << 40) - 1, (3 << 40) - 1]
+    );
+}
+
+#[test]
+fn solve_balanced() {
+    for modu in 1..60u8 {
+        for a in 0..modu {
+            let lin_eq = LinEq {
+                a,
+                b: 0,
+                c: 4,
+                modu,
+            };
+
+            let corr_sols = lin_eq.solve().map(|sols| {
+                let mut sols: Vec<i8> = sols
+                    .iter()
+                    .map(|&x| {
+                        if 2 * x > modu {
+                            x as i8 - modu as i8
+                        } else {
+                            x as i8
+                        }
+                    })
+                    .collect();
+                sols.sort();
+                sols
+            });
+
+            assert_eq!(lin_eq.solve_balanced::<i8>(), corr_sols, "{:?}", lin_eq);
+        }
+    }
+
+    let lin_eq = LinEqSigned::<i64, u64> {
+        a: 2,
+        b: 0,
+        c: -2,
+        modu: 1 << 40,
+    };
+    assert_eq!(lin_eq.solve_balanced(), Some(vec![-1, (1 << 39) - 1]));
+}
+
+#[test]
+fn is_solution() {
+    for modu in 0..40u32 {
+        for a in 0..modu + 1 {
+            let lin_eq = LinEq {
+                a,
+                b: 5,
+                c: 2,
+                modu,
+            };
+            let sols = lin_eq.solve().unwrap_or_default();
+
+            for x in 0..2 * modu {
+                assert_eq!(
+                    lin_eq.is_solution(x),
+                    sols.contains(&(x % modu)),
+                    "{:?}, x: {}",
+                    lin_eq,
+                    x
+                );
+            }
+        }
+    }
+
+    let lin_eq = LinEqSigned::<i32, u32> {
+        a: -3,
+        b: 1,
+        c: 4,
+        modu: 7,
+    };
+    assert!(lin_eq.is_solution(-1) && lin_eq.is_solution(6) && lin_eq.is_solution(-8));
+    assert!(!lin_eq.is_solution(1) && !lin_eq.is_solution(i32::MIN));
+}
+
+#[test]
+fn sides() {
+    let lin_eq = LinEq::<u32> {
+        a: 3,
+        b: 3,
+        c: 1,
+        modu: 1223,
+    };
+    assert_eq!(lin_eq.sides(407), Some((1, 1)));
+    assert_eq!(lin_eq.sides(407 + 1223), Some((1, 1)));
+    assert_eq!(lin_eq.sides(1222), Some((0, 1)));
+    assert_eq!(LinEq::<u32> { modu: 0, ..lin_eq }.sides(1), None);
+
+    let lin_eq = LinEqSigned::<i32, u32> {
+        a: -3,
+        b: 1,
+        c: 11,
+        modu: 7,
+    };
+    assert_eq!(lin_eq.sides(-1), Some((4, 4)));
+    assert_eq!(lin_eq.sides(1), Some((5, 4)));
+    assert_eq!(lin_eq.sides(i32::MIN), None);
+}
+
+#[test]
+fn eq_signed_wider_modulo_type() {
+    let lin_eq = LinEqSigned::<i32, u64> {
+        a: -3,
+        b: 7,
+        c: i32::MIN + 1,
+        modu: 1 << 40,
+    };
+    let a_us = (1u64 << 40) - 3;
+    let c_us = (1u64 << 40) - (i32::MAX as u64) - 7;
+
+    match lin_eq.solve() {
+        Some(sols) => {
+            assert_eq!(sols.len(), 1);
+            assert_eq!(u64::mult_mod(a_us, sols[0], 1 << 40), c_us);
+        }
+        None => panic!("no solutions"),
+    }
+
+    let lin_eq = LinEqSigned::<i8, u128> {
+        a: 2,
+        b: -1,
+        c: 0,
+        modu: u128::MAX,
+    };
+    assert_eq!(lin_eq.solve(), Some(vec![u128::MAX / 2 + 1]));
+    assert_eq!(lin_eq.solve_balanced(), None);
+
+    let lin_eq = LinEqSigned::<i8, u128> {
+        a: i8::MIN,
+        b: -1,
+        c: 0,
+        modu: u128::MAX,
+    };
+    assert_eq!(lin_eq.try_solve(), Err(SolveError::CastFailure));
+}
+
+#[test]
+fn builder() {
+    match LinEq::<u8>::builder()
+        .a_signed(-1i8)
+        .b(200)
+        .c_signed(i8::MAX)
+        .modu(100)
+        .build()
+    {
+        Ok(lin_eq) => {
+            assert_eq!(
+                (lin_eq.a, lin_eq.b, lin_eq.c, lin_eq.modu),
+                (99, 0, 27, 100)
+            );
+            assert_eq!(lin_eq.solve(), Some(vec![73]));
+        }
+        Err(err) => panic!("{}", err),
+    }
+
+    match LinEq::<u128>::builder().a_signed(-5i32).modu(1).build() {
+        Ok(lin_eq) => assert_eq!((lin_eq.a, lin_eq.b, lin_eq.c), (0, 0, 0)),
+        Err(err) => panic!("{}", err),
+    }
+
+    let test_cases = [
+        (
+            LinEq::<u32>::builder().a(1).c(2),
+            BuildError::MissingModulus,
+        ),
+        (LinEq::builder().a(1).modu(0), BuildError::InvalidModulus),
+        (
+            LinEq::builder().a(1).b_signed(i32::MIN).modu(0),
+            BuildError::CastFailure,
+        ),
+        (
+            LinEq::builder().c_signed(i16::MIN).c(1).modu(3),
+            BuildError::CastFailure,
+        ),
+    ];
+
+    for (builder, corr_err) in test_cases.iter() {
+        assert_eq!(builder.build().err(), Some(*corr_err), "{:?}", builder);
+    }
+}
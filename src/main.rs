@@ -7,58 +7,543 @@
 //! modulo M the corresponding unsigned type. This restriction applies
 //! only for the CLI, not for the library.
 //!
-//! Following example shows how to solve a linear equation, assuming
-//! that the binary has been built (or whatever installed) and made
-//! accessible
+//! Program is used through subcommands. Following example shows how to solve
+//! a linear equation, assuming that the binary has been built (or whatever installed)
+//! and made accessible
 //!
 //! ```bash
-//! modular_equations b c d n
+//! modular_equations solve b c d n
 //! ```
 //!
 //! Solving quadratic equation is almost the same, one just needs to add
 //! the a coefficient as the following example shows
 //!
 //! ```bash
-//! modular_equations a b c d n
+//! modular_equations solve a b c d n
 //! ```
 //!
+//! and cubic equation ax^3 + bx^2 + cx + d = e (mod n) is given by six arguments
+//! `a b c d e n`. Each further argument raises the degree by one, e.g. seven arguments
+//! give a quartic equation. Zero leading coefficient lowers the degree of the equation.
+//!
+//! Equation can also be given in the notation "3x^2 + 2x + 1 = 5 (mod 17)" as a single
+//! argument. Subcommand `solve` can be left out, e.g.
+//!
+//! ```bash
+//! modular_equations "3x^2 + 2x - 1 = 0 (mod 17)"
+//! ```
+//!
+//! Other subcommands are `factor n`, `prime n`, `inverse a n`, `crt r_1 m_1 r_2 m_2 ...`,
+//! `dlog g h n`, `order a n` and `totient n`.
+//!
+//! Integer arguments are given in decimal or as `0x`, `0b` or `0o` prefixed literals,
+//! underscores being allowed between the digits.
+//!
+//! With option `--output json`, or its shorthand `--json`, results are printed as JSON
+//! objects, e.g. solutions as {"modulus": "n", "solutions": ["x_1", ...], "count": k}, and
+//! errors as objects {"error": "<code>", ...}, e.g. {"error": "no_solution"}. Integers are
+//! given as strings as they may exceed the range of JSON numbers. Option `--output csv`
+//! prints solutions of an equation or subcommand `crt` as rows
+//! `modulus,solution_index,solution` after a header row, only the header if there
+//! are no solutions.
+//!
+//! Solutions of an equation can be summarized with flag `--count-only`, printing only
+//! their count, or limited with option `--limit N`, printing at most N solutions. JSON
+//! output has the total count of solutions also when they are limited. With flag
+//! `--balanced` solutions are printed as representatives in (-n/2, n/2] instead of
+//! the smallest nonnegative ones. Option `--check X` doesn't solve the equation but
+//! prints whether X is a solution, with the values of both sides modulo n.
+//!
+//! Factorization of a composite modulo of a quadratic or higher degree equation or
+//! the number of the factor command can be tuned with options `--max-curves N`, `--curve-bound N`,
+//! `--small-prime-bound N`, `--trial-bound N`, `--timeout SECONDS` and `--workers N`
+//! (alias `--threads N`).
+//! Timeout applies also to the factorization of subcommands `totient`, `dlog`, `order` and
+//! `solve` with flag `--count-only`. If factorization doesn't complete in time, the program prints
+//! "undecided (timeout)".
+//!
+//! Exit code of the program tells the outcome:
+//!
+//! - 0: the task was solved, e.g. the equation has solutions
+//! - 1: there is no solution, inverse, logarithm or order, or the value given to
+//!   `--check` isn't a solution
+//! - 2: invalid input, e.g. malformed arguments or an equation without the unknown
+//! - 3: timeout or other internal error, the result being left undecided
+//!
 use std::{env, process};
 
 extern crate modular_equations;
-use modular_equations::{LinEqSigned, QuadEqSigned, UInt};
+use modular_equations::{
+    crt, is_prime, multip_inv, CubicEqSigned, DiscreteLog, FactorConfig, FactorError, Factors,
+    LinEqSigned, PolyEqSigned, QuadEqSigned, UInt,
+};
 
 mod parser;
-use parser::EquaKind;
+use parser::{ArgError, CliArgs, EquaKind, Task};
+
+const U64_VALID_MAX: u128 = u64::MAX as u128;
+
+/// Exit code when there is no solution for the task.
+const EXIT_NO_SOLUTION: i32 = 1;
+
+/// Exit code for invalid command line arguments.
+const EXIT_INVALID_INPUT: i32 = 2;
+
+/// Exit code when the result is left undecided because of the timeout or an internal error.
+const EXIT_UNDECIDED: i32 = 3;
 
 fn main() {
     let args: Vec<String> = env::args().collect();
+    let json = parser::is_json_requested(&args);
 
-    let equa = parser::parse_args(&args[1..]).unwrap_or_else(|err| {
-        if err == "help" {
+    let cli_args = parser::parse_args(&args).unwrap_or_else(|err| {
+        if err == ArgError::Info {
             process::exit(0);
         }
 
-        eprintln!("Error with command line args: {}", err);
-        process::exit(1);
+        if json {
+            println!("{}", err.to_json());
+        } else {
+            eprintln!("Error with command line args: {}", err);
+        }
+        process::exit(EXIT_INVALID_INPUT);
     });
 
-    match &equa {
-        EquaKind::LinearI64(eq_lin) => print_sol(eq_lin.solve(), eq_lin.modu),
-        EquaKind::QuadI64(eq_quad) => print_sol(eq_quad.solve(), eq_quad.modu),
-        EquaKind::LinearI128(eq_lin_large) => print_sol(eq_lin_large.solve(), eq_lin_large.modu),
-        EquaKind::QuadI128(eq_quad_large) => print_sol(eq_quad_large.solve(), eq_quad_large.modu),
+    let json = cli_args.json;
+    let config = &cli_args.factor_config;
+
+    let solved = match &cli_args.task {
+        Task::Solve(equa) if cli_args.check.is_some() => {
+            check(equa, cli_args.check.unwrap_or_default(), json)
+        }
+        Task::Solve(equa) if cli_args.count_only => count(equa, config, json),
+        Task::Solve(equa) => solve(equa, &cli_args),
+        &Task::Factor(num) if num <= U64_VALID_MAX => {
+            factor(num as u64, config, json);
+            true
+        }
+        &Task::Factor(num) => {
+            factor(num, config, json);
+            true
+        }
+        &Task::Prime(num) => {
+            print_prime(num, is_prime(num), json);
+            true
+        }
+        &Task::Inverse { a, modu } => {
+            // Reduce in u128, as `modu` may exceed i128::MAX
+            let a = if a >= 0 {
+                a as u128 % modu
+            } else {
+                (modu - a.unsigned_abs() % modu) % modu
+            };
+            print_inverse(a, multip_inv(a, modu), modu, json)
+        }
+        Task::Crt(congruences) if cli_args.csv => {
+            let solution = crt(congruences);
+            let (sols, modu) =
+                solution.map_or((vec![], 0), |(x, modu)| (vec![x.to_string()], modu));
+            print_sol_csv(&sols, modu, None);
+            solution.is_some()
+        }
+        Task::Crt(congruences) => print_crt(crt(congruences), json),
+        &Task::Dlog { g, h, modu } => {
+            let dlog = DiscreteLog { g, h, modu };
+            let solution = dlog.solve_with_order_config(config);
+            print_dlog(unwrap_or_exit(solution, json), modu, json)
+        }
+        &Task::Order { a, modu } => {
+            let dlog = DiscreteLog { g: a, h: 1, modu };
+            let solution = unwrap_or_exit(dlog.solve_with_order_config(config), json);
+            print_order(a, solution.map(|(_, ord)| ord), modu, json)
+        }
+        &Task::Totient(num) if num <= 1 => {
+            print_totient(num, num, json);
+            true
+        }
+        &Task::Totient(num) => {
+            let mut factors = Factors::new(num);
+            unwrap_or_exit(factors.factorize_with_config(config), json);
+
+            let phi = factors
+                .iter()
+                .fold(1, |phi, (prm, k)| phi * prm.pow(k as u32 - 1) * (prm - 1));
+            print_totient(num, phi, json);
+            true
+        }
+    };
+
+    if !solved {
+        process::exit(EXIT_NO_SOLUTION);
+    }
+}
+
+/// Print the solutions, returning whether there are any.
+fn solve(equa: &EquaKind, cli_args: &CliArgs) -> bool {
+    let (json, config) = (cli_args.json, &cli_args.factor_config);
+
+    match equa {
+        EquaKind::LinearI64(eq_lin) => print_sol(eq_lin.solve(), eq_lin.modu, cli_args),
+        EquaKind::QuadI64(eq_quad) => {
+            let solution = eq_quad.solve_with_config(config);
+            print_sol(unwrap_or_exit(solution, json), eq_quad.modu, cli_args)
+        }
+        EquaKind::LinearI128(eq_lin_large) => {
+            print_sol(eq_lin_large.solve(), eq_lin_large.modu, cli_args)
+        }
+        EquaKind::QuadI128(eq_quad_large) => {
+            let solution = eq_quad_large.solve_with_config(config);
+            print_sol(unwrap_or_exit(solution, json), eq_quad_large.modu, cli_args)
+        }
+        EquaKind::CubicI64(eq_cubic) => {
+            let solution = eq_cubic.solve_with_config(config);
+            print_sol(unwrap_or_exit(solution, json), eq_cubic.modu, cli_args)
+        }
+        EquaKind::CubicI128(eq_cubic_large) => {
+            let solution = eq_cubic_large.solve_with_config(config);
+            print_sol(
+                unwrap_or_exit(solution, json),
+                eq_cubic_large.modu,
+                cli_args,
+            )
+        }
+        EquaKind::PolyI64(eq_poly) => {
+            let solution = eq_poly.solve_with_config(config);
+            print_sol(unwrap_or_exit(solution, json), eq_poly.modu, cli_args)
+        }
+        EquaKind::PolyI128(eq_poly_large) => {
+            let solution = eq_poly_large.solve_with_config(config);
+            print_sol(unwrap_or_exit(solution, json), eq_poly_large.modu, cli_args)
+        }
     }
 }
 
-fn print_sol<T: UInt>(solution: Option<Vec<T>>, modu: T) {
+/// Print whether `x` is a solution of the equation, with the values of both sides.
+fn check(equa: &EquaKind, x: i128, json: bool) -> bool {
+    match equa {
+        EquaKind::LinearI64(eq_lin) => {
+            print_check(x, eq_lin.sides(to_i64(x, eq_lin.modu)), eq_lin.modu, json)
+        }
+        EquaKind::QuadI64(eq_quad) => print_check(
+            x,
+            eq_quad.sides(to_i64(x, eq_quad.modu)),
+            eq_quad.modu,
+            json,
+        ),
+        EquaKind::LinearI128(eq_lin_large) => {
+            print_check(x, eq_lin_large.sides(x), eq_lin_large.modu, json)
+        }
+        EquaKind::QuadI128(eq_quad_large) => {
+            print_check(x, eq_quad_large.sides(x), eq_quad_large.modu, json)
+        }
+        EquaKind::CubicI64(eq_cubic) => print_check(
+            x,
+            eq_cubic.sides(to_i64(x, eq_cubic.modu)),
+            eq_cubic.modu,
+            json,
+        ),
+        EquaKind::CubicI128(eq_cubic_large) => {
+            print_check(x, eq_cubic_large.sides(x), eq_cubic_large.modu, json)
+        }
+        EquaKind::PolyI64(eq_poly) => print_check(
+            x,
+            eq_poly.sides(to_i64(x, eq_poly.modu)),
+            eq_poly.modu,
+            json,
+        ),
+        EquaKind::PolyI128(eq_poly_large) => {
+            print_check(x, eq_poly_large.sides(x), eq_poly_large.modu, json)
+        }
+    }
+}
+
+/// Reduce `x` to an i64 value of the same residue class modulo `modu`.
+fn to_i64(x: i128, modu: u64) -> i64 {
+    let x = x.rem_euclid(modu as i128);
+
+    // Residue above i64::MAX has a negative representative fitting into i64
+    i64::try_from(x).unwrap_or((x - modu as i128) as i64)
+}
+
+/// Print the count of solutions, counted without enumerating them, returning whether
+/// there are any.
+fn count(equa: &EquaKind, config: &FactorConfig, json: bool) -> bool {
+    match equa {
+        EquaKind::LinearI64(eq_lin) => print_count(eq_lin.count_solutions(), eq_lin.modu, json),
+        EquaKind::QuadI64(eq_quad) => {
+            let count = eq_quad.count_solutions_with_config(config);
+            print_count(unwrap_or_exit(count, json), eq_quad.modu, json)
+        }
+        EquaKind::LinearI128(eq_lin_large) => {
+            print_count(eq_lin_large.count_solutions(), eq_lin_large.modu, json)
+        }
+        EquaKind::QuadI128(eq_quad_large) => {
+            let count = eq_quad_large.count_solutions_with_config(config);
+            print_count(unwrap_or_exit(count, json), eq_quad_large.modu, json)
+        }
+        // Solutions of cubic and higher degree equations are counted by enumerating them
+        EquaKind::CubicI64(eq_cubic) => {
+            let solution = unwrap_or_exit(eq_cubic.solve_with_config(config), json);
+            print_count(
+                solution.map_or(0, |sols| sols.len() as u64),
+                eq_cubic.modu,
+                json,
+            )
+        }
+        EquaKind::CubicI128(eq_cubic_large) => {
+            let solution = unwrap_or_exit(eq_cubic_large.solve_with_config(config), json);
+            let count = solution.map_or(0, |sols| sols.len() as u128);
+            print_count(count, eq_cubic_large.modu, json)
+        }
+        EquaKind::PolyI64(eq_poly) => {
+            let solution = unwrap_or_exit(eq_poly.solve_with_config(config), json);
+            print_count(
+                solution.map_or(0, |sols| sols.len() as u64),
+                eq_poly.modu,
+                json,
+            )
+        }
+        EquaKind::PolyI128(eq_poly_large) => {
+            let solution = unwrap_or_exit(eq_poly_large.solve_with_config(config), json);
+            let count = solution.map_or(0, |sols| sols.len() as u128);
+            print_count(count, eq_poly_large.modu, json)
+        }
+    }
+}
+
+fn print_count<T: UInt>(count: T, modu: T, json: bool) -> bool {
+    if json {
+        println!("{{\"modulus\": \"{}\", \"count\": {}}}", modu, count);
+    } else {
+        println!("Count of solutions x in Z/{}Z: {}", modu, count);
+    }
+
+    count > T::zero()
+}
+
+/// Exit with an error if factorization didn't complete, leaving the result undecided.
+fn unwrap_or_exit<T>(solution: Result<T, FactorError>, json: bool) -> T {
+    solution.unwrap_or_else(|err| {
+        match (err, json) {
+            (FactorError::Timeout, true) => {
+                println!("{{\"error\": \"factorization_timeout\", \"status\": \"undecided\"}}")
+            }
+            (FactorError::Cancelled, true) => {
+                println!("{{\"error\": \"factorization_cancelled\"}}")
+            }
+            (FactorError::Timeout, false) => println!("undecided (timeout)"),
+            (FactorError::Cancelled, false) => eprintln!("Error: {}", err),
+        }
+
+        process::exit(EXIT_UNDECIDED);
+    })
+}
+
+/// Print the solutions, at most `limit` of them if it's given. Balanced solutions
+/// are printed in increasing order as representatives in (-n/2, n/2].
+fn print_sol<T: UInt>(solution: Option<Vec<T>>, modu: T, cli_args: &CliArgs) -> bool {
+    let solution = solution.map(|sols| {
+        if cli_args.balanced {
+            to_balanced(sols, modu)
+        } else {
+            sols.iter().map(|x| x.to_string()).collect()
+        }
+    });
+
+    let solved = solution.is_some();
+
+    if cli_args.json {
+        print_sol_json(solution, modu, cli_args.limit);
+        return solved;
+    }
+    if cli_args.csv {
+        print_sol_csv(&solution.unwrap_or_default(), modu, cli_args.limit);
+        return solved;
+    }
+
     match solution {
         None => println!("There is no solution in Z/{}Z", modu),
         Some(sols) => {
             println!("Solutions x in Z/{}Z", modu);
 
-            for (j, x) in sols.iter().enumerate() {
-                println!("x_{}: {}", j + 1, *x);
+            let shown = cli_args.limit.unwrap_or(usize::MAX).min(sols.len());
+
+            for (j, x) in sols[..shown].iter().enumerate() {
+                println!("x_{}: {}", j + 1, x);
+            }
+            if shown < sols.len() {
+                println!("... and {} more", sols.len() - shown);
             }
         }
     }
+
+    solved
+}
+
+/// Map sorted solutions in [0, n) to sorted representatives in (-n/2, n/2].
+fn to_balanced<T: UInt>(sols: Vec<T>, modu: T) -> Vec<String> {
+    let half = modu / (T::one() + T::one());
+    let split = sols.partition_point(|&x| x <= half);
+
+    sols[split..]
+        .iter()
+        .map(|&x| format!("-{}", modu - x))
+        .chain(sols[..split].iter().map(|x| x.to_string()))
+        .collect()
+}
+
+fn print_sol_json<T: UInt>(solution: Option<Vec<String>>, modu: T, limit: Option<usize>) {
+    match solution {
+        None => println!("{{\"error\": \"no_solution\", \"modulus\": \"{}\"}}", modu),
+        Some(sols) => {
+            let shown: Vec<String> = sols
+                .iter()
+                .take(limit.unwrap_or(usize::MAX))
+                .map(|x| format!("\"{}\"", x))
+                .collect();
+
+            println!(
+                "{{\"modulus\": \"{}\", \"solutions\": [{}], \"count\": {}}}",
+                modu,
+                shown.join(", "),
+                sols.len()
+            );
+        }
+    }
+}
+
+/// Print the solutions as CSV rows with a header, only the header if there are none.
+fn print_sol_csv<T: UInt>(sols: &[String], modu: T, limit: Option<usize>) {
+    println!("modulus,solution_index,solution");
+
+    for (j, x) in sols.iter().take(limit.unwrap_or(usize::MAX)).enumerate() {
+        println!("{},{},{}", modu, j + 1, x);
+    }
+}
+
+fn print_check<T: UInt>(x: i128, sides: Option<(T, T)>, modu: T, json: bool) -> bool {
+    let Some((lhs, rhs)) = sides else {
+        if json {
+            println!("{{\"error\": \"invalid_value\", \"x\": \"{}\"}}", x);
+        } else {
+            eprintln!("Error: cannot evaluate the equation at x = {}", x);
+        }
+        process::exit(EXIT_INVALID_INPUT);
+    };
+
+    if json {
+        println!(
+            "{{\"x\": \"{}\", \"modulus\": \"{}\", \"lhs\": \"{}\", \"rhs\": \"{}\", \
+             \"is_solution\": {}}}",
+            x,
+            modu,
+            lhs,
+            rhs,
+            lhs == rhs
+        );
+    } else {
+        let verdict = if lhs == rhs { "is" } else { "is not" };
+        println!("x = {} {} a solution in Z/{}Z", x, verdict, modu);
+        println!("lhs: {}, rhs: {}", lhs, rhs);
+    }
+
+    lhs == rhs
+}
+
+fn factor<T: 'static + UInt>(num: T, config: &FactorConfig, json: bool) {
+    let mut factors = Factors::new(num);
+    unwrap_or_exit(factors.factorize_with_config(config), json);
+
+    if json {
+        let factors: Vec<String> = factors
+            .iter()
+            .map(|(prm, k)| format!("{{\"prime\": \"{}\", \"exponent\": {}}}", prm, k))
+            .collect();
+
+        println!(
+            "{{\"number\": \"{}\", \"factors\": [{}]}}",
+            num,
+            factors.join(", ")
+        );
+    } else {
+        let factors: Vec<String> = factors
+            .iter()
+            .map(|(prm, k)| match k {
+                1 => prm.to_string(),
+                _ => format!("{}^{}", prm, k),
+            })
+            .collect();
+
+        println!("{} = {}", num, factors.join(" * "));
+    }
+}
+
+fn print_prime(num: u128, prime: bool, json: bool) {
+    if json {
+        println!("{{\"number\": \"{}\", \"is_prime\": {}}}", num, prime);
+    } else if prime {
+        println!("{} is prime", num);
+    } else {
+        println!("{} is not prime", num);
+    }
+}
+
+fn print_inverse(a: u128, inverse: Option<u128>, modu: u128, json: bool) -> bool {
+    match (inverse, json) {
+        (Some(x), true) => println!("{{\"modulus\": \"{}\", \"inverse\": \"{}\"}}", modu, x),
+        (None, true) => println!("{{\"error\": \"no_inverse\", \"modulus\": \"{}\"}}", modu),
+        (Some(x), false) => println!("Inverse of {} in Z/{}Z: {}", a, modu, x),
+        (None, false) => println!("{} has no inverse in Z/{}Z", a, modu),
+    }
+
+    inverse.is_some()
+}
+
+fn print_crt(solution: Option<(u128, u128)>, json: bool) -> bool {
+    match (solution, json) {
+        (Some((x, modu)), true) => {
+            println!(
+                "{{\"modulus\": \"{}\", \"solutions\": [\"{}\"], \"count\": 1}}",
+                modu, x
+            )
+        }
+        (None, true) => println!("{{\"error\": \"no_solution\"}}"),
+        (Some((x, modu)), false) => println!("x = {} (mod {})", x, modu),
+        (None, false) => println!("Congruences have no common solution"),
+    }
+
+    solution.is_some()
+}
+
+fn print_dlog(solution: Option<(u128, u128)>, modu: u128, json: bool) -> bool {
+    match (solution, json) {
+        (Some((x, ord)), true) => println!(
+            "{{\"modulus\": \"{}\", \"logarithm\": \"{}\", \"order\": \"{}\"}}",
+            modu, x, ord
+        ),
+        (None, true) => println!("{{\"error\": \"no_solution\", \"modulus\": \"{}\"}}", modu),
+        (Some((x, ord)), false) => println!("x = {} (mod {})", x, ord),
+        (None, false) => println!("There is no logarithm in Z/{}Z", modu),
+    }
+
+    solution.is_some()
+}
+
+fn print_order(a: u128, order: Option<u128>, modu: u128, json: bool) -> bool {
+    match (order, json) {
+        (Some(ord), true) => println!("{{\"modulus\": \"{}\", \"order\": \"{}\"}}", modu, ord),
+        (None, true) => println!("{{\"error\": \"not_unit\", \"modulus\": \"{}\"}}", modu),
+        (Some(ord), false) => println!("Order of {} in Z/{}Z: {}", a, modu, ord),
+        (None, false) => println!("{} is not a unit in Z/{}Z", a, modu),
+    }
+
+    order.is_some()
+}
+
+fn print_totient(num: u128, phi: u128, json: bool) {
+    if json {
+        println!("{{\"number\": \"{}\", \"totient\": \"{}\"}}", num, phi);
+    } else {
+        println!("totient({}) = {}", num, phi);
+    }
 }
@@ -0,0 +1,422 @@
+//! Implements the textual notation of linear, quadratic and polynomial equations.
+//!
+//! Equations are displayed in the form "3x^2 + 2x + 1 ≡ 5 (mod 17)", where terms with
+//! zero coefficient are left out and coefficient one of the unknown isn't shown. Parsing
+//! accepts the same notation, whitespace being optional and "=" being accepted in place
+//! of "≡". Terms of the left-hand side can be given in any order but each power of the
+//! unknown at most once, e.g. "-x + 4 + 2x^2 = 0 (mod 9)" is a valid quadratic equation.
+//! Degree of a polynomial equation is the highest power of the unknown in the notation,
+//! at most `MAX_POLY_DEGREE`.
+//!
+//! Negative coefficients of the unsigned equation types are turned to the smallest
+//! nonnegative representatives of their residue classes, similarly to the cast of
+//! signed coefficients done by the solvers of the signed equation types.
+//!
+use std::{error::Error, fmt, str::FromStr};
+
+use crate::{
+    arith::Arith, Int, LinEq, LinEqSigned, PolyEq, PolyEqSigned, QuadEq, QuadEqSigned, UInt,
+};
+
+/// Highest power of the unknown accepted for polynomial equations.
+const MAX_POLY_DEGREE: usize = 255;
+
+/// Errors that can occur when parsing an equation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseEquationError {
+    /// Equation doesn't follow the notation.
+    InvalidSyntax,
+    /// Coefficient or modulo doesn't fit into its type.
+    InvalidNumber,
+    /// Same power of the unknown occurs more than once.
+    RepeatedTerm,
+    /// Power of the unknown is too high for the equation type.
+    UnsupportedDegree,
+    /// Modulo is zero.
+    InvalidModulus,
+}
+
+impl fmt::Display for ParseEquationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseEquationError::InvalidSyntax => write!(f, "invalid equation syntax"),
+            ParseEquationError::InvalidNumber => write!(f, "number doesn't fit into its type"),
+            ParseEquationError::RepeatedTerm => write!(f, "power of the unknown is repeated"),
+            ParseEquationError::UnsupportedDegree => {
+                write!(f, "power of the unknown is too high")
+            }
+            ParseEquationError::InvalidModulus => write!(f, "modulus must be positive"),
+        }
+    }
+}
+
+impl Error for ParseEquationError {}
+
+/// Integer in the notation, given by its sign and decimal digits.
+struct Number {
+    negative: bool,
+    digits: String,
+}
+
+/// Equation parsed from the notation, `coefs` being the coefficients in ascending order.
+struct ParsedEquation {
+    coefs: Vec<Number>,
+    rhs: Number,
+    modu: String,
+}
+
+impl Number {
+    fn zero() -> Self {
+        Self {
+            negative: false,
+            digits: "0".to_string(),
+        }
+    }
+
+    fn to_unsigned<T: UInt>(&self, modu: T) -> Result<T, ParseEquationError> {
+        let abs =
+            T::from_str_radix(&self.digits, 10).map_err(|_| ParseEquationError::InvalidNumber)?;
+
+        if self.negative {
+            Ok(T::sub_mod(T::zero(), abs, modu))
+        } else {
+            Ok(abs)
+        }
+    }
+
+    fn to_signed<S: Int>(&self) -> Result<S, ParseEquationError> {
+        let value = if self.negative {
+            S::from_str_radix(&format!("-{}", self.digits), 10)
+        } else {
+            S::from_str_radix(&self.digits, 10)
+        };
+
+        value.map_err(|_| ParseEquationError::InvalidNumber)
+    }
+}
+
+impl ParsedEquation {
+    fn modu<T: UInt>(&self) -> Result<T, ParseEquationError> {
+        match T::from_str_radix(&self.modu, 10) {
+            Ok(modu) if modu == T::zero() => Err(ParseEquationError::InvalidModulus),
+            Ok(modu) => Ok(modu),
+            Err(_) => Err(ParseEquationError::InvalidNumber),
+        }
+    }
+}
+
+/// Parse equation of the given `degree` from string `s`, the degree being the highest power
+/// of the unknown in `s` if it's None.
+fn parse_equation(s: &str, degree: Option<usize>) -> Result<ParsedEquation, ParseEquationError> {
+    let s: String = s.chars().filter(|c| !c.is_whitespace()).collect();
+
+    let (lhs, rest) = s
+        .split_once('≡')
+        .or_else(|| s.split_once('='))
+        .ok_or(ParseEquationError::InvalidSyntax)?;
+
+    let (rhs, modu) = rest
+        .strip_suffix(')')
+        .and_then(|rest| rest.split_once("(mod"))
+        .ok_or(ParseEquationError::InvalidSyntax)?;
+
+    if !is_digits(modu) {
+        return Err(ParseEquationError::InvalidSyntax);
+    }
+
+    let terms = parse_terms(lhs)?;
+    let degree = match degree {
+        Some(degree) => degree,
+        None => {
+            let degree = terms.iter().map(|&(_, power)| power).max().unwrap_or(0);
+
+            if degree > MAX_POLY_DEGREE {
+                return Err(ParseEquationError::UnsupportedDegree);
+            }
+            degree
+        }
+    };
+
+    let mut coefs: Vec<Option<Number>> = (0..=degree).map(|_| None).collect();
+
+    for (coef, power) in terms {
+        if power > degree {
+            return Err(ParseEquationError::UnsupportedDegree);
+        }
+        if coefs[power].is_some() {
+            return Err(ParseEquationError::RepeatedTerm);
+        }
+
+        coefs[power] = Some(coef);
+    }
+
+    let mut rhs_terms = parse_terms(rhs)?;
+
+    if rhs_terms.len() != 1 || rhs_terms[0].1 > 0 {
+        return Err(ParseEquationError::InvalidSyntax);
+    }
+    let (rhs, _) = rhs_terms.remove(0);
+
+    Ok(ParsedEquation {
+        coefs: coefs
+            .into_iter()
+            .map(|coef| coef.unwrap_or_else(Number::zero))
+            .collect(),
+        rhs,
+        modu: modu.to_string(),
+    })
+}
+
+/// Split `s` to terms (coefficient, power of the unknown), e.g. "-3x^2" to (-3, 2).
+fn parse_terms(s: &str) -> Result<Vec<(Number, usize)>, ParseEquationError> {
+    if s.is_empty() {
+        return Err(ParseEquationError::InvalidSyntax);
+    }
+
+    let mut terms = vec![];
+    let mut rest = s;
+
+    while !rest.is_empty() {
+        let negative = rest.starts_with('-');
+
+        if negative || (rest.starts_with('+') && !terms.is_empty()) {
+            rest = &rest[1..];
+        }
+
+        let end = rest.find(['+', '-']).unwrap_or(rest.len());
+        terms.push(parse_term(&rest[..end], negative)?);
+        rest = &rest[end..];
+    }
+
+    Ok(terms)
+}
+
+fn parse_term(term: &str, negative: bool) -> Result<(Number, usize), ParseEquationError> {
+    let (digits, power) = match term.split_once('x') {
+        None => (term, 0),
+        Some((coef, "")) => (coef, 1),
+        Some((coef, power)) => match power.strip_prefix('^') {
+            Some(power) if is_digits(power) => (
+                coef,
+                power
+                    .parse()
+                    .map_err(|_| ParseEquationError::UnsupportedDegree)?,
+            ),
+            _ => return Err(ParseEquationError::InvalidSyntax),
+        },
+    };
+
+    let digits = if power > 0 {
+        digits.strip_suffix('*').unwrap_or(digits)
+    } else {
+        digits
+    };
+
+    let digits = if digits.is_empty() && power > 0 {
+        "1"
+    } else if is_digits(digits) {
+        digits
+    } else {
+        return Err(ParseEquationError::InvalidSyntax);
+    };
+
+    Ok((
+        Number {
+            negative,
+            digits: digits.to_string(),
+        },
+        power,
+    ))
+}
+
+fn is_digits(s: &str) -> bool {
+    !s.is_empty() && s.chars().all(|c| c.is_ascii_digit())
+}
+
+/// Write terms `coefs`, given in descending order of the powers of the unknown.
+fn fmt_equation(f: &mut fmt::Formatter, coefs: &[String], rhs: &str, modu: &str) -> fmt::Result {
+    let degree = coefs.len().saturating_sub(1);
+    let mut first = true;
+
+    for (j, coef) in coefs.iter().enumerate() {
+        let power = degree - j;
+        let (negative, abs) = match coef.strip_prefix('-') {
+            Some(abs) => (true, abs),
+            None => (false, coef.as_str()),
+        };
+
+        if abs == "0" {
+            continue;
+        }
+
+        match (first, negative) {
+            (true, true) => write!(f, "-")?,
+            (true, false) => {}
+            (false, true) => write!(f, " - ")?,
+            (false, false) => write!(f, " + ")?,
+        }
+        first = false;
+
+        if abs != "1" || power == 0 {
+            write!(f, "{}", abs)?;
+        }
+
+        match power {
+            0 => {}
+            1 => write!(f, "x")?,
+            _ => write!(f, "x^{}", power)?,
+        }
+    }
+
+    if first {
+        write!(f, "0")?;
+    }
+
+    write!(f, " ≡ {} (mod {})", rhs, modu)
+}
+
+impl<T: UInt> fmt::Display for LinEq<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let coefs = [self.a.to_string(), self.b.to_string()];
+        fmt_equation(f, &coefs, &self.c.to_string(), &self.modu.to_string())
+    }
+}
+
+impl<S: Int, T: UInt> fmt::Display for LinEqSigned<S, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let coefs = [self.a.to_string(), self.b.to_string()];
+        fmt_equation(f, &coefs, &self.c.to_string(), &self.modu.to_string())
+    }
+}
+
+impl<T: UInt> fmt::Display for QuadEq<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let coefs = [self.a.to_string(), self.b.to_string(), self.c.to_string()];
+        fmt_equation(f, &coefs, &self.d.to_string(), &self.modu.to_string())
+    }
+}
+
+impl<S: Int, T: UInt> fmt::Display for QuadEqSigned<S, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let coefs = [self.a.to_string(), self.b.to_string(), self.c.to_string()];
+        fmt_equation(f, &coefs, &self.d.to_string(), &self.modu.to_string())
+    }
+}
+
+impl<T: UInt> FromStr for LinEq<T> {
+    type Err = ParseEquationError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let eq = parse_equation(s, Some(1))?;
+        let modu = eq.modu()?;
+
+        Ok(LinEq {
+            a: eq.coefs[1].to_unsigned(modu)?,
+            b: eq.coefs[0].to_unsigned(modu)?,
+            c: eq.rhs.to_unsigned(modu)?,
+            modu,
+        })
+    }
+}
+
+impl<S: Int, T: UInt> FromStr for LinEqSigned<S, T> {
+    type Err = ParseEquationError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let eq = parse_equation(s, Some(1))?;
+
+        Ok(LinEqSigned {
+            a: eq.coefs[1].to_signed()?,
+            b: eq.coefs[0].to_signed()?,
+            c: eq.rhs.to_signed()?,
+            modu: eq.modu()?,
+        })
+    }
+}
+
+impl<T: UInt> FromStr for QuadEq<T> {
+    type Err = ParseEquationError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let eq = parse_equation(s, Some(2))?;
+        let modu = eq.modu()?;
+
+        Ok(QuadEq {
+            a: eq.coefs[2].to_unsigned(modu)?,
+            b: eq.coefs[1].to_unsigned(modu)?,
+            c: eq.coefs[0].to_unsigned(modu)?,
+            d: eq.rhs.to_unsigned(modu)?,
+            modu,
+        })
+    }
+}
+
+impl<S: Int, T: UInt> FromStr for QuadEqSigned<S, T> {
+    type Err = ParseEquationError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let eq = parse_equation(s, Some(2))?;
+
+        Ok(QuadEqSigned {
+            a: eq.coefs[2].to_signed()?,
+            b: eq.coefs[1].to_signed()?,
+            c: eq.coefs[0].to_signed()?,
+            d: eq.rhs.to_signed()?,
+            modu: eq.modu()?,
+        })
+    }
+}
+
+impl<T: UInt> fmt::Display for PolyEq<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let coefs: Vec<String> = self.coefs.iter().rev().map(|c| c.to_string()).collect();
+        fmt_equation(f, &coefs, &self.rhs.to_string(), &self.modu.to_string())
+    }
+}
+
+impl<S: Int, T: UInt> fmt::Display for PolyEqSigned<S, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let coefs: Vec<String> = self.coefs.iter().rev().map(|c| c.to_string()).collect();
+        fmt_equation(f, &coefs, &self.rhs.to_string(), &self.modu.to_string())
+    }
+}
+
+impl<T: UInt> FromStr for PolyEq<T> {
+    type Err = ParseEquationError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let eq = parse_equation(s, None)?;
+        let modu = eq.modu()?;
+
+        Ok(PolyEq {
+            coefs: eq
+                .coefs
+                .iter()
+                .map(|coef| coef.to_unsigned(modu))
+                .collect::<Result<Vec<T>, _>>()?,
+            rhs: eq.rhs.to_unsigned(modu)?,
+            modu,
+        })
+    }
+}
+
+impl<S: Int, T: UInt> FromStr for PolyEqSigned<S, T> {
+    type Err = ParseEquationError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let eq = parse_equation(s, None)?;
+
+        Ok(PolyEqSigned {
+            coefs: eq
+                .coefs
+                .iter()
+                .map(|coef| coef.to_signed())
+                .collect::<Result<Vec<S>, _>>()?,
+            rhs: eq.rhs.to_signed()?,
+            modu: eq.modu()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,170 @@
+use crate::notation::ParseEquationError;
+use crate::{LinEq, LinEqSigned, PolyEq, PolyEqSigned, QuadEq, QuadEqSigned};
+
+#[test]
+fn display_lin_eq() {
+    let test_cases = [
+        (
+            LinEq::<u32> {
+                a: 3,
+                b: 3,
+                c: 1,
+                modu: 1223,
+            },
+            "3x + 3 ≡ 1 (mod 1223)",
+        ),
+        (
+            LinEq {
+                a: 1,
+                b: 0,
+                c: 5,
+                modu: 7,
+            },
+            "x ≡ 5 (mod 7)",
+        ),
+        (
+            LinEq {
+                a: 0,
+                b: 0,
+                c: 0,
+                modu: 2,
+            },
+            "0 ≡ 0 (mod 2)",
+        ),
+    ];
+
+    for (lin_eq, corr_str) in test_cases.iter() {
+        assert_eq!(lin_eq.to_string(), *corr_str);
+    }
+
+    let lin_eq = LinEqSigned::<i8, u8> {
+        a: -1,
+        b: -128,
+        c: -3,
+        modu: 9,
+    };
+    assert_eq!(lin_eq.to_string(), "-x - 128 ≡ -3 (mod 9)");
+}
+
+#[test]
+fn display_quad_eq() {
+    let quad_eq = QuadEq::<u64> {
+        a: 3,
+        b: 2,
+        c: 1,
+        d: 5,
+        modu: 17,
+    };
+    assert_eq!(quad_eq.to_string(), "3x^2 + 2x + 1 ≡ 5 (mod 17)");
+
+    let quad_eq = QuadEqSigned::<i64, u64> {
+        a: -1,
+        b: 0,
+        c: -7,
+        d: 0,
+        modu: 22,
+    };
+    assert_eq!(quad_eq.to_string(), "-x^2 - 7 ≡ 0 (mod 22)");
+}
+
+#[test]
+fn parse_round_trip() {
+    let strs = [
+        "3x^2 + 2x + 1 ≡ 5 (mod 17)",
+        "-x^2 - 7 ≡ -1 (mod 22)",
+        "x ≡ 0 (mod 340282366920938463463374607431768211455)",
+        "0 ≡ 0 (mod 1)",
+    ];
+
+    for s in strs.iter() {
+        match s.parse::<QuadEqSigned<i128, u128>>() {
+            Ok(quad_eq) => assert_eq!(quad_eq.to_string(), *s),
+            Err(err) => panic!("{}: {}", s, err),
+        }
+    }
+
+    let lin_strs = ["17x + 3 ≡ 4 (mod 255)", "x - 2 ≡ 5 (mod 8)"];
+
+    for s in lin_strs.iter() {
+        match s.parse::<LinEqSigned<i16, u16>>() {
+            Ok(lin_eq) => assert_eq!(lin_eq.to_string(), *s),
+            Err(err) => panic!("{}: {}", s, err),
+        }
+    }
+}
+
+#[test]
+fn parse_free_form() {
+    match "-x+4+2*x^2=0(mod 9)".parse::<QuadEq<u32>>() {
+        Ok(quad_eq) => {
+            assert_eq!(
+                (quad_eq.a, quad_eq.b, quad_eq.c, quad_eq.d, quad_eq.modu),
+                (2, 8, 4, 0, 9)
+            );
+        }
+        Err(err) => panic!("{}", err),
+    }
+
+    match " 5 + 13x ≡ -5 (mod 29) ".parse::<LinEq<u8>>() {
+        Ok(lin_eq) => {
+            assert_eq!((lin_eq.a, lin_eq.b, lin_eq.c, lin_eq.modu), (13, 5, 24, 29));
+        }
+        Err(err) => panic!("{}", err),
+    }
+}
+
+#[test]
+fn parse_errors() {
+    let test_cases = [
+        ("3x + 1 (mod 7)", ParseEquationError::InvalidSyntax),
+        ("3x + 1 ≡ 2 mod 7", ParseEquationError::InvalidSyntax),
+        ("3y + 1 ≡ 2 (mod 7)", ParseEquationError::InvalidSyntax),
+        ("3x + + 1 ≡ 2 (mod 7)", ParseEquationError::InvalidSyntax),
+        ("3x + 1 ≡ 2x (mod 7)", ParseEquationError::InvalidSyntax),
+        ("3x + 1 ≡ 2 (mod -7)", ParseEquationError::InvalidSyntax),
+        ("3x + x ≡ 2 (mod 7)", ParseEquationError::RepeatedTerm),
+        ("x^3 ≡ 2 (mod 7)", ParseEquationError::UnsupportedDegree),
+        ("x ≡ 2 (mod 0)", ParseEquationError::InvalidModulus),
+        ("x ≡ 2 (mod 256)", ParseEquationError::InvalidNumber),
+        ("300x ≡ 2 (mod 7)", ParseEquationError::InvalidNumber),
+    ];
+
+    for (s, corr_err) in test_cases.iter() {
+        assert_eq!(s.parse::<QuadEq<u8>>().err(), Some(*corr_err), "{}", s);
+    }
+
+    assert_eq!(
+        "x^2 ≡ 2 (mod 7)".parse::<LinEq<u8>>().err(),
+        Some(ParseEquationError::UnsupportedDegree)
+    );
+    assert_eq!(
+        "-129x ≡ 2 (mod 7)".parse::<LinEqSigned<i8, u8>>().err(),
+        Some(ParseEquationError::InvalidNumber)
+    );
+}
+
+#[test]
+fn parse_poly_eq() {
+    match "x^3 + 1 = 0 (mod 9)".parse::<PolyEq<u32>>() {
+        Ok(poly_eq) => {
+            assert_eq!(poly_eq.coefs, vec![1, 0, 0, 1]);
+            assert_eq!((poly_eq.rhs, poly_eq.modu), (0, 9));
+            assert_eq!(poly_eq.to_string(), "x^3 + 1 ≡ 0 (mod 9)");
+        }
+        Err(err) => panic!("{}", err),
+    }
+
+    match "-x + x^5 ≡ -2 (mod 25)".parse::<PolyEqSigned<i32, u32>>() {
+        Ok(poly_eq) => {
+            assert_eq!(poly_eq.coefs, vec![0, -1, 0, 0, 0, 1]);
+            assert_eq!(poly_eq.rhs, -2);
+            assert_eq!(poly_eq.to_string(), "x^5 - x ≡ -2 (mod 25)");
+        }
+        Err(err) => panic!("{}", err),
+    }
+
+    assert_eq!(
+        "x^256 ≡ 2 (mod 7)".parse::<PolyEq<u8>>().err(),
+        Some(ParseEquationError::UnsupportedDegree)
+    );
+}
@@ -0,0 +1,300 @@
+//! Implements number-theoretic functions.
+//!
+//! Functions of this module are computed from the prime factor representation
+//! n = p_1^k_1 * ... * p_m^k_m of their argument n, thus calling them requires
+//! factorization of the argument unless it's small enough to be trivial.
+//!
+//! Tower exponentiation a^(b^(c^...)) (mod n) relies on the generalized Euler's
+//! theorem: if x >= k_max, k_max being the largest exponent k_i of n, then
+//! a^x = a^y (mod n) for every y >= k_max with x = y (mod λ(n)), λ being
+//! Carmichael's function. This holds even if a and n are not coprime and thus
+//! the exponent tower can be reduced recursively along the chain n, λ(n), λ(λ(n)), ...
+//!
+use crate::{arith::Arith, factor::Factors, prime, UInt};
+
+/// Exponents of at least this size are reduced by the generalized Euler's theorem.
+/// Every exponent k_i in the prime factor representation of a u128 is smaller.
+const TOWER_EXP_CAP: u8 = 128;
+
+/// Euler's totient function φ(n) for a positive natural number `num`.
+///
+/// Value of the totient equals the count of integers in range \[1, n\]
+/// being coprime to n, or in other words the order of the unit group
+/// (Z/nZ)*. It's computed as φ(n) = p_1^(k_1-1)(p_1-1) * ... * p_m^(k_m-1)(p_m-1),
+/// the prime factor representation being found by `Factors`.
+///
+/// For zero argument the return value is also zero.
+///
+/// # Examples
+///
+/// ```
+/// use modular_equations::totient;
+///
+/// // 360 = 2^3 * 3^2 * 5
+/// assert_eq!(totient::<u32>(360), 96);
+/// assert_eq!(totient::<u64>(1_000_000_007), 1_000_000_006);
+/// ```
+pub fn totient<T: 'static + UInt>(num: T) -> T {
+    if num <= T::one() {
+        return num;
+    }
+
+    prime_factor_repr(num)
+        .iter()
+        .fold(T::one(), |phi, &(prm, k)| {
+            phi * prm.pow((k - 1).into()) * (prm - T::one())
+        })
+}
+
+/// Carmichael's function λ(n) for a positive natural number `num`.
+///
+/// Value λ(n) is the exponent of the unit group (Z/nZ)*, i.e. the smallest positive
+/// integer m s.t. a^m = 1 (mod n) for every a coprime to n. It's the lcm of the
+/// values λ(p_i^k_i), where λ(p^k) = φ(p^k) for odd primes and λ(2^k) = 2^(k-2)
+/// for k >= 3. Thus λ(n) divides φ(n) and exponents of units can be reduced modulo λ(n).
+///
+/// For zero argument the return value is also zero.
+///
+/// # Examples
+///
+/// ```
+/// use modular_equations::{carmichael, totient};
+///
+/// // 360 = 2^3 * 3^2 * 5, λ(360) = lcm(2, 6, 4)
+/// assert_eq!(carmichael::<u32>(360), 12);
+/// assert_eq!(carmichael::<u32>(1 << 20), 1 << 18);
+/// assert_eq!(totient::<u32>(360) % carmichael::<u32>(360), 0);
+/// ```
+pub fn carmichael<T: 'static + UInt>(num: T) -> T {
+    if num <= T::one() {
+        return num;
+    }
+
+    prime_factor_repr(num)
+        .iter()
+        .fold(T::one(), |lambda, &(prm, k)| {
+            let lambda_prm_power = if prm == 2.into() && k >= 3 {
+                prm.pow((k - 2).into())
+            } else {
+                prm.pow((k - 1).into()) * (prm - T::one())
+            };
+
+            lambda / T::gcd_mod(lambda, lambda_prm_power) * lambda_prm_power
+        })
+}
+
+/// Möbius function μ(n) for a positive natural number `num`.
+///
+/// Value is zero if n has a squared prime factor and otherwise (-1)^m, m being the
+/// number of distinct prime factors of n. Hence μ(1) = 1.
+///
+/// For zero argument the return value is also zero.
+///
+/// # Examples
+///
+/// ```
+/// use modular_equations::moebius;
+///
+/// assert_eq!(moebius::<u32>(30), -1);
+/// assert_eq!(moebius::<u32>(18), 0);
+/// assert_eq!(moebius::<u64>(1_000_000_007 * 3), 1);
+/// ```
+pub fn moebius<T: 'static + UInt>(num: T) -> i8 {
+    if num <= T::one() {
+        return if num == T::one() { 1 } else { 0 };
+    }
+
+    let prm_factor_repr = prime_factor_repr(num);
+
+    if prm_factor_repr.iter().any(|&(_, k)| k > 1) {
+        0
+    } else if prm_factor_repr.len() & 1 == 1 {
+        -1
+    } else {
+        1
+    }
+}
+
+/// Check whether a natural number `num` is squarefree.
+///
+/// Number is squarefree if no prime square divides it, or equivalently if
+/// μ(n) is nonzero. One is squarefree whereas zero isn't.
+///
+/// # Examples
+///
+/// ```
+/// use modular_equations::is_squarefree;
+///
+/// assert!(is_squarefree::<u32>(30));
+/// assert!(!is_squarefree::<u32>(18));
+/// ```
+pub fn is_squarefree<T: 'static + UInt>(num: T) -> bool {
+    moebius(num) != 0
+}
+
+/// Check whether a natural number `num` is a Blum integer.
+///
+/// Blum integer is a product n = pq of two distinct primes p and q with p = q = 3 (mod 4).
+/// Modulo such n, -1 is a quadratic nonresidue having Jacobi symbol one and every quadratic
+/// residue coprime to n has exactly one square root that is itself a quadratic residue.
+///
+/// # Examples
+///
+/// ```
+/// use modular_equations::is_blum_integer;
+///
+/// assert!(is_blum_integer::<u32>(21));
+/// assert!(!is_blum_integer::<u32>(15));
+/// assert!(!is_blum_integer::<u32>(9));
+/// ```
+pub fn is_blum_integer<T: 'static + UInt>(num: T) -> bool {
+    if num <= T::one() {
+        return false;
+    }
+
+    match prime_factor_repr(num)[..] {
+        [(p, 1), (q, 1)] => p & 3.into() == 3.into() && q & 3.into() == 3.into(),
+        _ => false,
+    }
+}
+
+/// Tower exponentiation `a`^(b_1^(b_2^(...))) modulo `modu`.
+///
+/// Exponent tower \[b_1, b_2, ...\] is evaluated from the top, thus \[b, c\]
+/// corresponds to a^(b^c). Empty tower corresponds to exponent one and 0^0 is
+/// interpreted as one. Base and the modulo don't need to be coprime. Modulo must
+/// be strictly larger than one, otherwise None is returned.
+///
+/// # Examples
+///
+/// ```
+/// use modular_equations::tower_exp_mod;
+///
+/// // 2^(3^2) = 2^9 = 512 = 12 (mod 100)
+/// assert_eq!(tower_exp_mod::<u32>(2, &[3, 2], 100), Some(12));
+/// // 2^(2^(2^2)) = 2^16 = 65536
+/// assert_eq!(tower_exp_mod::<u64>(2, &[2, 2, 2], 100_000), Some(65_536));
+/// ```
+pub fn tower_exp_mod<T: 'static + UInt>(a: T, exponent_tower: &[T], modu: T) -> Option<T> {
+    if modu <= T::one() {
+        return None;
+    }
+
+    let mut tower = Vec::with_capacity(exponent_tower.len() + 1);
+    tower.push(a);
+    tower.extend_from_slice(exponent_tower);
+
+    Some(tower_value_mod(&tower, modu))
+}
+
+/// Value of the tower \[a, b, ...\] = a^(b^(...)) modulo `modu` > 0.
+fn tower_value_mod<T: 'static + UInt>(tower: &[T], modu: T) -> T {
+    if modu == T::one() {
+        return T::zero();
+    }
+
+    let a = tower[0];
+
+    if tower.len() == 1 {
+        return a % modu;
+    }
+
+    let cap: T = TOWER_EXP_CAP.into();
+    let exponent_capped = tower_value_capped(&tower[1..], cap);
+
+    if exponent_capped < cap {
+        return T::exp_mod(a, exponent_capped, modu);
+    }
+
+    // Exponent x >= cap, use y = (x mod λ) + q * λ where q * λ >= cap
+    let lambda = carmichael(modu);
+    let exponent_reduced = tower_value_mod(&tower[1..], lambda);
+    let q = if lambda >= cap {
+        T::one()
+    } else {
+        (cap + lambda - T::one()) / lambda
+    };
+
+    T::mult_mod(
+        T::exp_mod(a, exponent_reduced, modu),
+        T::exp_mod(T::exp_mod(a, lambda, modu), q, modu),
+        modu,
+    )
+}
+
+/// Value of the tower \[a, b, ...\] or `cap` if the value is at least `cap`.
+fn tower_value_capped<T: UInt>(tower: &[T], cap: T) -> T {
+    let a = tower[0];
+
+    if tower.len() == 1 {
+        return a.min(cap);
+    }
+
+    if a <= T::one() {
+        // 0^x is one only for x = 0 and 1^x is always one
+        let exponent_is_zero = tower_value_capped(&tower[1..], cap) == T::zero();
+
+        return if a == T::zero() && !exponent_is_zero {
+            T::zero()
+        } else {
+            T::one()
+        };
+    }
+
+    let exponent = tower_value_capped(&tower[1..], cap);
+
+    let mut value = T::one();
+    let mut count = T::zero();
+
+    while count < exponent {
+        match value.checked_mul(&a) {
+            Some(prod) if prod < cap => value = prod,
+            _ => return cap,
+        }
+        count = count + T::one();
+    }
+
+    value
+}
+
+/// Exponent of the prime `prm` in the factorial `num`! by Legendre's formula.
+///
+/// Valuation v_p(n!) is the sum of floor(n / p^i) over i >= 1, computed here by
+/// repeated division to avoid overflowing powers of the prime. If `prm` is not
+/// a prime, None is returned.
+///
+/// # Examples
+///
+/// ```
+/// use modular_equations::factorial_valuation;
+///
+/// // 10! = 2^8 * 3^4 * 5^2 * 7
+/// assert_eq!(factorial_valuation::<u32>(10, 2), Some(8));
+/// assert_eq!(factorial_valuation::<u32>(10, 5), Some(2));
+/// ```
+pub fn factorial_valuation<T: UInt>(num: T, prm: T) -> Option<T> {
+    if prm != 2.into() && !prime::is_odd_prime(prm) {
+        return None;
+    }
+
+    let mut valuation = T::zero();
+    let mut quotient = num;
+
+    while quotient >= prm {
+        quotient = quotient / prm;
+        valuation = valuation + quotient;
+    }
+
+    Some(valuation)
+}
+
+/// Prime factor representation \[(p_1,k_1), ..., (p_m,k_m)\] of `num` > 1.
+pub fn prime_factor_repr<T: 'static + UInt>(num: T) -> Vec<(T, u8)> {
+    let mut factors = Factors::new(num);
+    factors.factorize();
+
+    factors.prime_factor_repr()
+}
+
+#[cfg(test)]
+mod tests;
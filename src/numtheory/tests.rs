@@ -0,0 +1,278 @@
+use crate::arith::Arith;
+use crate::numtheory::{
+    carmichael, factorial_valuation, is_blum_integer, is_squarefree, moebius, totient,
+    tower_exp_mod,
+};
+
+#[test]
+fn totient_small_type() {
+    // [n, phi(n)]
+    let test_cases: [[u8; 2]; 10] = [
+        [0, 0],
+        [1, 1],
+        [2, 1],
+        [3, 2],
+        [8, 4],
+        [12, 4],
+        [36, 12],
+        [97, 96],
+        [128, 64],
+        [u8::MAX, 128],
+    ];
+
+    for test in test_cases.iter() {
+        assert_eq!(totient(test[0]), test[1], "n: {}", test[0]);
+    }
+}
+
+#[test]
+fn totient_brute_force_small_numbers() {
+    for n in 1..1000u32 {
+        let phi = (1..=n).filter(|&x| u32::gcd_mod(x, n) == 1).count() as u32;
+
+        assert_eq!(totient(n), phi, "n: {}", n);
+    }
+}
+
+#[test]
+fn totient_large_type() {
+    // [n, phi(n)]
+    let test_cases: [[u128; 2]; 5] = [
+        [1_000_000_007, 1_000_000_006],
+        [2_u128.pow(100), 2_u128.pow(99)],
+        [u64::MAX as u128, 9_208_981_628_670_443_520],
+        [u64::MAX as u128 + 2, 18_446_676_793_287_966_720],
+        [
+            u128::MAX,
+            169_875_107_699_410_294_159_549_716_941_399_654_400,
+        ],
+    ];
+
+    for test in test_cases.iter() {
+        assert_eq!(totient(test[0]), test[1], "n: {}", test[0]);
+    }
+}
+
+#[test]
+fn factorial_valuation_small_type() {
+    // [n, p, v_p(n!)]
+    let test_cases: [[u16; 3]; 8] = [
+        [0, 2, 0],
+        [1, 3, 0],
+        [4, 2, 3],
+        [10, 3, 4],
+        [25, 5, 6],
+        [100, 2, 97],
+        [100, 97, 1],
+        [u16::MAX, 2, 65_519],
+    ];
+
+    for test in test_cases.iter() {
+        assert_eq!(
+            factorial_valuation(test[0], test[1]),
+            Some(test[2]),
+            "n: {}, p: {}",
+            test[0],
+            test[1]
+        );
+    }
+}
+
+#[test]
+fn factorial_valuation_brute_force_small_numbers() {
+    for prm in [2u32, 3, 5, 7, 31].iter() {
+        let mut valuation = 0;
+
+        for n in 1..2_000u32 {
+            let mut m = n;
+            while m % prm == 0 {
+                m /= prm;
+                valuation += 1;
+            }
+
+            assert_eq!(factorial_valuation(n, *prm), Some(valuation), "n: {}", n);
+        }
+    }
+}
+
+#[test]
+fn factorial_valuation_large_type() {
+    let n = u128::MAX;
+
+    // v_2(n!) = n - s_2(n), s_2 being the binary digit sum
+    assert_eq!(factorial_valuation(n, 2), Some(n - 128));
+    assert_eq!(
+        factorial_valuation(n, 18_446_744_073_709_551_557),
+        Some(18_446_744_073_709_551_676)
+    );
+}
+
+#[test]
+fn factorial_valuation_non_prime() {
+    assert_eq!(factorial_valuation(100u32, 1), None);
+    assert_eq!(factorial_valuation(100u32, 0), None);
+    assert_eq!(factorial_valuation(100u32, 10), None);
+}
+
+#[test]
+fn carmichael_small_type() {
+    // [n, λ(n)]
+    let test_cases: [[u16; 2]; 10] = [
+        [0, 0],
+        [1, 1],
+        [2, 1],
+        [4, 2],
+        [8, 2],
+        [15, 4],
+        [16, 4],
+        [561, 80],
+        [1_024, 256],
+        [u16::MAX, 256],
+    ];
+
+    for test in test_cases.iter() {
+        assert_eq!(carmichael(test[0]), test[1], "n: {}", test[0]);
+    }
+}
+
+#[test]
+fn carmichael_brute_force_small_numbers() {
+    for n in 2..500u32 {
+        let units: Vec<u32> = (1..n).filter(|&x| u32::gcd_mod(x, n) == 1).collect();
+
+        let lambda = (1..=n)
+            .find(|&m| units.iter().all(|&x| u32::exp_mod(x, m, n) == 1))
+            .unwrap();
+
+        assert_eq!(carmichael(n), lambda, "n: {}", n);
+    }
+}
+
+#[test]
+fn tower_exp_mod_small_towers() {
+    // 2^(3^2) = 512, 3^(2^3) = 6561, 2^(2^(2^2)) = 65536
+    let test_cases: [(u32, &[u32], u32, u32); 10] = [
+        (7, &[], 5, 2),
+        (2, &[3, 2], 1_000, 512),
+        (3, &[2, 3], 10_000, 6_561),
+        (2, &[2, 2, 2], 100_000, 65_536),
+        (0, &[0], 7, 1),
+        (0, &[0, 5], 7, 1),
+        (0, &[5, 0], 7, 0),
+        (5, &[0, 0], 7, 5),
+        (1, &[u32::MAX, u32::MAX], 97, 1),
+        (6, &[1, 2, 3], 36, 6),
+    ];
+
+    for (a, tower, modu, corr) in test_cases.iter() {
+        assert_eq!(
+            tower_exp_mod(*a, tower, *modu),
+            Some(*corr),
+            "a: {}, tower: {:?}, modu: {}",
+            a,
+            tower,
+            modu
+        );
+    }
+}
+
+#[test]
+fn tower_exp_mod_non_coprime_base() {
+    // 2^(3^4) = 2^81 and 6^(5^3) = 6^125 computed directly with modular exponentiation
+    for modu in 2..3_000u64 {
+        assert_eq!(
+            tower_exp_mod(2, &[3, 4], modu),
+            Some(u64::exp_mod(2, 81, modu)),
+            "modu: {}",
+            modu
+        );
+        assert_eq!(
+            tower_exp_mod(6, &[5, 3], modu),
+            Some(u64::exp_mod(6, 125, modu)),
+            "modu: {}",
+            modu
+        );
+    }
+}
+
+#[test]
+fn tower_exp_mod_large_towers() {
+    // 3^(3^(3^3)) = 3^(3^27) ends with digits 6_100_739_387
+    assert_eq!(
+        tower_exp_mod(3u64, &[3, 3, 3], 10_000_000_000),
+        Some(6_100_739_387)
+    );
+
+    // 2^(2^(2^(2^2))) = 2^65536, checked against direct exponentiation
+    let modu: u128 = u128::MAX - 158;
+    assert_eq!(
+        tower_exp_mod(2, &[2, 2, 2, 2], modu),
+        Some(u128::exp_mod(2, 65_536, modu))
+    );
+
+    // 10^E = 1 (mod 3), 0 (mod 5) and 1 (mod 17) as 16 divides E = 10^(10^10)
+    assert_eq!(tower_exp_mod(10u8, &[10, 10, 10], u8::MAX), Some(205));
+}
+
+#[test]
+fn tower_exp_mod_invalid_modulo() {
+    assert_eq!(tower_exp_mod(2u32, &[3], 0), None);
+    assert_eq!(tower_exp_mod(2u32, &[3], 1), None);
+}
+
+#[test]
+fn moebius_brute_force_small_numbers() {
+    for n in 1..1000u32 {
+        let mut m = n;
+        let mut mu = 1;
+
+        for prm in 2..=n {
+            if m % prm == 0 {
+                m /= prm;
+                mu = if m % prm == 0 { 0 } else { -mu };
+            }
+        }
+
+        assert_eq!(moebius(n), mu, "n: {}", n);
+        assert_eq!(is_squarefree(n), mu != 0, "n: {}", n);
+    }
+
+    assert_eq!(moebius(0u32), 0);
+    assert!(!is_squarefree(0u32));
+}
+
+#[test]
+fn moebius_large_type() {
+    // [n, μ(n)], u128::MAX = 3 * 5 * 17 * 257 * 641 * 65537 * 274177 * 6700417 * 67280421310721
+    let test_cases: [(u128, i8); 5] = [
+        (u128::MAX, -1),
+        (u128::MAX - 158, -1),
+        (u64::MAX as u128 * 3, 0),
+        (1 << 100, 0),
+        (1_000_000_007 * 1_000_000_009, 1),
+    ];
+
+    for (n, mu) in test_cases.iter() {
+        assert_eq!(moebius(*n), *mu, "n: {}", n);
+    }
+}
+
+#[test]
+fn blum_integers_small_numbers() {
+    let corr_blum: [u32; 10] = [21, 33, 57, 69, 77, 93, 129, 133, 141, 161];
+
+    let blum: Vec<u32> = (0..162).filter(|&n| is_blum_integer(n)).collect();
+
+    assert_eq!(blum, corr_blum.to_vec());
+}
+
+#[test]
+fn blum_integers_large_type() {
+    // 2^61 - 1 and 1_000_000_007 are primes congruent to 3 (mod 4)
+    let m61 = (1u128 << 61) - 1;
+
+    assert!(is_blum_integer(m61 * 1_000_000_007));
+    assert!(!is_blum_integer(m61 * 1_000_000_009));
+    assert!(!is_blum_integer(m61 * m61));
+    assert!(!is_blum_integer(m61 * 1_000_000_007 * 3));
+}
@@ -0,0 +1,135 @@
+//! Implements p-adic lifting of inverses and square roots.
+//!
+//! Given a solution of f(x) = 0 (mod p) with f'(x) invertible modulo p, Hensel's
+//! lemma guarantees a unique solution modulo every power p^k lying above it. Newton's
+//! iteration x' = x - f(x) / f'(x) computes this solution and, if x is correct modulo
+//! p^e, then x' is correct modulo p^2e. Thus only about log2(k) iteration steps are
+//! needed instead of k - 1 steps of the linear Hensel lifting.
+//!
+//! Inverses are lifted with f(x) = ax - 1, for which the Newton step simplifies to
+//! x' = x(2 - ax), and square roots with f(x) = x^2 - a.
+//!
+use std::cmp;
+
+use num::checked_pow;
+
+use crate::{
+    arith::{Arith, CoreArith},
+    prime,
+    quad::QuadEq,
+    UInt,
+};
+
+/// Multiplicative inverse of `a` modulo `prm`^`k`, `prm` being a prime.
+///
+/// Inverse is first computed modulo the prime and then lifted to the power
+/// by Newton's iteration. None is returned if `prm` is not a prime, `a` is
+/// divisible by it, k is zero or `prm`^`k` doesn't fit into the type T.
+///
+/// # Examples
+///
+/// ```
+/// use modular_equations::lift_inverse_mod_pk;
+///
+/// // 3 * 33 = 99 = 1 (mod 7^2)
+/// assert_eq!(lift_inverse_mod_pk::<u32>(3, 7, 2), Some(33));
+/// assert_eq!(lift_inverse_mod_pk::<u32>(14, 7, 2), None);
+/// ```
+pub fn lift_inverse_mod_pk<T: UInt>(a: T, prm: T, k: u8) -> Option<T> {
+    if !is_prime(prm) || k == 0 || a % prm == T::zero() {
+        return None;
+    }
+
+    checked_pow(prm, k.into())?;
+
+    let mut x = T::multip_inv(a % prm, prm);
+    let mut e = 1;
+
+    while e < k {
+        e = cmp::min(2 * e, k);
+        let modu = prm.pow(e.into());
+
+        // x' = x(2 - ax) (mod p^e)
+        let ax = T::mult_mod(a, x, modu);
+        x = T::mult_mod(x, T::sub_mod(2.into(), ax, modu), modu);
+    }
+
+    Some(x)
+}
+
+/// Square root of `a` modulo `prm`^`k`, `prm` being an odd prime.
+///
+/// Square root is first computed modulo the prime and then lifted to the power by
+/// Newton's iteration. If x is returned, the other square root is `prm`^`k` - x and
+/// x is the one having the smaller residue modulo `prm`. None is returned if `a` is
+/// not a quadratic residue, `prm` is not an odd prime, `a` is divisible by it, k is
+/// zero or `prm`^`k` doesn't fit into the type T.
+///
+/// # Examples
+///
+/// ```
+/// use modular_equations::lift_sqrt_mod_pk;
+///
+/// // 10^2 = 100 = 2 (mod 7^2)
+/// assert_eq!(lift_sqrt_mod_pk::<u32>(2, 7, 2), Some(10));
+/// // 3 is not a quadratic residue modulo 7
+/// assert_eq!(lift_sqrt_mod_pk::<u32>(3, 7, 2), None);
+/// ```
+pub fn lift_sqrt_mod_pk<T: 'static + UInt>(a: T, prm: T, k: u8) -> Option<T> {
+    if !prime::is_odd_prime(prm) || k == 0 || a % prm == T::zero() {
+        return None;
+    }
+
+    checked_pow(prm, k.into())?;
+
+    let quad = QuadEq {
+        a: T::one(),
+        b: T::zero(),
+        c: T::zero(),
+        d: a % prm,
+        modu: prm,
+    };
+
+    let root = quad.solve()?[0];
+
+    Some(lift_simple_root(T::one(), T::zero(), a, root, prm, k))
+}
+
+/// Lift a simple root `root` of ax^2 + bx = d (mod `prm`) to modulo `prm`^`k`.
+///
+/// Root must be simple, i.e. the derivative 2a * `root` + b must be
+/// invertible modulo the prime, and `prm`^`k` must fit into the type T.
+pub fn lift_simple_root<T: UInt>(a: T, b: T, d: T, root: T, prm: T, k: u8) -> T {
+    let mut x = root;
+    let mut e = 1;
+
+    while e < k {
+        e = cmp::min(2 * e, k);
+        let modu = prm.pow(e.into());
+
+        let ax = T::mult_mod(a, T::mult_mod(x, x, modu), modu);
+        let bx = T::mult_mod(b, x, modu);
+        let poly = T::sub_mod(T::add_mod_unsafe(ax, bx, modu), d, modu);
+
+        let poly_d = T::add_mod(
+            T::mult_mod(2.into(), T::mult_mod(a, x, modu), modu),
+            b,
+            modu,
+        );
+
+        x = T::sub_mod_unsafe(
+            x,
+            T::mult_mod(poly, T::multip_inv(poly_d, modu), modu),
+            modu,
+        );
+    }
+
+    x
+}
+
+fn is_prime<T: UInt>(num: T) -> bool {
+    num == 2.into() || prime::is_odd_prime(num)
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,142 @@
+use crate::arith::Arith;
+use crate::padic::{lift_inverse_mod_pk, lift_simple_root, lift_sqrt_mod_pk};
+
+#[test]
+fn lift_inverse_small_type() {
+    // [a, prm, k, inverse]
+    let test_cases: [[u16; 4]; 6] = [
+        [1, 2, 1, 1],
+        [3, 2, 10, 683],
+        [3, 7, 2, 33],
+        [2, 3, 5, 122],
+        [10, 11, 3, 1_198],
+        [255, 257, 1, 128],
+    ];
+
+    for test in test_cases.iter() {
+        assert_eq!(
+            lift_inverse_mod_pk(test[0], test[1], test[2] as u8),
+            Some(test[3]),
+            "a: {}, prm: {}, k: {}",
+            test[0],
+            test[1],
+            test[2]
+        );
+    }
+}
+
+#[test]
+fn lift_inverse_all_exponents() {
+    let prm = 3u64;
+
+    for k in 1..=40u8 {
+        let modu = prm.pow(k.into());
+
+        for a in [1u64, 2, 5, 1_000_000_007, u64::MAX - 1].iter() {
+            match lift_inverse_mod_pk(*a, prm, k) {
+                Some(inv) => {
+                    assert!(inv < modu);
+                    assert_eq!(u64::mult_mod(*a, inv, modu), 1, "a: {}, k: {}", a, k);
+                }
+                None => panic!("a: {}, k: {}, inverse: None", a, k),
+            }
+        }
+    }
+}
+
+#[test]
+fn lift_inverse_large_type() {
+    let (prm, k) = (2u128, 127);
+    let a = 1_000_000_007;
+
+    match lift_inverse_mod_pk(a, prm, k) {
+        Some(inv) => assert_eq!(u128::mult_mod(a, inv, 1 << 127), 1),
+        None => panic!("inverse: None"),
+    }
+}
+
+#[test]
+fn lift_inverse_invalid_arguments() {
+    assert_eq!(lift_inverse_mod_pk(14u32, 7, 2), None);
+    assert_eq!(lift_inverse_mod_pk(3u32, 9, 2), None);
+    assert_eq!(lift_inverse_mod_pk(3u32, 7, 0), None);
+    assert_eq!(lift_inverse_mod_pk(3u8, 7, 3), None);
+}
+
+#[test]
+fn lift_sqrt_small_type() {
+    // [a, prm, k, root]
+    let test_cases: [[u32; 4]; 5] = [
+        [2, 7, 2, 10],
+        [4, 3, 3, 25],
+        [1, 5, 4, 1],
+        [6, 5, 3, 16],
+        [10, 13, 2, 32],
+    ];
+
+    for test in test_cases.iter() {
+        assert_eq!(
+            lift_sqrt_mod_pk(test[0], test[1], test[2] as u8),
+            Some(test[3]),
+            "a: {}, prm: {}, k: {}",
+            test[0],
+            test[1],
+            test[2]
+        );
+    }
+}
+
+#[test]
+fn lift_sqrt_all_exponents() {
+    let prm = 7u64;
+
+    for k in 1..=22u8 {
+        let modu = prm.pow(k.into());
+
+        for a in [2u64, 11, 1_000_000_009, 7 * 1_000 + 4].iter() {
+            match lift_sqrt_mod_pk(*a, prm, k) {
+                Some(x) => {
+                    assert!(x < modu);
+                    assert_eq!(u64::mult_mod(x, x, modu), a % modu, "a: {}, k: {}", a, k);
+                }
+                None => panic!("a: {}, k: {}, root: None", a, k),
+            }
+        }
+    }
+}
+
+#[test]
+fn lift_sqrt_large_type() {
+    let prm: u128 = 1_000_000_007;
+    let k = 4;
+    let modu = prm.pow(k);
+    let a = 3;
+
+    match lift_sqrt_mod_pk(a, prm, k as u8) {
+        Some(x) => assert_eq!(u128::mult_mod(x, x, modu), a),
+        None => panic!("root: None"),
+    }
+}
+
+#[test]
+fn lift_sqrt_invalid_arguments() {
+    assert_eq!(lift_sqrt_mod_pk(3u32, 7, 2), None);
+    assert_eq!(lift_sqrt_mod_pk(7u32, 7, 2), None);
+    assert_eq!(lift_sqrt_mod_pk(1u32, 2, 3), None);
+    assert_eq!(lift_sqrt_mod_pk(1u32, 15, 1), None);
+    assert_eq!(lift_sqrt_mod_pk(2u16, 7, 6), None);
+}
+
+#[test]
+fn lift_simple_root_of_quadratic() {
+    // 3x^2 + 5x = 2 (mod 11) has a simple root 4
+    let (prm, k) = (11u64, 9);
+    let modu = prm.pow(k);
+
+    let x = lift_simple_root(3, 5, 2, 4, prm, k as u8);
+
+    let poly = (u64::mult_mod(3, u64::mult_mod(x, x, modu), modu) + 5 * x) % modu;
+
+    assert_eq!(x % prm, 4);
+    assert_eq!(poly, 2);
+}
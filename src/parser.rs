@@ -2,13 +2,22 @@
 //!
 //! Library crate doesn't need this but binary crate does.
 //!
-use std::str::FromStr;
+//! Command line consists of a subcommand (`solve`, `factor`, `prime`, `inverse`, `crt`,
+//! `dlog`, `order` or `totient`) and its arguments. Bare equation or its coefficients without
+//! a subcommand are accepted as a shorthand of `solve`. Options `--output`, `--json` and the
+//! factorization options are global and may be given before or after the subcommand.
+//!
+use std::{fmt, time::Duration};
 
+use clap::{
+    error::{ContextKind, ContextValue, ErrorKind},
+    ArgAction, Args, Parser, Subcommand, ValueEnum,
+};
 use num::PrimInt;
 
-use crate::{LinEqSigned, QuadEqSigned};
+use modular_equations::{FactorConfig, ParseEquationError};
 
-const VERSION: Option<&str> = option_env!("CARGO_PKG_VERSION");
+use crate::{CubicEqSigned, LinEqSigned, PolyEqSigned, QuadEqSigned};
 
 const I64_VALID_MIN: i128 = (i64::MIN + 1) as i128;
 const I64_VALID_MAX: i128 = i64::MAX as i128;
@@ -19,115 +28,632 @@ pub enum EquaKind {
     LinearI128(LinEqSigned<i128, u128>),
     QuadI64(QuadEqSigned<i64, u64>),
     QuadI128(QuadEqSigned<i128, u128>),
+    CubicI64(CubicEqSigned<i64, u64>),
+    CubicI128(CubicEqSigned<i128, u128>),
+    PolyI64(PolyEqSigned<i64, u64>),
+    PolyI128(PolyEqSigned<i128, u128>),
+}
+
+/// Task given by the subcommand, its arguments being parsed and validated.
+pub enum Task {
+    Solve(EquaKind),
+    Factor(u128),
+    Prime(u128),
+    Inverse { a: i128, modu: u128 },
+    Crt(Vec<(u128, u128)>),
+    Dlog { g: u128, h: u128, modu: u128 },
+    Order { a: u128, modu: u128 },
+    Totient(u128),
+}
+
+/// Parsed command line: the task, whether output should be JSON or CSV, how solutions of
+/// equations are printed and factorization limits for quadratic equations and the
+/// factor command.
+pub struct CliArgs {
+    pub task: Task,
+    pub json: bool,
+    pub csv: bool,
+    pub count_only: bool,
+    pub limit: Option<usize>,
+    pub balanced: bool,
+    pub check: Option<i128>,
+    pub factor_config: FactorConfig,
+}
+
+#[derive(Parser)]
+#[command(
+    name = "modular_equations",
+    version,
+    about = "Solve modular equations and compute related number-theoretic functions",
+    allow_negative_numbers = true,
+    disable_version_flag = true
+)]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    /// Equation or its coefficients, shorthand of the solve command
+    #[arg(value_name = "EQUATION")]
+    coefs: Vec<String>,
+
+    #[command(flatten)]
+    options: Options,
+
+    /// Print version
+    #[arg(short = 'v', long, action = ArgAction::Version)]
+    version: Option<bool>,
+}
+
+/// Format of the printed results and errors.
+#[derive(Clone, Copy, PartialEq, ValueEnum)]
+enum OutputFormat {
+    /// Human-readable text, errors printed to stderr
+    Text,
+    /// Single JSON object printed to stdout
+    Json,
+    /// Rows `modulus,solution_index,solution` of the solutions, errors printed to stderr
+    Csv,
+}
+
+#[derive(Args)]
+struct Options {
+    /// Format of results and errors
+    #[arg(long, global = true, value_name = "FORMAT", default_value = "text")]
+    output: OutputFormat,
+
+    /// Shorthand of `--output json`
+    #[arg(long, global = true)]
+    json: bool,
+
+    /// Print only the count of solutions of an equation
+    #[arg(long, global = true)]
+    count_only: bool,
+
+    /// Print at most N solutions of an equation
+    #[arg(long, global = true, value_name = "N", value_parser = parse_positive::<usize>)]
+    limit: Option<usize>,
+
+    /// Print solutions of an equation as representatives in (-n/2, n/2]
+    #[arg(long, global = true)]
+    balanced: bool,
+
+    /// Check whether X is a solution of an equation instead of solving it
+    #[arg(long, global = true, value_name = "X", value_parser = parse_number::<i128>)]
+    check: Option<i128>,
+
+    /// Elliptic curves per worker and factorization round (default 125)
+    #[arg(long, global = true, value_name = "N", value_parser = parse_positive::<usize>)]
+    max_curves: Option<usize>,
+
+    /// First stage bound of each elliptic curve (default 10000)
+    #[arg(long, global = true, value_name = "N", value_parser = parse_number::<u64>)]
+    curve_bound: Option<u64>,
+
+    /// Largest small prime of the initial trial division (default 251)
+    #[arg(long, global = true, value_name = "N", value_parser = parse_number::<u32>)]
+    small_prime_bound: Option<u32>,
+
+    /// Largest trial divisor of the wheel factorization that follows the small primes
+    #[arg(long, global = true, value_name = "N", value_parser = parse_number::<u128>)]
+    trial_bound: Option<u128>,
+
+    /// Give up factorization after this time
+    #[arg(long, global = true, value_name = "SECONDS", value_parser = parse_timeout)]
+    timeout: Option<Duration>,
+
+    /// Factorization thread count, at least two (default from env var
+    /// MODULAR_EQUATIONS_WORKERS or available parallelism)
+    #[arg(
+        long,
+        visible_alias = "threads",
+        global = true,
+        value_name = "N",
+        value_parser = parse_workers
+    )]
+    workers: Option<usize>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Solve linear, quadratic, cubic or higher degree modular equation
+    ///
+    /// Equation is given either in the notation "3x^2 + 2x + 1 = 5 (mod 17)" or as the
+    /// coefficients b c d n of a linear, a b c d n of a quadratic or a b c d e n of
+    /// a cubic equation ax^3 + bx^2 + cx + d = e (mod n). Each further coefficient raises
+    /// the degree by one, the coefficients being given from the highest power of x down to
+    /// the right-hand side. Equation starting with a minus sign must be preceded by `--`.
+    #[command(allow_negative_numbers = true)]
+    Solve {
+        #[arg(value_name = "EQUATION", required = true)]
+        equation: Vec<String>,
+    },
+    /// Factor a natural number n > 1 to its prime factors
+    Factor {
+        #[arg(value_name = "N", value_parser = parse_modulo)]
+        num: u128,
+    },
+    /// Test primality of a natural number
+    Prime {
+        #[arg(value_name = "N", value_parser = parse_number::<u128>)]
+        num: u128,
+    },
+    /// Multiplicative inverse of a modulo n
+    #[command(allow_negative_numbers = true)]
+    Inverse {
+        #[arg(value_parser = parse_number::<i128>)]
+        a: i128,
+        #[arg(value_name = "N", value_parser = parse_modulo)]
+        modu: u128,
+    },
+    /// Combine congruences x = r_i (mod m_i) by the Chinese remainder theorem
+    Crt {
+        /// Pairs of residues and positive moduli, r_1 m_1 r_2 m_2 ...
+        #[arg(value_name = "R M", required = true, value_parser = parse_number::<u128>)]
+        congruences: Vec<u128>,
+    },
+    /// Discrete logarithm x of g^x = h (mod n)
+    Dlog {
+        #[arg(value_parser = parse_number::<u128>)]
+        g: u128,
+        #[arg(value_parser = parse_number::<u128>)]
+        h: u128,
+        #[arg(value_name = "N", value_parser = parse_modulo)]
+        modu: u128,
+    },
+    /// Multiplicative order of a modulo n
+    Order {
+        #[arg(value_parser = parse_number::<u128>)]
+        a: u128,
+        #[arg(value_name = "N", value_parser = parse_modulo)]
+        modu: u128,
+    },
+    /// Euler's totient function of a natural number
+    Totient {
+        #[arg(value_name = "N", value_parser = parse_number::<u128>)]
+        num: u128,
+    },
+}
+
+/// Errors of command line parsing.
+///
+/// Variant `Info` isn't an actual error but signals that help or version
+/// information was printed and the program should exit successfully.
+#[derive(Debug, PartialEq)]
+pub enum ArgError {
+    Info,
+    NoArgs,
+    ArgCountMismatch(usize),
+    /// Residue of the last congruence has no modulus.
+    UnpairedCongruence,
+    /// Coefficient at one-based position `arg` couldn't be parsed.
+    InvalidCoefficient {
+        arg: usize,
+        value: String,
+    },
+    InvalidModulo(String),
+    ZeroCoefficient,
+    /// Option `name` got an invalid or missing `value`.
+    InvalidOption {
+        name: String,
+        value: String,
+    },
+    /// Positional argument `name` got an invalid `value`.
+    InvalidValue {
+        name: String,
+        value: String,
+    },
+    /// Equation given in the notation couldn't be parsed.
+    InvalidEquation(ParseEquationError),
+    /// Command line doesn't match the usage, e.g. unknown subcommand.
+    InvalidUsage(String),
+}
+
+impl ArgError {
+    /// Machine-readable error code.
+    pub fn code(&self) -> &'static str {
+        match self {
+            ArgError::Info => "info",
+            ArgError::NoArgs => "no_arguments",
+            ArgError::ArgCountMismatch(_) => "arg_count_mismatch",
+            ArgError::UnpairedCongruence => "unpaired_congruence",
+            ArgError::InvalidCoefficient { .. } => "invalid_coefficient",
+            ArgError::InvalidModulo(_) => "invalid_modulo",
+            ArgError::ZeroCoefficient => "zero_coefficient",
+            ArgError::InvalidOption { .. } => "invalid_option",
+            ArgError::InvalidValue { .. } => "invalid_value",
+            ArgError::InvalidEquation(_) => "invalid_equation",
+            ArgError::InvalidUsage(_) => "invalid_usage",
+        }
+    }
+
+    /// Error as a JSON object, e.g. {"error": "invalid_coefficient", "arg": 2, "value": "x"}.
+    pub fn to_json(&self) -> String {
+        let details = match self {
+            ArgError::ArgCountMismatch(count) => format!(", \"count\": {}", count),
+            ArgError::InvalidCoefficient { arg, value } => {
+                format!(", \"arg\": {}, \"value\": \"{}\"", arg, json_escape(value))
+            }
+            ArgError::InvalidModulo(value) => format!(", \"value\": \"{}\"", json_escape(value)),
+            ArgError::InvalidOption { name, value } => format!(
+                ", \"option\": \"{}\", \"value\": \"{}\"",
+                json_escape(name),
+                json_escape(value)
+            ),
+            ArgError::InvalidValue { name, value } => format!(
+                ", \"arg\": \"{}\", \"value\": \"{}\"",
+                json_escape(name),
+                json_escape(value)
+            ),
+            ArgError::InvalidEquation(err) => {
+                format!(", \"message\": \"{}\"", json_escape(&err.to_string()))
+            }
+            ArgError::InvalidUsage(message) => {
+                format!(", \"message\": \"{}\"", json_escape(message))
+            }
+            _ => String::new(),
+        };
+
+        format!("{{\"error\": \"{}\"{}}}", self.code(), details)
+    }
+
+    /// Convert error of the clap parser, keeping the offending argument and value if known.
+    fn from_clap(err: &clap::Error) -> Self {
+        let context = |kind| match err.get(kind) {
+            Some(ContextValue::String(value)) => Some(value.to_string()),
+            _ => None,
+        };
+
+        match (
+            err.kind(),
+            context(ContextKind::InvalidArg),
+            context(ContextKind::InvalidValue),
+        ) {
+            (ErrorKind::ValueValidation, Some(arg), Some(value)) => {
+                // Argument is shown with its value name, e.g. "--workers <N>"
+                let name = arg.split(' ').next().unwrap_or(&arg).to_string();
+
+                if name.starts_with("--") {
+                    ArgError::InvalidOption { name, value }
+                } else {
+                    ArgError::InvalidValue { name, value }
+                }
+            }
+            (ErrorKind::InvalidValue, Some(name), Some(value)) if name.starts_with("--") => {
+                let name = name.split(' ').next().unwrap_or(&name).to_string();
+                ArgError::InvalidOption { name, value }
+            }
+            _ => {
+                let message = err.kind().as_str().unwrap_or("invalid usage");
+                ArgError::InvalidUsage(message.to_string())
+            }
+        }
+    }
+}
+
+impl fmt::Display for ArgError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ArgError::Info => write!(f, "help"),
+            ArgError::NoArgs => write!(f, "no arguments provided."),
+            ArgError::ArgCountMismatch(_) => write!(
+                f,
+                "arg count mismatch: pass four for linear, five for quadratic, six for cubic \
+                 or more for higher degree equation."
+            ),
+            ArgError::UnpairedCongruence => {
+                write!(f, "pass congruences as pairs of a residue and modulus.")
+            }
+            ArgError::InvalidCoefficient { arg, value } => {
+                write!(f, "invalid value `{}` for coefficient arg {}.", value, arg)
+            }
+            ArgError::InvalidModulo(value) => {
+                write!(
+                    f,
+                    "invalid modulo `{}`, must be an integer larger than one.",
+                    value
+                )
+            }
+            ArgError::ZeroCoefficient => {
+                write!(f, "coefficient for x^2 or x term must be nonzero.")
+            }
+            ArgError::InvalidOption { name, value } => {
+                write!(f, "invalid value `{}` for option {}.", value, name)
+            }
+            ArgError::InvalidValue { name, value } => {
+                write!(f, "invalid value `{}` for argument {}.", value, name)
+            }
+            ArgError::InvalidEquation(err) => write!(f, "{}.", err),
+            ArgError::InvalidUsage(message) => write!(f, "{}.", message),
+        }
+    }
 }
 
 #[derive(PartialEq)]
 enum EqType {
     Linear,
     Quad,
+    Cubic,
+    Poly,
 }
 
-pub fn parse_args(args: &[String]) -> Result<EquaKind, String> {
-    let args_len = args.len();
-
-    match args_len {
-        0 => Err("no arguments provided.".to_string()),
-        1 if &args[0] == "--help" || &args[0] == "-h" => {
-            show_help();
-            Err("help".to_string())
+impl EqType {
+    /// Type of the equation given by `coef_count` coefficients, right-hand side included.
+    fn from_coef_count(coef_count: usize) -> Self {
+        match coef_count {
+            3 => EqType::Linear,
+            4 => EqType::Quad,
+            5 => EqType::Cubic,
+            _ => EqType::Poly,
         }
-        1 if &args[0] == "--version" || &args[0] == "-v" => {
-            println!("modular_equations {}", VERSION.unwrap_or(""));
-            Err("help".to_string())
+    }
+}
+
+/// Whether JSON output is requested by `args`, also when they cannot be parsed.
+pub fn is_json_requested(args: &[String]) -> bool {
+    args.iter()
+        .zip(args.iter().skip(1).map(Some).chain([None]))
+        .any(|(arg, next)| {
+            arg == "--json"
+                || arg == "--output=json"
+                || (arg == "--output" && next.is_some_and(|next| next == "json"))
+        })
+}
+
+/// Parse command line `args`, the first one being the program name.
+///
+/// Errors of the usage are printed by clap unless JSON output was requested,
+/// in which case they are returned to be printed as JSON.
+pub fn parse_args(args: &[String]) -> Result<CliArgs, ArgError> {
+    if args.len() <= 1 {
+        return Err(ArgError::NoArgs);
+    }
+
+    let json = is_json_requested(args);
+
+    // Clap accepts only decimal negative numbers as values, convert the prefixed literals
+    let args = args.iter().map(|arg| match arg.get(..3) {
+        Some("-0x" | "-0X" | "-0b" | "-0B" | "-0o" | "-0O") => {
+            parse_to_number::<i128>(arg).map_or_else(|| arg.to_string(), |num| num.to_string())
         }
-        4 => {
-            if let Some(lin_equa) = parse_to_equation(args) {
-                Ok(lin_equa)
-            } else {
-                Err("invalid arg values for linear equation.".to_string())
+        _ => arg.to_string(),
+    });
+
+    let cli = match Cli::try_parse_from(args) {
+        Ok(cli) => cli,
+        Err(err) => match err.kind() {
+            ErrorKind::DisplayHelp | ErrorKind::DisplayVersion => {
+                let _ = err.print();
+                return Err(ArgError::Info);
             }
+            _ if json => return Err(ArgError::from_clap(&err)),
+            _ => err.exit(),
+        },
+    };
+
+    let task = match cli.command {
+        None if cli.coefs.is_empty() => return Err(ArgError::NoArgs),
+        None if cli.coefs.len() == 1 => Task::Solve(parse_notation(&cli.coefs[0])?),
+        None => Task::Solve(parse_to_equation(&cli.coefs)?),
+        Some(Command::Solve { equation }) if equation.len() == 1 => {
+            Task::Solve(parse_notation(&equation[0])?)
         }
-        5 => {
-            if let Some(quad_eq) = parse_to_equation(args) {
-                Ok(quad_eq)
-            } else {
-                Err("invalid arg values for quadratic equation.".to_string())
+        Some(Command::Solve { equation }) => Task::Solve(parse_to_equation(&equation)?),
+        Some(Command::Factor { num }) => Task::Factor(num),
+        Some(Command::Prime { num }) => Task::Prime(num),
+        Some(Command::Inverse { a, modu }) => Task::Inverse { a, modu },
+        Some(Command::Crt { congruences }) => {
+            if congruences.len() % 2 != 0 {
+                return Err(ArgError::UnpairedCongruence);
+            }
+            // Modulo zero isn't a congruence, reject it as an invalid value
+            if congruences.chunks(2).any(|pair| pair[1] == 0) {
+                return Err(ArgError::InvalidValue {
+                    name: "<M>".to_string(),
+                    value: "0".to_string(),
+                });
             }
+            Task::Crt(
+                congruences
+                    .chunks(2)
+                    .map(|pair| (pair[0], pair[1]))
+                    .collect(),
+            )
         }
-        _ => Err(
-            "arg count mismatch: pass either four for linear or five for quadratic equation."
-                .to_string(),
-        ),
+        Some(Command::Dlog { g, h, modu }) => Task::Dlog { g, h, modu },
+        Some(Command::Order { a, modu }) => Task::Order { a, modu },
+        Some(Command::Totient { num }) => Task::Totient(num),
+    };
+
+    let json = cli.options.json || cli.options.output == OutputFormat::Json;
+    let csv = !json && cli.options.output == OutputFormat::Csv;
+
+    let prints_solutions = match task {
+        Task::Solve(_) => !cli.options.count_only && cli.options.check.is_none(),
+        Task::Crt(_) => true,
+        _ => false,
+    };
+    if csv && !prints_solutions {
+        return Err(ArgError::InvalidUsage(
+            "CSV output is supported only for solutions of equations and congruences".to_string(),
+        ));
     }
+
+    Ok(CliArgs {
+        task,
+        json,
+        csv,
+        count_only: cli.options.count_only,
+        limit: cli.options.limit,
+        balanced: cli.options.balanced,
+        check: cli.options.check,
+        factor_config: cli.options.factor_config(),
+    })
 }
 
-fn parse_to_equation(args: &[String]) -> Option<EquaKind> {
-    let args_len = args.len();
+impl Options {
+    fn factor_config(&self) -> FactorConfig {
+        let mut config = FactorConfig::default();
 
-    let eq_type = match args_len {
-        4 => EqType::Linear,
-        5 => EqType::Quad,
-        _ => return None,
-    };
-    let mut coefs: [Option<i128>; 4] = [None; 4];
-    let mut modulo: Option<u128> = None;
-
-    for (idx, arg) in args.iter().enumerate() {
-        if idx == args_len - 1 {
-            modulo = parse_to_number::<u128>(arg);
-        } else {
-            coefs[idx] = parse_to_number::<i128>(arg);
+        if let Some(curves) = self.max_curves {
+            config.max_curves = curves;
+        }
+        if let Some(bound) = self.curve_bound {
+            config.curve_bound = bound;
+        }
+        if let Some(bound) = self.small_prime_bound {
+            config.small_prime_bound = bound;
         }
+        if let Some(workers) = self.workers {
+            config.workers = workers;
+        }
+        config.trial_bound = self.trial_bound;
+        config.timeout = self.timeout;
+
+        config
     }
+}
 
-    let coefs_len = coefs.len();
+fn parse_number<T: PrimInt>(arg: &str) -> Result<T, String> {
+    parse_to_number(arg).ok_or_else(|| "not a valid integer".to_string())
+}
 
-    match eq_type {
-        EqType::Linear => parse_proper_type(&coefs[..coefs_len - 1], modulo, eq_type),
-        EqType::Quad => parse_proper_type(&coefs, modulo, eq_type),
+fn parse_positive<T: PrimInt>(arg: &str) -> Result<T, String> {
+    match parse_to_number::<T>(arg) {
+        Some(num) if num > T::zero() => Ok(num),
+        _ => Err("not a positive integer".to_string()),
     }
 }
 
-fn parse_to_number<T: PrimInt + FromStr>(arg: &str) -> Option<T> {
-    match (*arg).parse::<T>() {
-        Ok(num) => Some(num),
-        Err(_) => {
-            let mut arg = String::from(arg);
-            arg.retain(|c| c != '_');
+fn parse_modulo(arg: &str) -> Result<u128, String> {
+    match parse_to_number::<u128>(arg) {
+        Some(modu) if modu > 1 => Ok(modu),
+        _ => Err("not an integer larger than one".to_string()),
+    }
+}
 
-            if let Ok(num) = arg.parse::<T>() {
-                Some(num)
-            } else {
-                None
-            }
+fn parse_workers(arg: &str) -> Result<usize, String> {
+    // Factorization needs the wheel thread and at least one elliptic-curve thread
+    match parse_to_number::<usize>(arg) {
+        Some(workers) if workers > 1 => Ok(workers),
+        _ => Err("not an integer larger than one".to_string()),
+    }
+}
+
+fn parse_timeout(arg: &str) -> Result<Duration, String> {
+    match arg.parse::<f64>() {
+        Ok(secs) if secs.is_finite() && secs >= 0.0 => Ok(Duration::from_secs_f64(secs)),
+        _ => Err("not a nonnegative number of seconds".to_string()),
+    }
+}
+
+/// Escape string `s` to be placed inside JSON quotes.
+pub fn json_escape(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            c if c.is_control() => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
         }
     }
+
+    escaped
 }
 
-fn parse_proper_type(
-    coefs: &[Option<i128>],
-    modulo: Option<u128>,
-    eq_type: EqType,
-) -> Option<EquaKind> {
-    if coefs.iter().any(|&coef| coef.is_none()) || modulo.is_none() {
-        return None;
+/// Parse equation given in the notation, e.g. "3x^2 + 2x + 1 = 5 (mod 17)".
+///
+/// Degree of the equation is the highest power of the unknown in the notation.
+fn parse_notation(s: &str) -> Result<EquaKind, ArgError> {
+    let eq = s
+        .parse::<PolyEqSigned<i128, u128>>()
+        .map_err(ArgError::InvalidEquation)?;
+
+    if eq.modu <= 1 {
+        return Err(ArgError::InvalidModulo(eq.modu.to_string()));
     }
 
-    let modu = modulo.unwrap();
+    // Coefficients from the highest power down, at least those of a linear equation
+    let mut coefs = eq.coefs;
+    coefs.resize(coefs.len().max(2), 0);
+    coefs.reverse();
+    coefs.push(eq.rhs);
 
-    if modu <= 1 {
-        // Modulo must be at least two
-        return None;
+    build_equation(&coefs, eq.modu)
+}
+
+fn parse_to_equation(args: &[String]) -> Result<EquaKind, ArgError> {
+    let args_len = args.len();
+
+    if args_len < 4 {
+        return Err(ArgError::ArgCountMismatch(args_len));
+    }
+    let mut coefs: Vec<i128> = Vec::with_capacity(args_len - 1);
+
+    for (idx, arg) in args[..args_len - 1].iter().enumerate() {
+        match parse_to_number::<i128>(arg) {
+            Some(coef) => coefs.push(coef),
+            None => {
+                return Err(ArgError::InvalidCoefficient {
+                    arg: idx + 1,
+                    value: arg.to_string(),
+                })
+            }
+        }
     }
 
-    let coefs: Vec<i128> = coefs.iter().map(|&coef| coef.unwrap()).collect();
+    let modu_arg = &args[args_len - 1];
+
+    let modu = match parse_to_number::<u128>(modu_arg) {
+        // Modulo must be at least two
+        Some(modu) if modu > 1 => modu,
+        _ => return Err(ArgError::InvalidModulo(modu_arg.to_string())),
+    };
+
+    build_equation(&coefs, modu)
+}
+
+/// Build equation of the coefficients, its type given by the coefficient count. Zero leading
+/// coefficient of an equation of degree two or higher lowers its degree by one.
+fn build_equation(coefs: &[i128], modu: u128) -> Result<EquaKind, ArgError> {
+    let eq_type = EqType::from_coef_count(coefs.len());
+
+    if eq_type != EqType::Linear && coefs[0] == 0 {
+        return build_equation(&coefs[1..], modu);
+    }
 
     if coefs[0] == 0 {
-        // Coef for x^2 or x term must be non-zero
+        // Coef for x term of linear equation must be non-zero
+        return Err(ArgError::ZeroCoefficient);
+    }
+
+    Ok(get_proper_eq_type(coefs, modu, eq_type))
+}
+
+/// Parse integer `arg` given in decimal or as a `0x`, `0b` or `0o` prefixed literal,
+/// optionally with a minus sign and underscores separating the digits.
+fn parse_to_number<T: PrimInt>(arg: &str) -> Option<T> {
+    let mut arg = String::from(arg);
+    arg.retain(|c| c != '_');
+
+    let (sign, unsigned) = match arg.strip_prefix('-') {
+        Some(unsigned) => ("-", unsigned),
+        None => ("", arg.as_str()),
+    };
+
+    let (radix, digits) = match unsigned.get(..2) {
+        Some("0x" | "0X") => (16, &unsigned[2..]),
+        Some("0b" | "0B") => (2, &unsigned[2..]),
+        Some("0o" | "0O") => (8, &unsigned[2..]),
+        _ => return T::from_str_radix(&arg, 10).ok(),
+    };
+
+    if digits.starts_with(['+', '-']) {
         return None;
     }
 
-    Some(get_proper_eq_type(&coefs, modu, eq_type))
+    T::from_str_radix(&format!("{}{}", sign, digits), radix).ok()
 }
 
 fn get_proper_eq_type(coefs: &[i128], modu: u128, eq_type: EqType) -> EquaKind {
@@ -163,13 +689,36 @@ fn get_proper_eq_type(coefs: &[i128], modu: u128, eq_type: EqType) -> EquaKind {
             d: coefs[3],
             modu,
         }),
+        (EqType::Cubic, true) => EquaKind::CubicI64(CubicEqSigned::<i64, u64> {
+            a: coefs[0].try_into().unwrap(),
+            b: coefs[1].try_into().unwrap(),
+            c: coefs[2].try_into().unwrap(),
+            d: coefs[3].try_into().unwrap(),
+            e: coefs[4].try_into().unwrap(),
+            modu: modu.try_into().unwrap(),
+        }),
+        (EqType::Cubic, false) => EquaKind::CubicI128(CubicEqSigned::<i128, u128> {
+            a: coefs[0],
+            b: coefs[1],
+            c: coefs[2],
+            d: coefs[3],
+            e: coefs[4],
+            modu,
+        }),
+        // Polynomial coefficients are in ascending order, the last one being the right-hand side
+        (EqType::Poly, true) => EquaKind::PolyI64(PolyEqSigned::<i64, u64> {
+            coefs: coefs[..coefs.len() - 1]
+                .iter()
+                .rev()
+                .map(|&coef| coef.try_into().unwrap())
+                .collect(),
+            rhs: coefs[coefs.len() - 1].try_into().unwrap(),
+            modu: modu.try_into().unwrap(),
+        }),
+        (EqType::Poly, false) => EquaKind::PolyI128(PolyEqSigned::<i128, u128> {
+            coefs: coefs[..coefs.len() - 1].iter().rev().copied().collect(),
+            rhs: coefs[coefs.len() - 1],
+            modu,
+        }),
     }
 }
-
-fn show_help() {
-    println!(
-        "Solve linear or quadratic modular equation ax^2 + bx + c = d (mod n)\n\n\
-        USAGE (linear):\n  modular_equations <b;integer> <c;integer> <d;integer> <n;positive integer>\n\n\
-        USAGE (quadratic):\n  modular_equations <a;integer> <b;integer> <c;integer> <d;integer> <n;positive integer>\n"
-    );
-}
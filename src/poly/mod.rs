@@ -0,0 +1,525 @@
+//! Implements root finding of polynomials modulo prime powers and a solver for polynomial
+//! modular equations of any degree.
+//!
+//! Polynomials are represented by their coefficients in ascending order, i.e.
+//! \[c_0, c_1, ..., c_d\] corresponds to c_0 + c_1 * x + ... + c_d * x^d.
+//!
+//! Roots modulo a prime p are found from g = gcd(f, x^p - x), which is the product
+//! of the distinct linear factors of f. Polynomial g is then split to its linear
+//! factors by Cantor-Zassenhaus method: for an odd prime, gcd(g, (x + δ)^((p-1)/2) - 1)
+//! is a nontrivial factor of g for roughly half of the shifts δ.
+//!
+//! Roots modulo p^k are lifted from the roots modulo p one power at a time. Simple
+//! roots, for which f'(x) != 0 (mod p), lift uniquely by Newton's iteration. A singular
+//! root r modulo p^j lifts either to all of r + t * p^j, 0 <= t < p, or to none of them.
+//!
+//! Polynomial equation c_0 + c_1 * x + ... + c_k * x^k = d (mod n) is solved by finding
+//! the roots modulo each prime power factor of n and combining them by the Chinese
+//! remainder theorem, as cubic equations are solved.
+//!
+use crate::{
+    arith::{Arith, SignCast},
+    crt,
+    factor::{FactorConfig, FactorError, Factors},
+    lin, prime, Int, LinEq, UInt,
+};
+
+/// Primes below this bound are searched for roots by evaluating every residue.
+const EXHAUSTIVE_SEARCH_BOUND: u8 = 64;
+
+/// Type for polynomial equations with unsigned terms only.
+///
+/// Polynomial modular equations are of the form c_0 + c_1 * x + ... + c_k * x^k = rhs
+/// (mod modu), `coefs` being the coefficients \[c_0, c_1, ..., c_k\] in ascending order.
+/// Coefficients and `rhs` must be nonnegative for this type. Modulo `modu` must have
+/// the same unsigned type and positive as its value.
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PolyEq<T: UInt> {
+    pub coefs: Vec<T>,
+    pub rhs: T,
+    pub modu: T,
+}
+
+/// Type for polynomial equations with unsigned modulo and signed other terms.
+///
+/// Polynomial modular equations are of the form c_0 + c_1 * x + ... + c_k * x^k = rhs
+/// (mod modu), `coefs` being the coefficients in ascending order. Coefficients and `rhs`
+/// are signed for this type. Modulo `modu` must be an unsigned type at least as wide as
+/// the signed type, e.g. u32, u64 or u128 if the signed type is i32, and positive as its value.
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PolyEqSigned<S: Int, T: UInt> {
+    pub coefs: Vec<S>,
+    pub rhs: S,
+    pub modu: T,
+}
+
+impl<T: 'static + UInt> PolyEq<T> {
+    /// Solve polynomial modular equation c_0 + c_1 * x + ... + c_k * x^k = rhs (mod modu).
+    ///
+    /// Modulo a prime there are at most k solutions, but for composite modulo the solution
+    /// count can be much larger. As for quadratic equations, composite modulo must first
+    /// be factorized.
+    ///
+    /// If every coefficient c_1, ..., c_k is divisible by the modulo, the unknown vanishes
+    /// and, as for `LinEq`, every residue class is a solution if c_0 = rhs (mod modu).
+    ///
+    /// If there aren't solutions, None is returned.
+    ///
+    /// # Examples
+    ///
+    /// Solve equation x^4 = 1 (mod 65)
+    ///
+    /// ```
+    /// use modular_equations::PolyEq;
+    ///
+    /// let poly_eq = PolyEq::<u32> {coefs: vec![0, 0, 0, 0, 1], rhs: 1, modu: 65};
+    ///
+    /// // Four roots modulo each of the primes 5 and 13
+    /// assert_eq!(poly_eq.solve().map(|x| x.len()), Some(16));
+    /// ```
+    pub fn solve(&self) -> Option<Vec<T>> {
+        // Default configuration has no timeout, hence factorization cannot fail
+        self.solve_with_config(&FactorConfig::default())
+            .unwrap_or(None)
+    }
+
+    /// Solve polynomial modular equation with factorization limits `config`.
+    ///
+    /// Like `QuadEq::solve_with_config`, returns `FactorError::Timeout` or
+    /// `FactorError::Cancelled` if the factorization of the modulo doesn't complete within
+    /// the limits. Otherwise the result equals that of `solve`.
+    pub fn solve_with_config(&self, config: &FactorConfig) -> Result<Option<Vec<T>>, FactorError> {
+        if self.modu == T::zero() {
+            return Ok(None);
+        }
+
+        let mut coefs: Vec<T> = self.coefs.iter().map(|&c| c % self.modu).collect();
+
+        if coefs.is_empty() {
+            coefs.push(T::zero());
+        }
+        coefs[0] = T::sub_mod(coefs[0], self.rhs, self.modu);
+
+        if coefs[1..].iter().all(|&c| c == T::zero()) {
+            // Unknown vanishes, c_0 - rhs = 0 must hold
+            let lin_eq = LinEq {
+                a: T::zero(),
+                b: coefs[0],
+                c: T::zero(),
+                modu: self.modu,
+            };
+            return Ok(lin_eq.solve());
+        }
+
+        roots_mod(&coefs, self.modu, config)
+    }
+
+    /// Evaluate the left and right hand sides of polynomial modular equation at `x`,
+    /// both reduced modulo `modu`.
+    ///
+    /// Value `x` doesn't need to be reduced modulo `modu`. For modulo zero, None is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use modular_equations::PolyEq;
+    ///
+    /// let poly_eq = PolyEq::<u32> {coefs: vec![0, 0, 0, 0, 1], rhs: 1, modu: 65};
+    ///
+    /// assert_eq!(poly_eq.sides(8), Some((1, 1)));
+    /// assert_eq!(poly_eq.sides(2), Some((16, 1)));
+    /// ```
+    pub fn sides(&self, x: T) -> Option<(T, T)> {
+        if self.modu == T::zero() {
+            return None;
+        }
+
+        Some((evaluate(&self.coefs, x, self.modu), self.rhs % self.modu))
+    }
+}
+
+impl<T, S> PolyEqSigned<S, T>
+where
+    S: Int + SignCast<S, T>,
+    T: 'static + UInt + TryFrom<S>,
+{
+    /// Solve polynomial modular equation for signed type terms.
+    ///
+    /// Coefficients are cast to unsigned type such that they represent the smallest
+    /// nonnegative integers of their residue classes, after which the `solve` method of
+    /// the struct `PolyEq` is called. If some of the casts fails, None is returned.
+    ///
+    /// # Examples
+    ///
+    /// Solve equation x^5 - x = 0 (mod 25)
+    ///
+    /// ```
+    /// use modular_equations::PolyEqSigned;
+    ///
+    /// let poly_eq = PolyEqSigned::<i32, u32> {coefs: vec![0, -1, 0, 0, 0, 1], rhs: 0, modu: 25};
+    ///
+    /// assert_eq!(poly_eq.solve(), Some(vec![0, 1, 7, 18, 24]));
+    /// ```
+    pub fn solve(&self) -> Option<Vec<T>> {
+        self.to_unsigned()?.solve()
+    }
+
+    /// Solve polynomial modular equation for signed type terms with factorization
+    /// limits `config`, casting the coefficients as in `solve`.
+    pub fn solve_with_config(&self, config: &FactorConfig) -> Result<Option<Vec<T>>, FactorError> {
+        match self.to_unsigned() {
+            Some(poly_eq) => poly_eq.solve_with_config(config),
+            None => Ok(None),
+        }
+    }
+
+    /// Evaluate the left and right hand sides of polynomial modular equation for signed
+    /// type terms at signed `x` as the `sides` method of `PolyEq`.
+    ///
+    /// Coefficients and `x` are cast to unsigned type as in `solve`. If some of the casts
+    /// fails or modulo is zero, None is returned.
+    pub fn sides(&self, x: S) -> Option<(T, T)> {
+        if self.modu == T::zero() {
+            return None;
+        }
+
+        self.to_unsigned()?
+            .sides(S::cast_to_unsigned(x, self.modu)?)
+    }
+
+    fn to_unsigned(&self) -> Option<PolyEq<T>> {
+        // Cast fails only for S::min_value()
+        let coefs = self
+            .coefs
+            .iter()
+            .map(|&coef| S::cast_to_unsigned(coef, self.modu))
+            .collect::<Option<Vec<T>>>()?;
+
+        Some(PolyEq {
+            coefs,
+            rhs: S::cast_to_unsigned(self.rhs, self.modu)?,
+            modu: self.modu,
+        })
+    }
+}
+
+/// Roots of polynomial `coefs` modulo `modu`, factorizing a composite modulo with limits
+/// `config`.
+///
+/// Roots modulo the prime power factors of the modulo are combined by the Chinese remainder
+/// theorem and returned in ascending order. If the polynomial vanishes modulo a prime power
+/// larger than 2^20, its residues aren't enumerated and None is returned as for `LinEq`.
+pub(crate) fn roots_mod<T: 'static + UInt>(
+    coefs: &[T],
+    modu: T,
+    config: &FactorConfig,
+) -> Result<Option<Vec<T>>, FactorError> {
+    let factor_repr = if prime::is_odd_prime(modu) {
+        vec![(modu, 1)]
+    } else {
+        let mut factors = Factors::new(modu);
+
+        factors.factorize_with_config(config)?;
+        factors.prime_factor_repr()
+    };
+
+    // Solutions as residue classes (x, m), combined one prime power at a time
+    let mut sols: Vec<T> = vec![T::zero()];
+    let mut sols_modu = T::one();
+
+    for &(prm, k) in factor_repr.iter() {
+        let prm_power = prm.pow(k.into());
+
+        if coefs.iter().all(|&c| c % prm_power == T::zero())
+            && prm_power.into() > lin::ALL_RESIDUES_MAX_MODULO
+        {
+            return Ok(None);
+        }
+
+        let roots = roots_mod_prime_power(coefs, prm, k);
+
+        if roots.is_empty() {
+            return Ok(None);
+        }
+
+        let mut combined = Vec::with_capacity(sols.len() * roots.len());
+
+        for &x in sols.iter() {
+            for &root in roots.iter() {
+                // Moduli are coprime and their product divides `modu`
+                match crt::combine_pair(x, sols_modu, root, prm_power) {
+                    Some((x_new, _)) => combined.push(x_new),
+                    None => return Ok(None),
+                }
+            }
+        }
+
+        sols = combined;
+        sols_modu = sols_modu * prm_power;
+    }
+
+    sols.sort_unstable();
+
+    Ok(Some(sols))
+}
+
+/// Roots of polynomial `coefs` modulo `prm`^`k`, sorted in ascending order.
+///
+/// Prime `prm` and its power must fit into the type T. If the polynomial is identically
+/// zero modulo the prime, every residue is a candidate root and their count can be huge.
+pub fn roots_mod_prime_power<T: UInt>(coefs: &[T], prm: T, k: u8) -> Vec<T> {
+    let modu = prm.pow(k.into());
+    let coefs: Vec<T> = coefs.iter().map(|&c| c % modu).collect();
+
+    let mut roots = roots_mod_prime(&coefs, prm);
+    let deriv = derivative(&coefs, modu);
+
+    let mut prm_power = prm;
+
+    for _ in 1..k {
+        let next_power = prm_power * prm;
+        let mut lifted_roots = vec![];
+
+        for &root in roots.iter() {
+            let value = evaluate(&coefs, root, next_power);
+            let deriv_value = evaluate(&deriv, root, prm);
+
+            if deriv_value != T::zero() {
+                // Simple root, f(r + t * p^j) = f(r) + t * p^j * f'(r) (mod p^(j+1))
+                let t = T::mult_mod(
+                    T::sub_mod(T::zero(), value / prm_power, prm),
+                    T::multip_inv(deriv_value, prm),
+                    prm,
+                );
+                lifted_roots.push(root + t * prm_power);
+            } else if value == T::zero() {
+                // Singular root, every lifting is a root
+                let mut t = T::zero();
+
+                while t < prm {
+                    lifted_roots.push(root + t * prm_power);
+                    t = t + T::one();
+                }
+            }
+        }
+
+        roots = lifted_roots;
+        prm_power = next_power;
+
+        if roots.is_empty() {
+            break;
+        }
+    }
+
+    roots.sort_unstable();
+    roots
+}
+
+/// Roots of polynomial `coefs` modulo the prime `prm`, sorted in ascending order.
+pub fn roots_mod_prime<T: UInt>(coefs: &[T], prm: T) -> Vec<T> {
+    let f = normalize(coefs, prm);
+
+    if f.is_empty() || prm < EXHAUSTIVE_SEARCH_BOUND.into() {
+        // Zero polynomial or a small prime, evaluate every residue
+        let mut roots = vec![];
+        let mut x = T::zero();
+
+        while x < prm {
+            if evaluate(&f, x, prm) == T::zero() {
+                roots.push(x);
+            }
+            x = x + T::one();
+        }
+        return roots;
+    }
+
+    if f.len() == 1 {
+        // Nonzero constant
+        return vec![];
+    }
+
+    let f = make_monic(&f, prm);
+
+    // x^p mod f, the product of distinct linear factors of f is gcd(f, x^p - x)
+    let x_pow = pow_mod(&[T::zero(), T::one()], prm, &f, prm);
+    let x_pow_minus_x = sub(&x_pow, &[T::zero(), T::one()], prm);
+
+    let g = gcd(&f, &x_pow_minus_x, prm);
+
+    let mut roots = vec![];
+    split_linear_factors(&g, prm, &mut roots);
+
+    roots.sort_unstable();
+    roots
+}
+
+/// Split monic `g`, a product of distinct linear factors, and push its roots to `roots`.
+fn split_linear_factors<T: UInt>(g: &[T], prm: T, roots: &mut Vec<T>) {
+    match g.len() {
+        0 | 1 => return,
+        2 => {
+            // x + g_0
+            roots.push(T::sub_mod(T::zero(), g[0], prm));
+            return;
+        }
+        _ => (),
+    }
+
+    let exponent = (prm - T::one()) / 2.into();
+    let mut delta = T::zero();
+
+    while delta < prm {
+        // (x + δ)^((p-1)/2) - 1 mod g
+        let h = pow_mod(&[delta, T::one()], exponent, g, prm);
+        let h = sub(&h, &[T::one()], prm);
+
+        let d = gcd(g, &h, prm);
+
+        if d.len() > 1 && d.len() < g.len() {
+            let (quotient, _) = div_rem(g, &d, prm);
+
+            split_linear_factors(&d, prm, roots);
+            split_linear_factors(&quotient, prm, roots);
+            return;
+        }
+
+        delta = delta + T::one();
+    }
+}
+
+/// Derivative of polynomial `coefs` modulo `modu`.
+pub fn derivative<T: UInt>(coefs: &[T], modu: T) -> Vec<T> {
+    let mut deriv = vec![];
+    let mut i = T::one();
+
+    for &c in coefs.iter().skip(1) {
+        deriv.push(T::mult_mod(c, i, modu));
+        i = i + T::one();
+    }
+
+    deriv
+}
+
+/// Value of polynomial `coefs` at `x` modulo `modu` by Horner's method.
+pub fn evaluate<T: UInt>(coefs: &[T], x: T, modu: T) -> T {
+    coefs.iter().rev().fold(T::zero(), |acc, &c| {
+        T::add_mod(T::mult_mod(acc, x, modu), c, modu)
+    })
+}
+
+/// Reduce coefficients modulo `modu` and drop the leading zero coefficients.
+fn normalize<T: UInt>(coefs: &[T], modu: T) -> Vec<T> {
+    let mut f: Vec<T> = coefs.iter().map(|&c| c % modu).collect();
+
+    while f.last() == Some(&T::zero()) {
+        f.pop();
+    }
+
+    f
+}
+
+fn make_monic<T: UInt>(f: &[T], prm: T) -> Vec<T> {
+    let lead_inv = T::multip_inv(f[f.len() - 1], prm);
+
+    f.iter().map(|&c| T::mult_mod(c, lead_inv, prm)).collect()
+}
+
+fn sub<T: UInt>(f: &[T], g: &[T], prm: T) -> Vec<T> {
+    let len = f.len().max(g.len());
+    let mut diff = Vec::with_capacity(len);
+
+    for i in 0..len {
+        let f_i = f.get(i).copied().unwrap_or_else(T::zero);
+        let g_i = g.get(i).copied().unwrap_or_else(T::zero);
+
+        diff.push(T::sub_mod(f_i, g_i, prm));
+    }
+
+    normalize(&diff, prm)
+}
+
+fn mul<T: UInt>(f: &[T], g: &[T], prm: T) -> Vec<T> {
+    if f.is_empty() || g.is_empty() {
+        return vec![];
+    }
+
+    let mut prod = vec![T::zero(); f.len() + g.len() - 1];
+
+    for (i, &f_i) in f.iter().enumerate() {
+        for (j, &g_j) in g.iter().enumerate() {
+            prod[i + j] = T::add_mod(prod[i + j], T::mult_mod(f_i, g_j, prm), prm);
+        }
+    }
+
+    normalize(&prod, prm)
+}
+
+/// Quotient and remainder of `f` divided by nonzero `g` modulo the prime `prm`.
+fn div_rem<T: UInt>(f: &[T], g: &[T], prm: T) -> (Vec<T>, Vec<T>) {
+    let mut rem = normalize(f, prm);
+
+    if rem.len() < g.len() {
+        return (vec![], rem);
+    }
+
+    let g_lead_inv = T::multip_inv(g[g.len() - 1], prm);
+    let mut quotient = vec![T::zero(); rem.len() - g.len() + 1];
+
+    while rem.len() >= g.len() {
+        let shift = rem.len() - g.len();
+        let factor = T::mult_mod(rem[rem.len() - 1], g_lead_inv, prm);
+
+        quotient[shift] = factor;
+
+        for (i, &g_i) in g.iter().enumerate() {
+            rem[shift + i] = T::sub_mod(rem[shift + i], T::mult_mod(factor, g_i, prm), prm);
+        }
+
+        rem = normalize(&rem, prm);
+    }
+
+    (normalize(&quotient, prm), rem)
+}
+
+/// Monic gcd of polynomials `f` and `g` modulo the prime `prm`.
+fn gcd<T: UInt>(f: &[T], g: &[T], prm: T) -> Vec<T> {
+    let mut a = normalize(f, prm);
+    let mut b = normalize(g, prm);
+
+    while !b.is_empty() {
+        let (_, rem) = div_rem(&a, &b, prm);
+        a = b;
+        b = rem;
+    }
+
+    if a.is_empty() {
+        a
+    } else {
+        make_monic(&a, prm)
+    }
+}
+
+/// Polynomial `base`^`exponent` modulo polynomial `modu_poly` and the prime `prm`.
+fn pow_mod<T: UInt>(base: &[T], mut exponent: T, modu_poly: &[T], prm: T) -> Vec<T> {
+    let mut result = vec![T::one()];
+    let (_, mut base) = div_rem(base, modu_poly, prm);
+
+    while exponent > T::zero() {
+        if exponent & T::one() == T::one() {
+            result = div_rem(&mul(&result, &base, prm), modu_poly, prm).1;
+        }
+
+        exponent = exponent.unsigned_shr(1);
+
+        if exponent > T::zero() {
+            base = div_rem(&mul(&base, &base, prm), modu_poly, prm).1;
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,212 @@
+use std::time::Duration;
+
+use crate::factor::{FactorConfig, FactorError};
+use crate::poly::{
+    derivative, evaluate, roots_mod_prime, roots_mod_prime_power, PolyEq, PolyEqSigned,
+};
+
+fn brute_force_roots(coefs: &[u32], modu: u32) -> Vec<u32> {
+    (0..modu)
+        .filter(|&x| evaluate(coefs, x, modu) == 0)
+        .collect()
+}
+
+#[test]
+fn evaluate_and_derivative() {
+    // 2 + 3x + x^3
+    let coefs: [u32; 4] = [2, 3, 0, 1];
+
+    assert_eq!(evaluate(&coefs, 4, 100), 78);
+    assert_eq!(evaluate(&coefs, 4, 7), 1);
+    assert_eq!(evaluate(&[], 4u32, 7), 0);
+    assert_eq!(derivative(&coefs, 100), vec![3, 0, 3]);
+}
+
+#[test]
+fn roots_small_primes_brute_force() {
+    let primes: [u32; 6] = [2, 3, 5, 61, 67, 101];
+    let polys: [[u32; 4]; 6] = [
+        [0, 0, 0, 1],
+        [1, 1, 1, 1],
+        [96, 1, 0, 1],
+        [6, 11, 6, 1],
+        [5, 0, 0, 7],
+        [0, 14, 0, 3],
+    ];
+
+    for &prm in primes.iter() {
+        for coefs in polys.iter() {
+            assert_eq!(
+                roots_mod_prime(coefs, prm),
+                brute_force_roots(coefs, prm),
+                "coefs: {:?}, prm: {}",
+                coefs,
+                prm
+            );
+        }
+    }
+}
+
+#[test]
+fn roots_large_primes() {
+    // (x - 3)(x - 1234567890123)(x - (p - 5)), p = 2^61 - 1
+    let prm = 2_305_843_009_213_693_951u64;
+    let coefs = [
+        18_518_518_351_845,
+        2_305_840_540_077_913_690,
+        2_305_841_774_645_803_830,
+        1,
+    ];
+
+    assert_eq!(
+        roots_mod_prime(&coefs, prm),
+        vec![3, 1_234_567_890_123, prm - 5]
+    );
+
+    // (x - 2)(x - r)(x^2 + 1), p = 2^127 - 1 and x^2 + 1 is irreducible
+    let prm = 170_141_183_460_469_231_731_687_303_715_884_105_727u128;
+    let coefs = [
+        170_141_183_460_469_231_731_687_303_715_884_104_273,
+        725,
+        170_141_183_460_469_231_731_687_303_715_884_104_274,
+        725,
+        1,
+    ];
+
+    assert_eq!(
+        roots_mod_prime(&coefs, prm),
+        vec![2, 170_141_183_460_469_231_731_687_303_715_884_105_000]
+    );
+}
+
+#[test]
+fn roots_prime_powers_brute_force() {
+    let prime_powers: [(u32, u8); 5] = [(2, 5), (3, 4), (5, 3), (7, 2), (67, 2)];
+    let polys: [[u32; 4]; 5] = [
+        [0, 0, 0, 1],
+        [4, 0, 3, 1],
+        [18, 0, 0, 9],
+        [2, 3, 1, 0],
+        [7, 1, 5, 2],
+    ];
+
+    for &(prm, k) in prime_powers.iter() {
+        let modu = prm.pow(k.into());
+
+        for coefs in polys.iter() {
+            assert_eq!(
+                roots_mod_prime_power(coefs, prm, k),
+                brute_force_roots(coefs, modu),
+                "coefs: {:?}, modu: {}",
+                coefs,
+                modu
+            );
+        }
+    }
+}
+
+#[test]
+fn poly_eq_brute_force() {
+    // Coefficients in ascending order and the right-hand side
+    let eqs: [(&[u32], u32); 6] = [
+        (&[0, 0, 0, 0, 1], 1),
+        (&[0, 1, 0, 0, 0, 1], 0),
+        (&[24, 50, 35, 10, 1], 0),
+        (&[3, 0, 2, 0, 0, 0, 5], 7),
+        (&[6, 0, 0, 0, 12], 6),
+        (&[1, 12, 0, 0, 0, 0, 0, 0, 0, 12], 1),
+    ];
+
+    for modu in 2..200u32 {
+        for (coefs, rhs) in eqs.iter() {
+            let poly_eq = PolyEq {
+                coefs: coefs.to_vec(),
+                rhs: *rhs,
+                modu,
+            };
+
+            let corr_sols: Vec<u32> = (0..modu)
+                .filter(|&x| evaluate(coefs, x, modu) == rhs % modu)
+                .collect();
+            let corr_sols = if corr_sols.is_empty() {
+                None
+            } else {
+                Some(corr_sols)
+            };
+
+            assert_eq!(poly_eq.solve(), corr_sols, "{:?}, modu: {}", coefs, modu);
+        }
+    }
+}
+
+#[test]
+fn poly_eq_signed() {
+    // x^5 - x = 0 (mod 25)
+    let poly_eq = PolyEqSigned::<i64, u64> {
+        coefs: vec![0, -1, 0, 0, 0, 1],
+        rhs: 0,
+        modu: 25,
+    };
+
+    assert_eq!(poly_eq.solve(), Some(vec![0, 1, 7, 18, 24]));
+    assert_eq!(poly_eq.sides(-1), Some((0, 0)));
+
+    let poly_eq = PolyEqSigned::<i64, u64> {
+        coefs: vec![i64::MIN, 1],
+        rhs: 0,
+        modu: 25,
+    };
+
+    assert_eq!(poly_eq.solve(), None);
+}
+
+#[test]
+fn poly_eq_vanishing_unknown() {
+    let poly_eq = PolyEq::<u64> {
+        coefs: vec![3, 9, 0, 18],
+        rhs: 12,
+        modu: 9,
+    };
+    assert_eq!(poly_eq.solve(), Some((0..9).collect()));
+
+    let poly_eq = PolyEq::<u64> {
+        coefs: vec![],
+        rhs: 1,
+        modu: 9,
+    };
+    assert_eq!(poly_eq.solve(), None);
+
+    // Too many solutions to be enumerated, also when vanishing modulo a prime power only
+    let poly_eq = PolyEq::<u64> {
+        coefs: vec![0, 0, 0, 0, 1 << 40],
+        rhs: 0,
+        modu: 1 << 40,
+    };
+    assert_eq!(poly_eq.solve(), None);
+
+    let poly_eq = PolyEq::<u64> {
+        coefs: vec![0, 0, 0, 0, 1 << 40],
+        rhs: 0,
+        modu: 3 << 40,
+    };
+    assert_eq!(poly_eq.solve(), None);
+}
+
+#[test]
+fn poly_eq_with_factor_config_timeout() {
+    let config = FactorConfig {
+        timeout: Some(Duration::ZERO),
+        ..FactorConfig::default()
+    };
+
+    let poly_eq = PolyEq::<u128> {
+        coefs: vec![1, 0, 0, 0, 1],
+        rhs: 0,
+        modu: 90_124_258_835_295_998_242_413_094_252_351,
+    };
+
+    assert_eq!(
+        poly_eq.solve_with_config(&config),
+        Err(FactorError::Timeout)
+    );
+}
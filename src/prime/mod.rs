@@ -1,4 +1,4 @@
-//! Primality testing for odd natural numbers.
+//! Primality testing for natural numbers.
 //!
 //! Primality testing is separated in the following manner:
 //! - Run first a small check with first primes up to 61 (smallest prime good for the MR test is 67).
@@ -8,18 +8,187 @@
 //! Baillie-PSW primality test is not deterministic but there are not known counterexamples in the range
 //! this program uses (numbers up to 128 bits).
 //!
-use std::cmp::Ordering;
+use std::cmp::{self, Ordering};
 use std::convert::{Into, TryInto};
+use std::ops::Range;
 
-use num::{integer, PrimInt};
+use num::{integer, NumCast, PrimInt};
+use rand::Rng;
 
 use crate::{
-    arith::{Arith, CoreArith},
-    UInt,
+    arith::{square_mod_mersenne, Arith, Barrett, CoreArith},
+    numtheory, UInt,
 };
 
 struct LucasParams<T: UInt>(T, T, T);
 
+/// Wheel of the primes 2, 3 and 5 used to step between prime candidates.
+const WHEEL_MODULUS: u8 = 30;
+
+/// Residues modulo `WHEEL_MODULUS` coprime to it, in increasing order.
+const WHEEL_RESIDUES: [u8; 8] = [1, 7, 11, 13, 17, 19, 23, 29];
+
+/// Primes up to 7, handled before stepping on the wheel.
+const WHEEL_PRIMES: [u8; 4] = [2, 3, 5, 7];
+
+/// Odd primes used in trial division before the Miller-Rabin test.
+static SMALL_ODD_PRIMES: [u8; 17] = [
+    3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61,
+];
+
+/// Length of the segments sieved at a time by `PrimesInRange`.
+const SIEVE_SEGMENT_LEN: u64 = 1 << 15;
+
+/// Ranges are sieved only if their base primes, the primes up to the square root
+/// of the range end, are at most this large.
+const SIEVE_MAX_BASE_PRIME: u64 = 1 << 20;
+
+/// Iterator over the primes of a half-open range, created by `primes_in_range`.
+///
+/// Dense ranges are sieved segment by segment with the base primes up to the square
+/// root of the range end. Other ranges, being short compared to their square root or
+/// ending beyond 2^40, are enumerated by testing every candidate with `is_prime`.
+pub struct PrimesInRange<T: UInt> {
+    next: T,
+    end: T,
+    base_primes: Option<Vec<u64>>,
+    segment_primes: std::vec::IntoIter<T>,
+}
+
+/// Check whether a natural number `num` is a prime.
+///
+/// Numbers up to 64 bits are tested deterministically by the Miller-Rabin test and
+/// larger numbers by the strong Baillie-PSW test, which has no known counterexamples.
+///
+/// # Examples
+///
+/// ```
+/// use modular_equations::is_prime;
+///
+/// assert!(is_prime::<u8>(2));
+/// assert!(!is_prime::<u32>(561));
+/// assert!(is_prime::<u128>(340_282_366_920_938_463_463_374_607_431_768_211_297));
+/// ```
+pub fn is_prime<T: UInt>(num: T) -> bool {
+    num == 2.into() || is_odd_prime(num)
+}
+
+/// Check whether a natural number `num` is a probable prime by `rounds` Miller-Rabin tests.
+///
+/// Unlike `is_prime`, which uses fixed base sets making the result certain up to 64 bits,
+/// bases of the Miller-Rabin test are drawn randomly from \[2, n - 2\]. A prime always
+/// passes whereas a composite passes with probability at most 4^(-rounds). Trial division
+/// by the primes up to 61 is done first, thus for zero rounds only that remains.
+///
+/// # Examples
+///
+/// ```
+/// use modular_equations::is_probable_prime;
+///
+/// assert!(is_probable_prime::<u64>(1_000_000_007, 10));
+/// assert!(!is_probable_prime::<u64>(1_000_000_007 * 1_000_000_009, 10));
+/// ```
+pub fn is_probable_prime<T: UInt>(num: T, rounds: u32) -> bool {
+    if num < 67.into() || num & T::one() == T::zero() {
+        return is_prime(num);
+    }
+
+    if SMALL_ODD_PRIMES
+        .iter()
+        .any(|&prm| num % prm.into() == T::zero())
+    {
+        return false;
+    }
+
+    let num_u128: u128 = num.into();
+    let mut rng = rand::thread_rng();
+
+    // Bases are smaller than num, hence they fit into the type T
+    let bases: Vec<T> = (0..rounds)
+        .map(|_| <T as NumCast>::from(rng.gen_range(2..num_u128 - 1)).unwrap())
+        .collect();
+
+    is_prime_mr(num, &bases)
+}
+
+/// Check whether the Mersenne number 2^`exp` - 1 is a prime.
+///
+/// Mersenne number can be a prime only if the exponent is a prime. For an odd prime
+/// exponent p the deterministic Lucas-Lehmer test is used: 2^p - 1 is a prime if and only
+/// if s_(p-2) = 0 (mod 2^p - 1), where s_0 = 4 and s_(j+1) = s_j^2 - 2. Squares are reduced
+/// by the Mersenne modulus without divisions. None is returned if 2^exp - 1 doesn't fit
+/// into u128, i.e. the exponent is larger than 128.
+///
+/// # Examples
+///
+/// ```
+/// use modular_equations::is_mersenne_prime;
+///
+/// assert_eq!(is_mersenne_prime(127), Some(true));
+/// assert_eq!(is_mersenne_prime(11), Some(false));
+/// assert_eq!(is_mersenne_prime(521), None);
+/// ```
+pub fn is_mersenne_prime(exp: u32) -> Option<bool> {
+    if exp > u128::BITS {
+        return None;
+    }
+    if exp == 2 {
+        return Some(true);
+    }
+    if !is_odd_prime(exp) {
+        return Some(false);
+    }
+
+    let modu = (1u128 << exp) - 1;
+    let mut s = 4;
+
+    for _ in 0..exp - 2 {
+        s = u128::sub_mod(square_mod_mersenne(s, exp), 2, modu);
+    }
+
+    Some(s == 0)
+}
+
+/// Check whether `num` is a safe prime, i.e. both p and (p - 1) / 2 are primes.
+///
+/// Unit group modulo a safe prime p has no subgroups of small order besides the one
+/// of order two, making discrete logarithms modulo p hard to compute.
+///
+/// # Examples
+///
+/// ```
+/// use modular_equations::is_safe_prime;
+///
+/// assert!(is_safe_prime::<u32>(23));
+/// assert!(!is_safe_prime::<u32>(29));
+/// ```
+pub fn is_safe_prime<T: UInt>(num: T) -> bool {
+    num >= 5.into() && is_prime((num - T::one()).unsigned_shr(1)) && is_odd_prime(num)
+}
+
+/// Check whether `num` is a Sophie Germain prime, i.e. both p and 2p + 1 are primes.
+///
+/// Value 2p + 1 is tested as u128, thus for u128 primes larger than 2^127 it's unknown
+/// and false is returned.
+///
+/// # Examples
+///
+/// ```
+/// use modular_equations::is_sophie_germain;
+///
+/// assert!(is_sophie_germain::<u8>(2));
+/// assert!(is_sophie_germain::<u8>(251));
+/// assert!(!is_sophie_germain::<u32>(13));
+/// ```
+pub fn is_sophie_germain<T: UInt>(num: T) -> bool {
+    let num_u128: u128 = num.into();
+
+    match num_u128.checked_mul(2).and_then(|x| x.checked_add(1)) {
+        Some(safe_cand) => is_odd_prime(safe_cand) && is_prime(num),
+        None => false,
+    }
+}
+
 /// Check whether a positive natural number `num` is an odd prime.
 pub fn is_odd_prime<T: UInt>(num: T) -> bool {
     if num <= T::one() || num & T::one() == T::zero() {
@@ -48,12 +217,363 @@ pub fn is_odd_prime<T: UInt>(num: T) -> bool {
     }
 }
 
-fn is_sure_odd_small_prime<T: UInt>(num: T) -> bool {
-    static PRIMES: [u8; 17] = [
-        3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61,
-    ];
+/// Smallest prime strictly larger than `num`.
+///
+/// Candidates coprime to 30 are tested by `is_odd_prime`. None is returned if there
+/// isn't a larger prime representable by the type.
+///
+/// # Examples
+///
+/// ```
+/// use modular_equations::next_prime;
+///
+/// assert_eq!(next_prime::<u32>(1_000_000_000), Some(1_000_000_007));
+/// assert_eq!(next_prime::<u8>(251), None);
+/// ```
+pub fn next_prime<T: UInt>(num: T) -> Option<T> {
+    if let Some(&prm) = WHEEL_PRIMES.iter().find(|&&prm| num < prm.into()) {
+        return Some(prm.into());
+    }
+
+    let mut cand = next_wheel_candidate(num)?;
+
+    while !is_odd_prime(cand) {
+        cand = next_wheel_candidate(cand)?;
+    }
+
+    Some(cand)
+}
+
+/// Largest prime strictly smaller than `num`.
+///
+/// Candidates coprime to 30 are tested by `is_odd_prime`. None is returned if
+/// `num` is at most two.
+///
+/// # Examples
+///
+/// ```
+/// use modular_equations::prev_prime;
+///
+/// assert_eq!(prev_prime::<u64>(1 << 61), Some((1 << 61) - 1));
+/// assert_eq!(prev_prime::<u8>(2), None);
+/// ```
+pub fn prev_prime<T: UInt>(num: T) -> Option<T> {
+    if num <= 11.into() {
+        return WHEEL_PRIMES
+            .iter()
+            .rev()
+            .find(|&&prm| num > prm.into())
+            .map(|&prm| prm.into());
+    }
+
+    // Candidates stop at the prime 11 at the latest
+    let mut cand = prev_wheel_candidate(num);
+
+    while !is_odd_prime(cand) {
+        cand = prev_wheel_candidate(cand);
+    }
 
-    for prime in PRIMES.iter() {
+    Some(cand)
+}
+
+/// Primes of the half-open `range` in increasing order.
+///
+/// Returned iterator sieves the range segment by segment when it's dense enough
+/// and otherwise tests each number of the range by `is_prime`.
+///
+/// # Examples
+///
+/// ```
+/// use modular_equations::primes_in_range;
+///
+/// assert_eq!(primes_in_range::<u32>(90..110).collect::<Vec<_>>(), vec![97, 101, 103, 107, 109]);
+/// assert_eq!(primes_in_range::<u128>(u128::MAX - 200..u128::MAX).count(), 2);
+/// ```
+pub fn primes_in_range<T: UInt>(range: Range<T>) -> PrimesInRange<T> {
+    let (start, end) = (range.start, cmp::max(range.start, range.end));
+
+    let base_primes = if end <= start {
+        None
+    } else {
+        // Sieving pays off if the range is at least as long as there are base prime candidates
+        match ((end - T::one()).sqrt().to_u64(), (end - start).to_u64()) {
+            (Some(sqrt_end), Some(len)) if sqrt_end <= SIEVE_MAX_BASE_PRIME && len >= sqrt_end => {
+                Some(sieve_primes(sqrt_end))
+            }
+            _ => None,
+        }
+    };
+
+    PrimesInRange {
+        next: start,
+        end,
+        base_primes,
+        segment_primes: vec![].into_iter(),
+    }
+}
+
+impl<T: UInt> PrimesInRange<T> {
+    /// Sieve the next segment of the range by the base primes.
+    fn sieve_next_segment(&mut self, base_primes: &[u64]) -> Vec<T> {
+        // Sieved ranges end below 2^40, hence the numbers fit into u64
+        let seg_start = self.next.to_u64().unwrap();
+        let seg_end = cmp::min(seg_start + SIEVE_SEGMENT_LEN, self.end.to_u64().unwrap());
+
+        let mut is_prime = vec![true; (seg_end - seg_start) as usize];
+
+        for num in seg_start..cmp::min(seg_end, 2) {
+            is_prime[(num - seg_start) as usize] = false;
+        }
+
+        for &prm in base_primes.iter() {
+            let first_multiple = cmp::max(prm * prm, seg_start.div_ceil(prm) * prm);
+
+            for multiple in (first_multiple..seg_end).step_by(prm as usize) {
+                is_prime[(multiple - seg_start) as usize] = false;
+            }
+        }
+
+        self.next = <T as NumCast>::from(seg_end).unwrap();
+
+        (seg_start..seg_end)
+            .filter(|&num| is_prime[(num - seg_start) as usize])
+            .map(|num| <T as NumCast>::from(num).unwrap())
+            .collect()
+    }
+}
+
+impl<T: UInt> Iterator for PrimesInRange<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        loop {
+            if let Some(prm) = self.segment_primes.next() {
+                return Some(prm);
+            }
+
+            if self.next >= self.end {
+                return None;
+            }
+
+            match self.base_primes.take() {
+                Some(base_primes) => {
+                    self.segment_primes = self.sieve_next_segment(&base_primes).into_iter();
+                    self.base_primes = Some(base_primes);
+                }
+                None => {
+                    while self.next < self.end {
+                        let cand = self.next;
+                        self.next = self.next + T::one();
+
+                        if is_prime(cand) {
+                            return Some(cand);
+                        }
+                    }
+
+                    return None;
+                }
+            }
+        }
+    }
+}
+
+/// Primes up to `limit` by the sieve of Eratosthenes.
+fn sieve_primes(limit: u64) -> Vec<u64> {
+    let mut is_prime = vec![true; limit as usize + 1];
+
+    for num in 2..=limit {
+        if num * num > limit {
+            break;
+        }
+        if is_prime[num as usize] {
+            for multiple in (num * num..=limit).step_by(num as usize) {
+                is_prime[multiple as usize] = false;
+            }
+        }
+    }
+
+    (2..=limit).filter(|&num| is_prime[num as usize]).collect()
+}
+
+/// Smallest number larger than `num` and coprime to 30, or None on overflow.
+fn next_wheel_candidate<T: UInt>(num: T) -> Option<T> {
+    let wheel: T = WHEEL_MODULUS.into();
+    let block = num - num % wheel;
+
+    match WHEEL_RESIDUES.iter().find(|&&r| num % wheel < r.into()) {
+        Some(&r) => block.checked_add(&r.into()),
+        None => block.checked_add(&(WHEEL_MODULUS + WHEEL_RESIDUES[0]).into()),
+    }
+}
+
+/// Largest number smaller than `num` and coprime to 30, `num` being larger than one.
+fn prev_wheel_candidate<T: UInt>(num: T) -> T {
+    let wheel: T = WHEEL_MODULUS.into();
+    let block = num - num % wheel;
+
+    match WHEEL_RESIDUES
+        .iter()
+        .rev()
+        .find(|&&r| num % wheel > r.into())
+    {
+        Some(&r) => block + r.into(),
+        None => block - (WHEEL_MODULUS - WHEEL_RESIDUES[7]).into(),
+    }
+}
+
+/// Lucas sequences U_k(P, Q) and V_k(P, Q) modulo `modu` for `k`.
+///
+/// Sequences are defined by U_0 = 0, U_1 = 1, V_0 = 2, V_1 = P and the recurrence
+/// X_(j+2) = P * X_(j+1) - Q * X_j. Parameters are residues modulo `modu`, negative
+/// ones given by their representatives, e.g. Q = -1 as modu - 1. Pair (U_k, V_k) is
+/// computed by doubling in O(log k) steps without divisions, thus the modulo needs
+/// not be odd or coprime to the parameters. None is returned if the modulo is zero.
+///
+/// # Examples
+///
+/// ```
+/// use modular_equations::lucas_sequence;
+///
+/// // P = 1 and Q = -1 give the Fibonacci and Lucas numbers, F_10 = 55 and L_10 = 123
+/// assert_eq!(lucas_sequence::<u32>(1, 999, 10, 1_000), Some((55, 123)));
+/// assert_eq!(lucas_sequence::<u32>(1, 99, 10, 100), Some((55, 23)));
+/// ```
+pub fn lucas_sequence<T: UInt>(p: T, q: T, k: T, modu: T) -> Option<(T, T)> {
+    if modu == T::zero() {
+        return None;
+    }
+
+    let p = p % modu;
+    let (u, u_next) = lucas_u_pair(p, q % modu, k, modu);
+
+    // V_k = 2U_(k+1) - P * U_k
+    let v = T::sub_mod(
+        T::add_mod(u_next, u_next, modu),
+        T::mult_mod(p, u, modu),
+        modu,
+    );
+
+    Some((u, v))
+}
+
+/// Fibonacci number F_`k` modulo `modu`.
+///
+/// Fibonacci numbers are the Lucas sequence U_k(1, -1), hence F_k is computed by
+/// doubling in O(log k) steps. None is returned if the modulo is zero.
+///
+/// # Examples
+///
+/// ```
+/// use modular_equations::fibonacci_mod;
+///
+/// assert_eq!(fibonacci_mod::<u32>(10, 1_000), Some(55));
+/// assert_eq!(fibonacci_mod::<u64>(1_000_000, 1_000_000_007), Some(918_091_266));
+/// ```
+pub fn fibonacci_mod<T: UInt>(k: T, modu: T) -> Option<T> {
+    if modu == T::zero() {
+        return None;
+    }
+
+    Some(lucas_u_pair(T::one() % modu, modu - T::one(), k, modu).0)
+}
+
+/// Pisano period π(n), the period of the Fibonacci numbers modulo `modu`.
+///
+/// Period is the lcm of the periods π(p^k) of the prime power factors of n. Period π(p)
+/// of a prime p divides p - 1 if p = ±1 (mod 10) and 2(p + 1) if p = ±3 (mod 10), π(2) = 3
+/// and π(5) = 20, and π(p^k) divides p^(k-1) * π(p). Each period is found as the order of
+/// the pair (F_0, F_1) = (0, 1) by dividing out the prime factors of such multiple.
+/// None is returned if the modulo is zero or the period doesn't fit into the type.
+///
+/// # Examples
+///
+/// ```
+/// use modular_equations::pisano_period;
+///
+/// assert_eq!(pisano_period::<u32>(10), Some(60));
+/// assert_eq!(pisano_period::<u32>(1_000), Some(1_500));
+/// ```
+pub fn pisano_period<T: 'static + UInt>(modu: T) -> Option<T> {
+    if modu <= T::one() {
+        return if modu == T::one() {
+            Some(T::one())
+        } else {
+            None
+        };
+    }
+
+    let mut period = T::one();
+
+    for (prm, k) in numtheory::prime_factor_repr(modu).into_iter() {
+        let prm_period = match prm.to_u8() {
+            Some(2) => 3.into(),
+            Some(5) => 20.into(),
+            _ if matches!((prm % 10.into()).to_u8(), Some(1) | Some(9)) => prm - T::one(),
+            _ => (prm + T::one()).checked_mul(&2.into())?,
+        };
+        let prm_period = fibonacci_period_dividing(prm_period, prm);
+
+        let prm_power_period = prm.pow((k - 1).into()).checked_mul(&prm_period)?;
+        let prm_power_period = fibonacci_period_dividing(prm_power_period, prm.pow(k.into()));
+
+        period = (period / T::gcd_mod(period, prm_power_period)).checked_mul(&prm_power_period)?;
+    }
+
+    Some(period)
+}
+
+/// Pisano period modulo `modu` > 1, given its multiple `period_multiple`.
+fn fibonacci_period_dividing<T: 'static + UInt>(period_multiple: T, modu: T) -> T {
+    let mut period = period_multiple;
+
+    for (q, _) in numtheory::prime_factor_repr(period_multiple).into_iter() {
+        while period % q == T::zero()
+            && lucas_u_pair(T::one(), modu - T::one(), period / q, modu) == (T::zero(), T::one())
+        {
+            period = period / q;
+        }
+    }
+
+    period
+}
+
+/// Pair (U_k, U_(k+1)) of the Lucas sequence U(P, Q) modulo `modu`, `p` and `q` being reduced.
+fn lucas_u_pair<T: UInt>(p: T, q: T, k: T, modu: T) -> (T, T) {
+    // (U_j, U_(j+1)) for the leading bits j of k
+    let (mut u, mut u_next) = (T::zero(), T::one() % modu);
+
+    for bit in (0..T::zero().count_zeros() - k.leading_zeros()).rev() {
+        // U_2j = U_j * (2U_(j+1) - P * U_j), U_(2j+1) = U_(j+1)^2 - Q * U_j^2
+        let v = T::sub_mod(
+            T::add_mod(u_next, u_next, modu),
+            T::mult_mod(p, u, modu),
+            modu,
+        );
+        let u_double = T::mult_mod(u, v, modu);
+        let u_double_next = T::sub_mod(
+            T::mult_mod(u_next, u_next, modu),
+            T::mult_mod(q, T::mult_mod(u, u, modu), modu),
+            modu,
+        );
+
+        if (k >> bit as usize) & T::one() == T::one() {
+            u = u_double_next;
+            u_next = T::sub_mod(
+                T::mult_mod(p, u_double_next, modu),
+                T::mult_mod(q, u_double, modu),
+                modu,
+            );
+        } else {
+            u = u_double;
+            u_next = u_double_next;
+        }
+    }
+
+    (u, u_next)
+}
+
+fn is_sure_odd_small_prime<T: UInt>(num: T) -> bool {
+    for prime in SMALL_ODD_PRIMES.iter() {
         let prm = (*prime).into();
 
         if prm > num / prm {
@@ -75,15 +595,17 @@ fn is_prime_mr<T: UInt>(num: T, bases: &[T]) -> bool {
     let num_odd = num_even.unsigned_shr(pow);
     // num_even = 2^pow * num_odd
 
+    let barrett = Barrett::new(num);
+
     'base: for base in bases.iter() {
-        let mut q = T::exp_mod(*base, num_odd, num);
+        let mut q = barrett.exp_mod(*base, num_odd);
 
         if q == T::one() || q == num_even {
             continue;
         }
 
         for _ in 1..pow {
-            q = T::mult_mod_unsafe(q, q, num);
+            q = barrett.mult_mod(q, q);
 
             if q == num_even {
                 continue 'base;
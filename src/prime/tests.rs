@@ -1,4 +1,7 @@
-use crate::prime::is_odd_prime;
+use crate::prime::{
+    fibonacci_mod, is_mersenne_prime, is_odd_prime, is_prime, is_probable_prime, is_safe_prime,
+    is_sophie_germain, lucas_sequence, next_prime, pisano_period, prev_prime, primes_in_range,
+};
 
 #[test]
 fn is_prime_first_odd_primes() {
@@ -151,7 +154,7 @@ fn is_prime_strong_bpsw_primes() {
 
 #[test]
 fn is_prime_strong_bpsw_larger_primes() {
-    let test_primes: [u128; 21] = [
+    let test_primes: [u128; 22] = [
         41_538_374_868_278_621_028_243_970_633_760_399,
         41_538_374_868_278_621_028_243_970_633_760_057,
         166_153_499_473_114_484_112_975_882_535_042_517,
@@ -169,6 +172,7 @@ fn is_prime_strong_bpsw_larger_primes() {
         170_141_183_460_469_231_731_687_303_715_884_105_419,
         170_141_183_460_469_231_731_687_303_715_884_104_993,
         340_282_366_920_938_463_463_374_607_431_768_210_659,
+        340_282_366_920_938_463_463_374_607_431_768_211_181,
         340_282_366_920_938_463_463_374_607_431_768_211_219,
         340_282_366_920_938_463_463_374_607_431_768_211_223,
         340_282_366_920_938_463_463_374_607_431_768_211_283,
@@ -256,3 +260,261 @@ fn is_prime_range_containing_no_primes() {
 
     assert_eq!(prime_count, 0);
 }
+
+#[test]
+fn is_prime_even_and_odd() {
+    let corr_primes: [u16; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+
+    let primes: Vec<u16> = (0..40).filter(|&x| is_prime(x)).collect();
+    assert_eq!(primes, corr_primes.to_vec());
+
+    assert!(!is_prime(u64::MAX - 1));
+    assert!(is_prime(u64::MAX - 58));
+    assert!(!is_prime(u128::MAX - 1));
+}
+
+#[test]
+fn next_and_prev_prime_small_numbers() {
+    let primes: Vec<u16> = (0..2_000).filter(|&x| is_prime(x)).collect();
+
+    for num in 0..1_990u16 {
+        let next = primes.iter().find(|&&prm| prm > num).copied();
+        let prev = primes.iter().rev().find(|&&prm| prm < num).copied();
+
+        assert_eq!(next_prime(num), next, "num: {}", num);
+        assert_eq!(prev_prime(num), prev, "num: {}", num);
+    }
+}
+
+#[test]
+fn next_and_prev_prime_type_bounds() {
+    assert_eq!(next_prime(u8::MAX - 4), None);
+    assert_eq!(next_prime(250u8), Some(251));
+    assert_eq!(prev_prime(u8::MAX), Some(251));
+
+    assert_eq!(next_prime(u64::MAX - 58), None);
+    assert_eq!(prev_prime(u64::MAX), Some(u64::MAX - 58));
+
+    assert_eq!(next_prime(u128::MAX - 158), None);
+    assert_eq!(prev_prime(u128::MAX), Some(u128::MAX - 158));
+    assert_eq!(
+        next_prime(u128::pow(2, 107) - 170),
+        Some(u128::pow(2, 107) - 1)
+    );
+}
+
+#[test]
+fn primes_in_range_matches_primality_test() {
+    // (start, end), both sieved and tested ranges
+    let test_cases: [(u64, u64); 8] = [
+        (0, 0),
+        (0, 2),
+        (0, 100_000),
+        (1, 3),
+        (99_990, 100_000),
+        (10_000_000_000, 10_000_200_000),
+        (1_000_000_000_000 - 1_000, 1_000_000_000_000),
+        (1 << 40, (1 << 40) + 3_000_000),
+    ];
+
+    for (start, end) in test_cases.iter() {
+        let primes: Vec<u64> = primes_in_range(*start..*end).collect();
+        let corr_primes: Vec<u64> = (*start..*end).filter(|&x| is_prime(x)).collect();
+
+        assert_eq!(primes, corr_primes, "start: {}, end: {}", start, end);
+    }
+
+    let (start, end) = (10u64, 5u64);
+    assert_eq!(primes_in_range(start..end).count(), 0);
+}
+
+#[test]
+fn primes_in_range_type_bounds() {
+    assert_eq!(
+        primes_in_range(240u8..u8::MAX).collect::<Vec<u8>>(),
+        vec![241, 251]
+    );
+    assert_eq!(primes_in_range(0u16..u16::MAX).count(), 6_542);
+    assert_eq!(
+        primes_in_range(u128::MAX - 200..u128::MAX).collect::<Vec<u128>>(),
+        vec![u128::MAX - 172, u128::MAX - 158]
+    );
+}
+
+#[test]
+fn safe_and_sophie_germain_primes_small_numbers() {
+    let corr_sophie_germain: [u32; 10] = [2, 3, 5, 11, 23, 29, 41, 53, 83, 89];
+    let corr_safe: Vec<u32> = corr_sophie_germain.iter().map(|&p| 2 * p + 1).collect();
+
+    let sophie_germain: Vec<u32> = (0..100).filter(|&x| is_sophie_germain(x)).collect();
+    let safe: Vec<u32> = (0..180).filter(|&x| is_safe_prime(x)).collect();
+
+    assert_eq!(sophie_germain, corr_sophie_germain.to_vec());
+    assert_eq!(safe, corr_safe);
+}
+
+#[test]
+fn safe_and_sophie_germain_primes_large_type() {
+    // 2^127 - 1 is a prime but 2^128 - 1 isn't
+    assert!(!is_sophie_germain(u128::MAX / 2));
+    assert!(!is_safe_prime(u128::MAX));
+    // Largest u128 prime exceeds 2^127, hence 2p + 1 isn't representable
+    assert!(!is_sophie_germain(u128::MAX - 158));
+    // Sophie Germain prime of u8 whose 2p + 1 exceeds the type
+    assert!(is_sophie_germain(251u8));
+    assert!(is_safe_prime(503u16));
+}
+
+#[test]
+fn probable_primes_match_primality_test() {
+    for num in 0..5_000u32 {
+        assert_eq!(is_probable_prime(num, 20), is_prime(num), "num: {}", num);
+    }
+
+    // Carmichael numbers and strong pseudoprimes to base 2
+    let composites: [u64; 6] = [
+        561,
+        1_105,
+        41_041,
+        2_047,
+        3_215_031_751,
+        3_825_123_056_546_413_051,
+    ];
+
+    for comp in composites.iter() {
+        assert!(!is_probable_prime(*comp, 20), "{}", comp);
+    }
+
+    assert!(is_probable_prime(u64::MAX - 58, 20));
+    assert!(is_probable_prime(u128::MAX - 158, 20));
+    assert!(!is_probable_prime(u128::MAX - 1, 20));
+}
+
+#[test]
+fn probable_primes_zero_rounds() {
+    assert!(is_probable_prime(61u32, 0));
+    assert!(!is_probable_prime(67u32 * 3, 0));
+    // Without the Miller-Rabin rounds only the trial division remains
+    assert!(is_probable_prime(67u32 * 71, 0));
+}
+
+#[test]
+fn lucas_sequence_matches_recurrence() {
+    for modu in 1..40u32 {
+        for p in 0..modu {
+            for q in 0..modu {
+                let (mut u, mut u_next) = (0, 1 % modu);
+                let (mut v, mut v_next) = (2 % modu, p);
+
+                for k in 0..50 {
+                    assert_eq!(
+                        lucas_sequence(p, q, k, modu),
+                        Some((u, v)),
+                        "p: {}, q: {}, k: {}, modu: {}",
+                        p,
+                        q,
+                        k,
+                        modu
+                    );
+
+                    let u_next_next = (p * u_next + (modu - q) * u) % modu;
+                    let v_next_next = (p * v_next + (modu - q) * v) % modu;
+
+                    (u, u_next) = (u_next, u_next_next);
+                    (v, v_next) = (v_next, v_next_next);
+                }
+            }
+        }
+    }
+
+    assert_eq!(lucas_sequence(1u32, 1, 5, 0), None);
+}
+
+#[test]
+fn lucas_sequence_large_type() {
+    // U_(p+1) = 0 (mod p) for a prime p if the discriminant P^2 - 4Q is a non-residue
+    let prm = u128::MAX - 158;
+    let (p, q) = (1, prm - 1);
+
+    assert_eq!(lucas_sequence(p, q, prm + 1, prm).map(|(u, _)| u), Some(0));
+    // F_186 is the largest Fibonacci number fitting into u128, L_186 exceeds it
+    assert_eq!(
+        lucas_sequence(1, u128::MAX - 1, 186, u128::MAX),
+        Some((
+            332_825_110_087_067_562_321_196_029_789_634_457_848,
+            63_654_836_931_657_091_742_894_317_532_791_180_308
+        ))
+    );
+}
+
+#[test]
+fn fibonacci_mod_small_moduli() {
+    for modu in 1..100u32 {
+        let (mut fib, mut fib_next) = (0, 1 % modu);
+
+        for k in 0..300 {
+            assert_eq!(
+                fibonacci_mod(k, modu),
+                Some(fib),
+                "k: {}, modu: {}",
+                k,
+                modu
+            );
+            (fib, fib_next) = (fib_next, (fib + fib_next) % modu);
+        }
+    }
+
+    assert_eq!(fibonacci_mod(5u32, 0), None);
+    assert_eq!(
+        fibonacci_mod(186u128, u128::MAX),
+        Some(332_825_110_087_067_562_321_196_029_789_634_457_848)
+    );
+}
+
+#[test]
+fn pisano_period_brute_force_small_moduli() {
+    for modu in 1..2_000u32 {
+        let (mut fib, mut fib_next, mut period) = (1 % modu, 1 % modu, 1);
+
+        while (fib, fib_next) != (0, 1 % modu) {
+            (fib, fib_next) = (fib_next, (fib + fib_next) % modu);
+            period += 1;
+        }
+
+        assert_eq!(pisano_period(modu), Some(period), "modu: {}", modu);
+    }
+
+    assert_eq!(pisano_period(0u32), None);
+}
+
+#[test]
+fn pisano_period_large_type() {
+    // 1_000_000_007 = 7 (mod 10), hence the period divides 2(p + 1)
+    assert_eq!(pisano_period(1_000_000_007u64), Some(2_000_000_016));
+    assert_eq!(pisano_period(10u64.pow(18)), Some(15 * 10u64.pow(17)));
+    // π(250) = 1500 doesn't fit into u8
+    assert_eq!(pisano_period(250u8), None);
+}
+
+#[test]
+fn mersenne_primes_by_lucas_lehmer() {
+    let corr_exps: [u32; 12] = [2, 3, 5, 7, 13, 17, 19, 31, 61, 89, 107, 127];
+
+    let exps: Vec<u32> = (0..=128)
+        .filter(|&exp| is_mersenne_prime(exp) == Some(true))
+        .collect();
+
+    assert_eq!(exps, corr_exps.to_vec());
+
+    for exp in 2..=127 {
+        let mersenne = (1u128 << exp) - 1;
+        assert_eq!(
+            is_mersenne_prime(exp),
+            Some(is_prime(mersenne)),
+            "exp: {}",
+            exp
+        );
+    }
+
+    assert_eq!(is_mersenne_prime(129), None);
+}
@@ -2,31 +2,49 @@
 //!
 //! Modular quadratic equations are of the form ax^2 + bx + c = d (mod n) where
 //! every coefficient or term is a residue class \[*\] belonging to the ring of
-//! integers Z/nZ. Modulo `n` must be a positive integer and strictly larger than one.
+//! integers Z/nZ. Modulo `n` must be a positive integer. Modulo one is trivial,
+//! every integer being congruent to 0, and [0] is its only solution.
 //!
 //! Solutions x, if any, are given as residue classes \[x\] such that
 //! each class is represented by smallest nonnegative integer (modulo n).
 //!
 use crate::{
     arith::{Arith, CoreArith, SignCast},
-    factor::Factors,
-    lin::LinEq,
-    prime,
+    crt,
+    factor::{FactorConfig, FactorError, Factors},
+    lin::{self, BuildError, Coef, LinEq, SolveError},
+    padic, poly, prime,
     utils::{largest_common_dividing_power_of_two, make_index_combinations},
-    Int, UInt,
+    Int, ModularEquation, UInt,
 };
 
-use num::{integer, iter};
-use std::collections::HashSet;
+use num::{iter, NumCast};
+use smallvec::{smallvec, SmallVec};
+use std::collections::{HashMap, HashSet};
+use std::sync::{Mutex, OnceLock};
+use std::thread;
+
+/// Sub-equations modulo prime powers are solved in parallel if at least
+/// two of the primes are at least this large.
+const PARALLEL_SOLVE_MIN_PRIME: u128 = 1 << 32;
+
+/// Count of prime moduli whose quadratic non-residue is kept in the cache of `non_residue`.
+const NON_RESIDUE_CACHE_CAPACITY: usize = 256;
+
+/// Solutions of a quadratic equation, up to four of them stored without heap allocation.
+///
+/// Most quadratic equations have at most four solutions, e.g. for an odd prime modulo
+/// there are zero to two solutions.
+pub type Solutions<T> = SmallVec<[T; 4]>;
 
 /// Type for quadratic equations with unsigned terms only.
 ///
 /// Quadratic modular equations are of the form ax^2 + bx + c = d (mod modu) where
 /// coefficients `a`, `b`, `c` and `d` must be nonnegative for this type. Furthermore,
 /// the modulo term `modu` must have the same unsigned type as the other terms
-/// and strictly larger than one as its value.
+/// and positive as its value.
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct QuadEq<T: UInt> {
     pub a: T,
     pub b: T,
@@ -39,11 +57,11 @@ pub struct QuadEq<T: UInt> {
 ///
 /// Quadratic modular equations are of the form ax^2 + bx + c = d (mod n) where
 /// coefficient `a`, `b`, `c` and `d` are signed for this type. Modulo `modu` must be
-/// an unsigned type but compatible to the signed type (same byte count), e.g.
-/// unsigned type u32 would be accepted if the signed type is i32. The modulo
-/// n must be strictly larger than one as its value.
+/// an unsigned type at least as wide as the signed type, e.g. unsigned types u32,
+/// u64 and u128 would be accepted if the signed type is i32. The modulo n must be
+/// positive as its value.
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct QuadEqSigned<S: Int, T: UInt> {
     pub a: S,
     pub b: S,
@@ -52,6 +70,46 @@ pub struct QuadEqSigned<S: Int, T: UInt> {
     pub modu: T,
 }
 
+/// Builder for quadratic equations, created by `QuadEq::builder`.
+///
+/// Coefficients may be given as unsigned or signed values and they default to zero.
+/// Method `build` validates the equation and reduces every coefficient to the smallest
+/// nonnegative representative of its residue class.
+#[derive(Debug, Clone, Copy)]
+pub struct QuadEqBuilder<T: UInt> {
+    a: Coef<T>,
+    b: Coef<T>,
+    c: Coef<T>,
+    d: Coef<T>,
+    modu: Option<T>,
+    error: Option<BuildError>,
+}
+
+/// Solutions of a quadratic equation stored modulo the prime power factors of the modulo.
+///
+/// Solutions modulo n = m_1 * ... * m_k, m_i being prime powers, are the combinations of
+/// the roots modulo every m_i. Instead of the cross product of the roots, only the roots
+/// and the CRT basis e_i (e_i = 1 (mod m_i) and e_i = 0 (mod m_j) for j != i) are stored,
+/// the solution for roots r_i being r_1 * e_1 + ... + r_k * e_k (mod n).
+///
+/// Solutions are indexed in the mixed radix order of the root indices, the roots
+/// modulo m_1 changing fastest, and hence they aren't in increasing order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SolutionSet<T: UInt> {
+    modu: T,
+    moduli: Vec<T>,
+    roots: Vec<Vec<T>>,
+    basis: Vec<T>,
+}
+
+/// Iterator over the solutions of `SolutionSet`, created by its `iter` method.
+#[derive(Debug, Clone)]
+pub struct SolutionSetIter<'a, T: UInt> {
+    set: &'a SolutionSet<T>,
+    index: usize,
+    len: usize,
+}
+
 impl<T: 'static + UInt> QuadEq<T> {
     /// Solve quadratic modular equation ax^2 + bx + c = d (mod modu).
     ///
@@ -63,8 +121,9 @@ impl<T: 'static + UInt> QuadEq<T> {
     /// Chinese remainder theorem.
     ///
     /// If a % modu == 0 (0 is the smallest nonnegative representative of \[a\]) and
-    /// also b % modu == 0, there are no solutions since the variable x vanishes
-    /// from the equation.
+    /// also b % modu == 0, the variable x vanishes from the equation. Then every residue
+    /// class is a solution if c = d (mod modu) and otherwise there are no solutions. As for
+    /// `LinEq`, every residue class is enumerated only for moduli up to 2^20.
     ///
     /// If there aren't solutions, None is returned.
     ///
@@ -95,24 +154,559 @@ impl<T: 'static + UInt> QuadEq<T> {
     /// assert_eq!(quad_eq.solve(), None);
     /// ```
     pub fn solve(&self) -> Option<Vec<T>> {
-        if self.modu <= T::one() {
+        // Default configuration has no timeout, hence factorization cannot fail
+        self.solve_with_config(&FactorConfig::default())
+            .unwrap_or(None)
+    }
+
+    /// Solve quadratic modular equation with factorization limits `config`.
+    ///
+    /// Factorization of a composite modulo is the only potentially slow step of
+    /// the solver. If it doesn't complete within the timeout of the configuration,
+    /// `FactorError::Timeout` is returned and if its cancel token gets cancelled,
+    /// `FactorError::Cancelled` is returned. Otherwise the result equals that of `solve`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use modular_equations::{FactorConfig, QuadEq};
+    ///
+    /// let config = FactorConfig {
+    ///     timeout: Some(Duration::from_secs(10)),
+    ///     ..FactorConfig::default()
+    /// };
+    /// let quad_eq = QuadEq::<u64> {a: 1, b: 1, c: 1, d: 0, modu: 1_000_000_007 * 91};
+    ///
+    /// assert_eq!(quad_eq.solve_with_config(&config), Ok(quad_eq.solve()));
+    /// ```
+    pub fn solve_with_config(&self, config: &FactorConfig) -> Result<Option<Vec<T>>, FactorError> {
+        Ok(self
+            .solve_with_config_small(config)?
+            .map(|sols| sols.into_vec()))
+    }
+
+    /// Solve quadratic modular equation using known prime factorization of the modulo.
+    ///
+    /// Factorization `factors` is given as pairs (p_i, k_i) of distinct primes and their
+    /// exponents s.t. modu = p_1^k_1 * ... * p_n^k_n, in any order. This skips the
+    /// factorization step of `solve`, which may be slow for large moduli. Modulo one has
+    /// the empty factorization.
+    ///
+    /// If `factors` isn't the prime factorization of the modulo or there aren't solutions,
+    /// None is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use modular_equations::QuadEq;
+    ///
+    /// let (p, q) = (4_294_967_291u64, 4_294_967_279u64);
+    /// let quad_eq = QuadEq::<u64> {a: 1, b: 0, c: 0, d: 4, modu: p * q};
+    ///
+    /// assert_eq!(quad_eq.solve_with_factors(&[(p, 1), (q, 1)]), quad_eq.solve());
+    /// assert_eq!(quad_eq.solve_with_factors(&[(p, 1)]), None);
+    /// ```
+    pub fn solve_with_factors(&self, factors: &[(T, u8)]) -> Option<Vec<T>> {
+        let mut prm_factor_repr = factors.to_vec();
+        prm_factor_repr.sort_unstable();
+
+        if !is_prime_factor_repr(&prm_factor_repr, self.modu) {
             return None;
         }
 
-        let a_is_zero = self.a % self.modu == T::zero();
+        // Factorization is given, hence solving cannot fail
+        self.solve_with_factorizer(|_| Ok(prm_factor_repr))
+            .unwrap_or(None)
+            .map(|sols| sols.into_vec())
+    }
 
-        if a_is_zero && self.b % self.modu == T::zero() {
+    /// Solve quadratic modular equation as `solve` but tell why there aren't solutions.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use modular_equations::{QuadEq, SolveError};
+    ///
+    /// let quad_eq = QuadEq::<u8> {a: 1, b: 0, c: 0, d: 3, modu: 17};
+    /// assert_eq!(quad_eq.try_solve(), Err(SolveError::NoSolution));
+    ///
+    /// let quad_eq = QuadEq::<u8> {a: 1, b: 0, c: 0, d: 3, modu: 0};
+    /// assert_eq!(quad_eq.try_solve(), Err(SolveError::InvalidModulus));
+    /// ```
+    pub fn try_solve(&self) -> Result<Vec<T>, SolveError> {
+        if self.modu == T::zero() {
+            return Err(SolveError::InvalidModulus);
+        }
+        if self.a % self.modu == T::zero() {
+            // Equation is linear bx + c = d (mod modu)
+            let lin_eq = LinEq {
+                a: self.b,
+                b: self.c,
+                c: self.d,
+                modu: self.modu,
+            };
+            return lin_eq.try_solve();
+        }
+
+        self.solve().ok_or(SolveError::NoSolution)
+    }
+
+    /// Solve quadratic modular equation as `solve` but return the solutions as `Solutions`.
+    ///
+    /// Up to four solutions are stored inline and thus solving an equation with an odd
+    /// prime modulo doesn't allocate. For a composite modulo, factorization of the modulo
+    /// still allocates.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use modular_equations::QuadEq;
+    ///
+    /// let quad_eq = QuadEq::<u32> {a: 1, b: 0, c: 0, d: 2, modu: 7};
+    ///
+    /// match quad_eq.solve_small() {
+    ///     Some(x) => assert_eq!(x.as_slice(), &[3, 4]),
+    ///     None => panic!("no solutions"),
+    /// }
+    /// ```
+    pub fn solve_small(&self) -> Option<Solutions<T>> {
+        // Default configuration has no timeout, hence factorization cannot fail
+        self.solve_with_config_small(&FactorConfig::default())
+            .unwrap_or(None)
+    }
+
+    /// Solve quadratic modular equation as `solve` but return the solutions as `SolutionSet`.
+    ///
+    /// Roots modulo every prime power factor of the modulo are found as in `solve` but
+    /// they aren't combined, hence the memory usage is linear in the number of roots
+    /// modulo the prime powers even if the total count of solutions is exponential.
+    ///
+    /// If there aren't solutions, None is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use modular_equations::QuadEq;
+    ///
+    /// let quad_eq = QuadEq::<u128> {a: 1, b: 0, c: 0, d: 1, modu: u128::MAX};
+    ///
+    /// match quad_eq.solve_set() {
+    ///     Some(sols) => {
+    ///         assert_eq!(sols.len(), 512);
+    ///         assert!(sols.contains(u128::MAX - 1));
+    ///
+    ///         let mut x: Vec<u128> = sols.iter().collect();
+    ///         x.sort();
+    ///         assert_eq!(Some(x), quad_eq.solve());
+    ///     }
+    ///     None => panic!("no solutions"),
+    /// }
+    /// ```
+    pub fn solve_set(&self) -> Option<SolutionSet<T>> {
+        if self.modu == T::zero() {
+            return None;
+        }
+
+        let mut quad = QuadEq { ..*self };
+
+        if quad.c > T::zero() {
+            quad.d = T::sub_mod(quad.d, quad.c, quad.modu);
+            quad.c = T::zero();
+        }
+
+        let prm_factor_repr = if quad.modu == T::one() {
+            // Single solution 0 is the empty combination
+            vec![]
+        } else {
+            let mut factors = Factors::new(quad.modu);
+            factors.factorize();
+
+            factors.prime_factor_repr()
+        };
+
+        let mut moduli = Vec::with_capacity(prm_factor_repr.len());
+        let mut roots = Vec::with_capacity(prm_factor_repr.len());
+
+        for &(prm, k) in prm_factor_repr.iter() {
+            let total_modulo = prm.pow(k.into());
+
+            let mut sub_sols = quad.solve_quad_prime_power_mod(prm, k)?.into_vec();
+
+            if sub_sols.is_empty() {
+                return None;
+            }
+            sub_sols.sort_unstable();
+            sub_sols.dedup();
+
+            moduli.push(total_modulo);
+            roots.push(sub_sols);
+        }
+
+        Some(SolutionSet::new(quad.modu, moduli, roots))
+    }
+
+    /// Create a builder for quadratic equations.
+    ///
+    /// Builder validates the equation up front instead of the solvers returning None.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use modular_equations::{BuildError, QuadEq};
+    ///
+    /// let quad_eq = QuadEq::<u64>::builder().a(1).b(1).c(1).d_signed(-1).modu(22).build();
+    ///
+    /// match quad_eq {
+    ///     Ok(quad_eq) => assert_eq!(quad_eq.solve(), Some(vec![4, 6, 15, 17])),
+    ///     Err(err) => panic!("{}", err),
+    /// }
+    ///
+    /// let quad_eq = QuadEq::<u64>::builder().a(1).modu(0).build();
+    /// assert_eq!(quad_eq.err(), Some(BuildError::InvalidModulus));
+    /// ```
+    pub fn builder() -> QuadEqBuilder<T> {
+        QuadEqBuilder {
+            a: Coef::unsigned(T::zero()),
+            b: Coef::unsigned(T::zero()),
+            c: Coef::unsigned(T::zero()),
+            d: Coef::unsigned(T::zero()),
+            modu: None,
+            error: None,
+        }
+    }
+
+    /// Check whether `x` is a solution of quadratic modular equation ax^2 + bx + c = d (mod modu).
+    ///
+    /// Value `x` doesn't need to be reduced modulo `modu`. For modulo zero, false is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use modular_equations::QuadEq;
+    ///
+    /// let quad_eq = QuadEq::<u32> {a: 1, b: 1, c: 3, d: 11, modu: 41};
+    ///
+    /// assert!(quad_eq.is_solution(9) && quad_eq.is_solution(31));
+    /// assert!(!quad_eq.is_solution(10));
+    /// ```
+    pub fn is_solution(&self, x: T) -> bool {
+        self.sides(x).is_some_and(|(lhs, rhs)| lhs == rhs)
+    }
+
+    /// Evaluate the left and right hand sides of quadratic modular equation
+    /// ax^2 + bx + c = d (mod modu) at `x`, both reduced modulo `modu`.
+    ///
+    /// Value `x` doesn't need to be reduced modulo `modu`. For modulo zero, None is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use modular_equations::QuadEq;
+    ///
+    /// let quad_eq = QuadEq::<u32> {a: 1, b: 1, c: 3, d: 11, modu: 41};
+    ///
+    /// assert_eq!(quad_eq.sides(9), Some((11, 11)));
+    /// assert_eq!(quad_eq.sides(10), Some((31, 11)));
+    /// ```
+    pub fn sides(&self, x: T) -> Option<(T, T)> {
+        if self.modu == T::zero() {
             return None;
         }
 
-        if a_is_zero {
+        Some((
+            poly::evaluate(&[self.c, self.b, self.a], x, self.modu),
+            self.d % self.modu,
+        ))
+    }
+
+    /// Solve quadratic modular equation as `solve` but return the solutions as balanced
+    /// representatives of their residue classes, i.e. in range (-modu/2, modu/2].
+    ///
+    /// Solutions are returned in increasing order for the signed type S. If there aren't
+    /// solutions or some of them doesn't fit into S, None is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use modular_equations::QuadEq;
+    ///
+    /// let quad_eq = QuadEq::<u32> {a: 1, b: 0, c: 0, d: 1, modu: 35};
+    ///
+    /// // Smallest nonnegative solutions are [1, 6, 29, 34]
+    /// assert_eq!(quad_eq.solve_balanced::<i32>(), Some(vec![-6, -1, 1, 6]));
+    /// ```
+    pub fn solve_balanced<S>(&self) -> Option<Vec<S>>
+    where
+        S: Int + SignCast<S, T>,
+        T: TryFrom<S>,
+    {
+        let mut sols = self
+            .solve()?
+            .into_iter()
+            .map(|x| S::cast_to_balanced(x, self.modu))
+            .collect::<Option<Vec<S>>>()?;
+        sols.sort_unstable();
+
+        Some(sols)
+    }
+
+    /// Find all integer solutions x of quadratic modular equation in range `lo` <= x < `hi`.
+    ///
+    /// Solutions aren't restricted to the smallest nonnegative representatives of their
+    /// residue classes but every integer of the range satisfying the equation is returned,
+    /// in increasing order. Equation is first solved modulo `modu` as in `solve` and the
+    /// integers of the range are then picked from each residue class.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use modular_equations::QuadEq;
+    ///
+    /// let quad_eq = QuadEq::<u64> {a: 1, b: 0, c: 0, d: 1, modu: 35};
+    ///
+    /// // Solutions modulo 35 are [1, 6, 29, 34]
+    /// assert_eq!(
+    ///     quad_eq.solutions_in_range(1_000_000, 1_000_040),
+    ///     vec![1_000_014, 1_000_019, 1_000_021, 1_000_026]
+    /// );
+    /// ```
+    pub fn solutions_in_range(&self, lo: T, hi: T) -> Vec<T> {
+        let sols = match self.solve() {
+            Some(sols) => sols,
+            None => return vec![],
+        };
+
+        let mut range_sols: Vec<T> = sols
+            .into_iter()
+            .flat_map(|x| lin::congruence_in_range(x, self.modu, lo, hi))
+            .collect();
+        range_sols.sort_unstable();
+
+        range_sols
+    }
+
+    /// Count the solutions of quadratic modular equation ax^2 + bx + c = d (mod modu).
+    ///
+    /// Solutions are counted separately modulo every prime power factor of the modulo
+    /// and by the Chinese remainder theorem the count is the product of these counts.
+    /// Neither the solutions for the original modulo nor those modulo its prime power
+    /// factors are enumerated. Zero count means that there aren't solutions.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use modular_equations::QuadEq;
+    ///
+    /// // Nine distinct prime factors, each having two square roots of one
+    /// let quad_eq = QuadEq::<u128> {a: 1, b: 0, c: 0, d: 1, modu: u128::MAX};
+    /// assert_eq!(quad_eq.count_solutions(), 512);
+    ///
+    /// let quad_eq = QuadEq::<u32> {a: 1, b: 0, c: 0, d: 3, modu: 17};
+    /// assert_eq!(quad_eq.count_solutions(), 0);
+    /// ```
+    pub fn count_solutions(&self) -> T {
+        // Default configuration has no timeout, hence factorization cannot fail
+        self.count_solutions_with_config(&FactorConfig::default())
+            .unwrap_or(T::zero())
+    }
+
+    /// Count the solutions of quadratic modular equation with factorization limits `config`.
+    ///
+    /// Like `solve_with_config`, returns `FactorError::Timeout` or `FactorError::Cancelled`
+    /// if the factorization of the modulo doesn't complete within the limits. Otherwise the
+    /// result equals that of `count_solutions`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use modular_equations::{FactorConfig, QuadEq};
+    ///
+    /// let config = FactorConfig {
+    ///     timeout: Some(Duration::from_secs(10)),
+    ///     ..FactorConfig::default()
+    /// };
+    /// let quad_eq = QuadEq::<u64> {a: 1, b: 0, c: 0, d: 1, modu: 1 << 40};
+    ///
+    /// assert_eq!(quad_eq.count_solutions_with_config(&config), Ok(4));
+    /// ```
+    pub fn count_solutions_with_config(&self, config: &FactorConfig) -> Result<T, FactorError> {
+        if self.modu == T::zero() {
+            return Ok(T::zero());
+        }
+
+        if self.a % self.modu == T::zero() {
+            // Equation is linear bx + c = d (mod modu)
+            let lin_eq = LinEq {
+                a: self.b,
+                b: self.c,
+                c: self.d,
+                modu: self.modu,
+            };
+            return Ok(lin_eq.count_solutions());
+        }
+
+        let mut quad = QuadEq { ..*self };
+
+        if quad.c > T::zero() {
+            quad.d = T::sub_mod(quad.d, quad.c, quad.modu);
+            quad.c = T::zero();
+        }
+
+        let prm_factor_repr = if prime::is_odd_prime(quad.modu) {
+            vec![(quad.modu, 1)]
+        } else {
+            let mut factors = Factors::new(quad.modu);
+            factors.factorize_with_config(config)?;
+
+            factors.prime_factor_repr()
+        };
+
+        let mut count = T::one();
+
+        for &(prm, k) in prm_factor_repr.iter() {
+            // Count modulo prm^k cannot exceed prm^k, thus the product fits in T
+            match quad.count_solutions_prime_power_mod(prm, k) {
+                sub_count if sub_count == T::zero() => return Ok(T::zero()),
+                sub_count => count = count * sub_count,
+            }
+        }
+
+        Ok(count)
+    }
+
+    /// Count the solutions of equation ax^2 + bx = d (mod `prm`^`prm_k`).
+    ///
+    /// Common power prm^v of the coefficients is taken out first, every solution modulo
+    /// prm^(k-v) giving prm^v solutions modulo prm^k. If prm doesn't divide a, completing
+    /// the square turns the equation to y^2 = D (mod prm^(k-v)) and otherwise a simple
+    /// root modulo prm lifts uniquely by Hensel's lemma.
+    fn count_solutions_prime_power_mod(&self, prm: T, prm_k: u8) -> T {
+        let total_modulo = prm.pow(prm_k.into());
+        let (a, b, d) = (
+            self.a % total_modulo,
+            self.b % total_modulo,
+            self.d % total_modulo,
+        );
+
+        let v = [a, b, d]
+            .iter()
+            .map(|&x| prime_power_valuation(x, prm, prm_k))
+            .min()
+            .unwrap_or(prm_k);
+
+        if v == prm_k {
+            // Every residue class is a solution
+            return total_modulo;
+        }
+
+        let scale = prm.pow(v.into());
+        let (a, b, d) = (a / scale, b / scale, d / scale);
+        let modu = total_modulo / scale;
+
+        let count = if a % prm == T::zero() {
+            // If prm divides b, d must be a unit. Otherwise the root of bx = d (mod prm) is simple
+            if b % prm == T::zero() {
+                T::zero()
+            } else {
+                T::one()
+            }
+        } else if prm == 2.into() && b & T::one() == T::one() {
+            // Both a and b odd, x = 0 and x = 1 are simple roots modulo 2 iff d is even
+            if d & T::one() == T::zero() {
+                2.into()
+            } else {
+                T::zero()
+            }
+        } else if prm == 2.into() {
+            // (ax + b/2)^2 = ad + (b/2)^2 (mod modu)
+            let b_half = b.unsigned_shr(1);
+            let disc = T::add_mod(
+                T::mult_mod(a, d, modu),
+                T::mult_mod(b_half, b_half, modu),
+                modu,
+            );
+
+            QuadEq::count_square_roots_prime_power_mod(disc, prm, prm_k - v)
+        } else {
+            // (2ax + b)^2 = b^2 + 4ad (mod modu)
+            let disc = T::add_mod(
+                T::mult_mod(b, b, modu),
+                T::mult_mod(4.into(), T::mult_mod(a, d, modu), modu),
+                modu,
+            );
+
+            QuadEq::count_square_roots_prime_power_mod(disc, prm, prm_k - v)
+        };
+
+        count * scale
+    }
+
+    /// Count the solutions of y^2 = `disc` (mod `prm`^`prm_k`).
+    fn count_square_roots_prime_power_mod(disc: T, prm: T, prm_k: u8) -> T {
+        let e = prime_power_valuation(disc, prm, prm_k);
+
+        if e == prm_k {
+            // y^2 = 0, y must be divisible by prm^ceil(k/2)
+            return prm.pow((prm_k / 2).into());
+        }
+        if e & 1 == 1 {
+            return T::zero();
+        }
+
+        // y = prm^(e/2) * z for a unit z satisfying z^2 = u (mod prm^(k-e)), z being
+        // unique modulo prm^(k-e/2)
+        let u = disc / prm.pow(e.into());
+        let z_count: T = if prm == 2.into() {
+            match prm_k - e {
+                1 => T::one(),
+                2 if u % 4.into() == T::one() => 2.into(),
+                k if k > 2 && u % 8.into() == T::one() => 4.into(),
+                _ => T::zero(),
+            }
+        } else if T::exp_mod(u, (prm - T::one()) / 2.into(), prm) == T::one() {
+            2.into()
+        } else {
+            T::zero()
+        };
+
+        z_count * prm.pow((e / 2).into())
+    }
+
+    fn solve_with_config_small(
+        &self,
+        config: &FactorConfig,
+    ) -> Result<Option<Solutions<T>>, FactorError> {
+        self.solve_with_factorizer(|modu| {
+            let mut factors = Factors::new(modu);
+
+            factors.factorize_with_config(config)?;
+            Ok(factors.prime_factor_repr())
+        })
+    }
+
+    /// Solve the equation, factorizing a composite modulo with `factorize`.
+    ///
+    /// Closure `factorize` must return the prime factor representation of the modulo:
+    /// [(p_1,k_1), ..., (p_n,k_n)] s.t. modu = p_1^k_1 * ... * p_n^k_n holds.
+    fn solve_with_factorizer<F>(&self, factorize: F) -> Result<Option<Solutions<T>>, FactorError>
+    where
+        F: FnOnce(T) -> Result<Vec<(T, u8)>, FactorError>,
+    {
+        if self.modu == T::zero() {
+            return Ok(None);
+        }
+
+        if self.a % self.modu == T::zero() {
+            // Equation is linear bx + c = d (mod modu)
             let lin_eq = LinEq {
                 a: self.b,
                 b: self.c,
                 c: self.d,
                 modu: self.modu,
             };
-            return lin_eq.solve();
+            return Ok(lin_eq.solve().map(Solutions::from_vec));
         }
 
         let mut quad = QuadEq { ..*self };
@@ -125,30 +719,130 @@ impl<T: 'static + UInt> QuadEq<T> {
         match prime::is_odd_prime(quad.modu) {
             true if quad.a == T::one() && quad.b == T::zero() => {
                 // Solve x^2 = d (mod modu)
-                quad.solve_quad_residue_odd_prime_mod()
+                Ok(quad.solve_quad_residue_odd_prime_mod())
             }
             true => {
                 // It might be possible to convert ax^2 + bx = d (mod modu)
                 // to (2ax + b)^2 = b^2 + 4ad which can then be solved in two steps
-                quad.solve_quad_simple()
+                Ok(quad.solve_quad_simple())
             }
             false => {
-                let mut factors = Factors::new(quad.modu);
+                let prm_factor_repr = factorize(quad.modu)?;
+
+                Ok(quad.solve_quad_composite_mod(&prm_factor_repr))
+            }
+        }
+    }
+
+    /// Solve quadratic equation together with linear constraints on the same unknown.
+    ///
+    /// Returns solutions x (mod modu) of ax^2 + bx + c = d (mod modu) that satisfy
+    /// every linear equation of `lin_eqs` as well. Modulo of each linear equation must
+    /// divide the modulo of the quadratic equation. As for `LinEq`, a linear equation
+    /// whose unknown vanishes either holds for every residue or has no solutions.
+    ///
+    /// Linear constraints are solved first and combined to a single congruence
+    /// x = r (mod m) by the Chinese remainder theorem. Substituting x = r + my to the
+    /// quadratic equation leaves a quadratic equation for y, so the solutions of the
+    /// quadratic equation are never enumerated without the constraints.
+    ///
+    /// If there aren't solutions, None is returned.
+    ///
+    /// # Examples
+    ///
+    /// Solve x^2 = 1 (mod 35) with constraint x = 1 (mod 5)
+    ///
+    /// ```
+    /// use modular_equations::{LinEq, QuadEq};
+    ///
+    /// let quad_eq = QuadEq::<u32> {a: 1, b: 0, c: 0, d: 1, modu: 35};
+    /// let lin_eq = LinEq::<u32> {a: 1, b: 0, c: 1, modu: 5};
+    ///
+    /// // Without the constraint, solutions would be [1, 6, 29, 34]
+    /// assert_eq!(quad_eq.solve_with_linear(&[lin_eq]), Some(vec![1, 6]));
+    /// ```
+    pub fn solve_with_linear(&self, lin_eqs: &[LinEq<T>]) -> Option<Vec<T>> {
+        if self.modu == T::zero() {
+            return None;
+        }
 
-                factors.factorize();
-                // Prime factor repr of `quad.modu`: [(p_1,k_1), ..., (p_n,k_n)] s.t.
-                // quad.modu = p_1^k_1 * ... * p_n^k_n holds
-                let prm_factor_repr = factors.prime_factor_repr();
+        let (mut r, mut m) = (T::zero(), T::one());
 
-                quad.solve_quad_composite_mod(&prm_factor_repr)
+        for lin_eq in lin_eqs.iter() {
+            if lin_eq.modu == T::zero() || self.modu % lin_eq.modu != T::zero() {
+                return None;
             }
+
+            let (r_lin, m_lin) = lin::solve_as_congruence(lin_eq)?;
+
+            // Moduli divide `self.modu`, hence also their lcm does
+            (r, m) = crt::combine_pair(r, m, r_lin, m_lin)?;
+        }
+
+        if m == T::one() {
+            return self.solve();
+        }
+
+        let modu = self.modu;
+        let (a, b) = (self.a % modu, self.b % modu);
+
+        // a(r + my)^2 + b(r + my) + c - d = m * (amy^2 + (2ar + b)y) + c' (mod modu),
+        // where c' = ar^2 + br + c - d must be divisible by m for any solutions
+        let c_sub = T::sub_mod(
+            T::add_mod(
+                T::mult_mod(T::add_mod(T::mult_mod(a, r, modu), b, modu), r, modu),
+                self.c % modu,
+                modu,
+            ),
+            self.d % modu,
+            modu,
+        );
+
+        if c_sub % m != T::zero() {
+            return None;
+        }
+
+        // Dividing by m leaves an equation for y (mod modu / m)
+        let y_modu = modu / m;
+
+        if y_modu == T::one() {
+            return Some(vec![r]);
         }
+
+        let a_sub = T::mult_mod(a, m, y_modu);
+        let b_sub = T::add_mod(
+            T::mult_mod(2.into(), T::mult_mod(a, r, y_modu), y_modu),
+            b % y_modu,
+            y_modu,
+        );
+        let d_sub = T::sub_mod(T::zero(), (c_sub / m) % y_modu, y_modu);
+
+        let y_sols: Solutions<T> = if a_sub == T::zero() && b_sub == T::zero() {
+            if d_sub != T::zero() {
+                return None;
+            }
+
+            iter::range(T::zero(), y_modu).collect()
+        } else {
+            let quad = QuadEq {
+                a: a_sub,
+                b: b_sub,
+                c: T::zero(),
+                d: d_sub,
+                modu: y_modu,
+            };
+
+            quad.solve_small()?
+        };
+
+        // Solutions are increasing as r < m
+        Some(y_sols.into_iter().map(|y| r + m * y).collect())
     }
 
     /// Solve equation (2ax + b)^2 = d' (mod modu), where modu is an odd prime
     /// and d' = b^2 + 4a(d - c). For this to work, a must be greater than zero.
     /// First solve z^2 = d (mod modu), and then 2ax + b = z (mod modu) for x.
-    fn solve_quad_simple(&self) -> Option<Vec<T>> {
+    fn solve_quad_simple(&self) -> Option<Solutions<T>> {
         if self.a == T::zero() && self.b == T::zero() {
             return None;
         }
@@ -179,10 +873,9 @@ impl<T: 'static + UInt> QuadEq<T> {
             modu: quad.modu,
         };
 
-        let mut x_sols = match lin_eq.solve() {
-            Some(sols) => sols,
-            _ => return None,
-        };
+        // Modulo is an odd prime and 2a is nonzero, thus x is unique for each z
+        let (x, _) = lin::solve_as_congruence(&lin_eq)?;
+        let mut x_sols: Solutions<T> = smallvec![x];
 
         if z[0] == T::zero() || z.len() == 1 {
             // z^2 = d (mod modu) has only one root
@@ -191,21 +884,18 @@ impl<T: 'static + UInt> QuadEq<T> {
 
         lin_eq.c = z[1];
 
-        let mut x_sols_2 = match lin_eq.solve() {
-            Some(sols) => sols,
-            _ => return Some(x_sols),
-        };
-
-        x_sols.append(&mut x_sols_2);
-        x_sols.sort();
+        if let Some((x_2, _)) = lin::solve_as_congruence(&lin_eq) {
+            x_sols.push(x_2);
+            x_sols.sort();
+        }
 
         Some(x_sols)
     }
 
-    fn solve_linear_singular(&self) -> Option<Vec<T>> {
+    fn solve_linear_singular(&self) -> Option<Solutions<T>> {
         if self.b == T::zero() && self.d == T::zero() {
             // a > 0 but a % modu == 0
-            return Some(vec![T::zero()]);
+            return Some(smallvec![T::zero()]);
         }
 
         let gcd_bm = T::gcd_mod(self.b, self.modu);
@@ -215,7 +905,7 @@ impl<T: 'static + UInt> QuadEq<T> {
         }
 
         if gcd_bm == T::one() {
-            Some(vec![T::mult_mod(
+            Some(smallvec![T::mult_mod(
                 T::multip_inv(self.b, self.modu),
                 self.d,
                 self.modu,
@@ -234,9 +924,9 @@ impl<T: 'static + UInt> QuadEq<T> {
 
     /// Solve equation x^2 = d (mod modu), where modu is an odd prime.
     /// There will be 0 to 2 roots for the equation.
-    fn solve_quad_residue_odd_prime_mod(&self) -> Option<Vec<T>> {
+    fn solve_quad_residue_odd_prime_mod(&self) -> Option<Solutions<T>> {
         if self.d == T::zero() {
-            return Some(vec![self.d]);
+            return Some(smallvec![self.d]);
         }
 
         if T::exp_mod(self.d, (self.modu - T::one()) / 2.into(), self.modu) != T::one() {
@@ -244,27 +934,101 @@ impl<T: 'static + UInt> QuadEq<T> {
             return None;
         }
 
-        match QuadEq::tonelli_shanks(self.d, self.modu) {
-            None => None,
-            Some(x) if x == T::zero() => Some(vec![x]),
-            Some(x) => {
-                let mut x_sols = vec![x, T::sub_mod_unsafe(T::zero(), x, self.modu)];
-                x_sols.sort();
+        match QuadEq::sqrt_odd_prime_mod(self.d, self.modu) {
+            None => None,
+            Some(x) if x == T::zero() => Some(smallvec![x]),
+            Some(x) => {
+                let mut x_sols = smallvec![x, T::sub_mod_unsafe(T::zero(), x, self.modu)];
+                x_sols.sort();
+
+                Some(x_sols)
+            }
+        }
+    }
+
+    /// Square root of quadratic residue `q` modulo odd prime `modu`.
+    ///
+    /// Primes p = 3 (mod 4) and p = 5 (mod 8) have closed-form roots needing a single
+    /// exponentiation, the latter by Atkin's method, and only for p = 1 (mod 8)
+    /// Tonelli-Shanks algorithm is run. Its main loop grows quadratically in the 2-adic
+    /// valuation s of p - 1, hence for primes such as the Proth primes k * 2^m + 1
+    /// with s(s - 1) > 8 * bits(p) + 20 Cipolla's algorithm is used instead.
+    fn sqrt_odd_prime_mod(q: T, modu: T) -> Option<T> {
+        let q = q % modu;
+
+        if modu & 3.into() == 3.into() {
+            // Exponent (p + 1) / 4 without overflow
+            Some(T::exp_mod_unsafe(q, (modu >> 2) + T::one(), modu))
+        } else if modu & 7.into() == 5.into() {
+            let q_double = T::add_mod_unsafe(q, q, modu);
+            let v = T::exp_mod_unsafe(q_double, modu >> 3, modu);
+            let i = T::mult_mod_unsafe(q_double, T::mult_mod_unsafe(v, v, modu), modu);
+            let i_minus_one = T::sub_mod_unsafe(i, T::one(), modu);
+
+            Some(T::mult_mod_unsafe(
+                T::mult_mod_unsafe(q, v, modu),
+                i_minus_one,
+                modu,
+            ))
+        } else {
+            let two_adicity = (modu - T::one()).trailing_zeros();
+            let bits = T::zero().count_zeros() - modu.leading_zeros();
+
+            if two_adicity * (two_adicity - 1) > 8 * bits + 20 {
+                QuadEq::cipolla(q, modu)
+            } else {
+                QuadEq::tonelli_shanks(q, modu)
+            }
+        }
+    }
+
+    /// Square root of quadratic residue `q`, smaller than odd prime `modu`, by Cipolla's algorithm.
+    ///
+    /// For w = a^2 - q a quadratic non-residue, the root is (a + sqrt(w))^((p + 1) / 2)
+    /// computed in the field F_p(sqrt(w)), the number of multiplications being linear
+    /// in the bit length of p.
+    fn cipolla(q: T, modu: T) -> Option<T> {
+        let (a, w) = iter::range(T::zero(), modu)
+            .map(|a| {
+                (
+                    a,
+                    T::sub_mod_unsafe(T::mult_mod_unsafe(a, a, modu), q, modu),
+                )
+            })
+            .find(|&(_, w)| T::jacobi_symbol(w, modu) == -1)?;
+
+        // Multiply x_1 + y_1 * sqrt(w) and x_2 + y_2 * sqrt(w)
+        let mult = |(x_1, y_1): (T, T), (x_2, y_2): (T, T)| {
+            let yy_w = T::mult_mod_unsafe(T::mult_mod_unsafe(y_1, y_2, modu), w, modu);
+
+            (
+                T::add_mod_unsafe(T::mult_mod_unsafe(x_1, x_2, modu), yy_w, modu),
+                T::add_mod_unsafe(
+                    T::mult_mod_unsafe(x_1, y_2, modu),
+                    T::mult_mod_unsafe(x_2, y_1, modu),
+                    modu,
+                ),
+            )
+        };
 
-                Some(x_sols)
+        let (mut res, mut base) = ((T::one(), T::zero()), (a, T::one()));
+        // Exponent (p + 1) / 2 without overflow
+        let mut ex = (modu >> 1) + T::one();
+
+        while ex > T::zero() {
+            if ex & T::one() == T::one() {
+                res = mult(res, base);
             }
+
+            ex = ex >> 1;
+            base = mult(base, base);
         }
+
+        Some(res.0)
     }
 
     fn tonelli_shanks(q: T, modu: T) -> Option<T> {
-        let modu_half = (modu - T::one()) / 2.into();
-
-        let non_resid = match iter::range(2.into(), modu)
-            .find(|&b| T::exp_mod_unsafe(b, modu_half, modu) != T::one())
-        {
-            Some(non_residue) => non_residue,
-            None => return None,
-        };
+        let non_resid = non_residue(modu)?;
 
         let modu_ev = modu - T::one();
         let pow = modu_ev.trailing_zeros();
@@ -317,7 +1081,15 @@ impl<T: 'static + UInt> QuadEq<T> {
     /// representation. Hence, the equation is actually solved in every ring
     /// of integers modulo p_i^k_i and at the end all the solutions are combined
     /// to a final solution for the original composite modulo.
-    fn solve_quad_composite_mod(&self, factor_repr: &[(T, u8)]) -> Option<Vec<T>> {
+    ///
+    /// Sub-equations are independent and if at least two of the primes are large,
+    /// they are solved concurrently in their own threads, except with the feature `wasm`.
+    fn solve_quad_composite_mod(&self, factor_repr: &[(T, u8)]) -> Option<Solutions<T>> {
+        if self.a == T::one() && self.b == T::zero() && !is_square_mod(self.d, factor_repr) {
+            // No square roots of d, skip solving modulo the other prime powers
+            return None;
+        }
+
         let mut x_sols: Vec<(T, T)> = vec![];
         let mut x_sols_count = 0;
 
@@ -326,21 +1098,39 @@ impl<T: 'static + UInt> QuadEq<T> {
         let mut modu_start_index: Vec<usize> = vec![0];
         let mut modu_sol_count: Vec<usize> = vec![];
 
-        let mut quad = QuadEq { ..*self };
+        let large_prm_count = factor_repr
+            .iter()
+            .filter(|&&(prm, _)| Into::<u128>::into(prm) >= PARALLEL_SOLVE_MIN_PRIME)
+            .count();
 
-        for (prm_factor, prm_k) in factor_repr.iter() {
-            let total_modulo = (*prm_factor).pow((*prm_k).into());
-            quad.modu = *prm_factor;
+        let all_sub_sols: Vec<Option<Solutions<T>>> = if large_prm_count >= 2
+            && !cfg!(feature = "wasm")
+        {
+            thread::scope(|scope| {
+                let handles: Vec<_> = factor_repr
+                    .iter()
+                    .map(|&(prm, k)| scope.spawn(move || self.solve_quad_prime_power_mod(prm, k)))
+                    .collect();
+
+                handles
+                    .into_iter()
+                    .map(|handle| handle.join().unwrap_or(None))
+                    .collect()
+            })
+        } else {
+            let mut all_sub_sols = Vec::with_capacity(uniq_factors);
 
-            let x_sub_sols = if quad.modu > 2.into() {
-                match quad.solve_quad_simple() {
-                    Some(x_sols) if *prm_k <= 1 => Some(x_sols),
-                    Some(x_sols) => quad.lift_with_hensel_method(x_sols, *prm_k),
-                    None => None,
+            for &(prm, k) in factor_repr.iter() {
+                match self.solve_quad_prime_power_mod(prm, k) {
+                    Some(sub_sols) => all_sub_sols.push(Some(sub_sols)),
+                    None => return None,
                 }
-            } else {
-                quad.solve_quad_mod_power_of_two(*prm_k, total_modulo)
-            };
+            }
+            all_sub_sols
+        };
+
+        for (x_sub_sols, (prm_factor, prm_k)) in all_sub_sols.into_iter().zip(factor_repr.iter()) {
+            let total_modulo = (*prm_factor).pow((*prm_k).into());
 
             match x_sub_sols {
                 Some(sub_sols) if !sub_sols.is_empty() => {
@@ -370,15 +1160,45 @@ impl<T: 'static + UInt> QuadEq<T> {
             ))
         } else {
             // Only one factor (p_i^k_i), nothing to combine
-            let mut sol: Vec<T> = x_sols.iter().map(|&x_tuple| x_tuple.0).collect();
+            let mut sol: Solutions<T> = x_sols.iter().map(|&x_tuple| x_tuple.0).collect();
             sol.sort();
 
             Some(sol)
         }
     }
 
+    /// Solve equation ax^2 + bx = d (mod `prm`^`prm_k`).
+    ///
+    /// If a vanishes modulo the prime power, the equation is solved as the linear
+    /// equation bx = d there.
+    fn solve_quad_prime_power_mod(&self, prm: T, prm_k: u8) -> Option<Solutions<T>> {
+        let total_modulo = prm.pow(prm_k.into());
+
+        if self.a % total_modulo == T::zero() {
+            let lin_eq = LinEq {
+                a: self.b,
+                b: T::zero(),
+                c: self.d,
+                modu: total_modulo,
+            };
+            return lin_eq.solve().map(Solutions::from_vec);
+        }
+
+        let quad = QuadEq { modu: prm, ..*self };
+
+        if prm > 2.into() {
+            match quad.solve_quad_simple() {
+                Some(x_sols) if prm_k <= 1 => Some(x_sols),
+                Some(x_sols) => quad.lift_with_hensel_method(x_sols, prm_k),
+                None => None,
+            }
+        } else {
+            quad.solve_quad_mod_power_of_two(prm_k, prm.pow(prm_k.into()))
+        }
+    }
+
     /// Solve equation ax^2 + bx = d (mod 2^m) for some m >= 1.
-    fn solve_quad_mod_power_of_two(&self, prm_k: u8, total_modulo: T) -> Option<Vec<T>> {
+    fn solve_quad_mod_power_of_two(&self, prm_k: u8, total_modulo: T) -> Option<Solutions<T>> {
         if self.b == T::zero() {
             return self.solve_quad_residue_power_of_two_mod(prm_k, total_modulo);
         }
@@ -422,7 +1242,11 @@ impl<T: 'static + UInt> QuadEq<T> {
     }
 
     /// Solve equation ax^2 = d (mod 2^m) for some m >= 1.
-    fn solve_quad_residue_power_of_two_mod(&self, prm_k: u8, total_modulo: T) -> Option<Vec<T>> {
+    fn solve_quad_residue_power_of_two_mod(
+        &self,
+        prm_k: u8,
+        total_modulo: T,
+    ) -> Option<Solutions<T>> {
         match prm_k {
             1 => self.solve_quad_simple_mod_two(),
             2 => self.solve_quad_simple_mod_four(total_modulo),
@@ -440,19 +1264,19 @@ impl<T: 'static + UInt> QuadEq<T> {
         }
     }
 
-    fn solve_quad_simple_mod_two(&self) -> Option<Vec<T>> {
+    fn solve_quad_simple_mod_two(&self) -> Option<Solutions<T>> {
         match (self.a & T::one() == T::one(), self.d & T::one() == T::one()) {
-            (true, true) => Some(vec![T::one()]),
-            (true, false) => Some(vec![T::zero()]),
+            (true, true) => Some(smallvec![T::one()]),
+            (true, false) => Some(smallvec![T::zero()]),
             (false, true) => {
                 // a even and d odd => no solution
                 None
             }
-            (false, false) => Some(vec![T::zero(), T::one()]),
+            (false, false) => Some(smallvec![T::zero(), T::one()]),
         }
     }
 
-    fn solve_quad_simple_mod_four(&self, total_modulo: T) -> Option<Vec<T>> {
+    fn solve_quad_simple_mod_four(&self, total_modulo: T) -> Option<Solutions<T>> {
         let d_is_even = self.d & T::one() == T::zero();
 
         if d_is_even {
@@ -460,11 +1284,11 @@ impl<T: 'static + UInt> QuadEq<T> {
             let a_mod_four = self.a % 4.into();
 
             if d_div_by_four && a_mod_four == T::zero() {
-                Some(vec![T::zero(), T::one(), 2.into(), 3.into()])
+                Some(smallvec![T::zero(), T::one(), 2.into(), 3.into()])
             } else if d_div_by_four {
-                Some(vec![T::zero(), 2.into()])
+                Some(smallvec![T::zero(), 2.into()])
             } else if a_mod_four == 2.into() {
-                Some(vec![T::one(), 3.into()])
+                Some(smallvec![T::one(), 3.into()])
             } else {
                 None
             }
@@ -472,7 +1296,7 @@ impl<T: 'static + UInt> QuadEq<T> {
             let d = T::mult_mod(T::multip_inv(self.a, total_modulo), self.d, total_modulo);
 
             if d % 4.into() == T::one() {
-                Some(vec![T::one(), 3.into()])
+                Some(smallvec![T::one(), 3.into()])
             } else {
                 None
             }
@@ -485,7 +1309,7 @@ impl<T: 'static + UInt> QuadEq<T> {
         &self,
         prm_k: u8,
         total_modulo: T,
-    ) -> Option<Vec<T>> {
+    ) -> Option<Solutions<T>> {
         let d = T::mult_mod(T::multip_inv(self.a, total_modulo), self.d, total_modulo);
 
         if d == T::zero() {
@@ -495,8 +1319,8 @@ impl<T: 'static + UInt> QuadEq<T> {
 
         if d % 8.into() == T::one() {
             // Odd squares
-            let mut sols: Vec<T> = vec![];
-            let base: Vec<T> = vec![T::one(), 3.into()];
+            let mut sols: Solutions<T> = smallvec![];
+            let base: Solutions<T> = smallvec![T::one(), 3.into()];
 
             for b in base.into_iter() {
                 let mut s = b;
@@ -515,25 +1339,48 @@ impl<T: 'static + UInt> QuadEq<T> {
             return Some(sols);
         }
 
-        let d_sqrt = integer::sqrt(d);
+        // Even d = 2^e * u, u odd, requires even e and x = 2^(e/2) * y, y^2 = u (mod 2^(k-e))
+        let e = d.trailing_zeros() as u8;
+
+        if e == 0 || e & 1 == 1 {
+            // Odd squares are 1 (mod 8)
+            return None;
+        }
+
+        let u_quad = QuadEq {
+            a: T::one(),
+            b: T::zero(),
+            c: T::zero(),
+            d: d.unsigned_shr(e.into()),
+            modu: self.modu,
+        };
+        let u_modulo = self.modu.pow((prm_k - e).into());
+
+        let u_sols = u_quad.solve_quad_residue_power_of_two_mod(prm_k - e, u_modulo)?;
+
+        // Solutions y are unique modulo 2^(k-e), thus x modulo 2^(k-e/2)
+        let x_scale = self.modu.pow((e / 2).into());
+        let x_step = self.modu.pow((prm_k - e / 2).into());
 
-        if T::trunc_square(d_sqrt) == d {
-            // Even square, base solution for mod 2 equals 0
-            let mut m_quad = QuadEq { ..*self };
-            m_quad.a = T::one();
-            m_quad.d = d;
+        let mut sols: Solutions<T> = smallvec![];
 
-            return m_quad.lift_with_hensel_method(vec![T::zero()], prm_k);
+        for y in u_sols.into_iter() {
+            let mut x = y * x_scale;
+
+            while x < total_modulo {
+                sols.push(x);
+                x = x + x_step;
+            }
         }
 
-        None
+        Some(sols)
     }
 
     fn solve_quad_simple_even_terms_mod_higher_power_of_two(
         &self,
         prm_k: u8,
         total_modulo: T,
-    ) -> Option<Vec<T>> {
+    ) -> Option<Solutions<T>> {
         let t = largest_common_dividing_power_of_two(
             (self.a % total_modulo).into(),
             total_modulo.into(),
@@ -554,9 +1401,9 @@ impl<T: 'static + UInt> QuadEq<T> {
         }
     }
 
-    fn search_possible_solutions_mod_power_of_two(&self) -> Option<Vec<T>> {
-        let mut sols: Vec<T> = vec![];
-        let sols_cand: Vec<T> = vec![T::zero(), T::one()];
+    fn search_possible_solutions_mod_power_of_two(&self) -> Option<Solutions<T>> {
+        let mut sols: Solutions<T> = smallvec![];
+        let sols_cand: Solutions<T> = smallvec![T::zero(), T::one()];
 
         for s in sols_cand.into_iter() {
             let poly_lhs = T::add_mod_unsafe(
@@ -579,11 +1426,11 @@ impl<T: 'static + UInt> QuadEq<T> {
 
     fn scale_possible_solutions_mod_power_of_two(
         &self,
-        sub_sols: Vec<T>,
+        sub_sols: Solutions<T>,
         prm_k: u8,
         m_prm_k: u8,
         t: u8,
-    ) -> Option<Vec<T>> {
+    ) -> Option<Solutions<T>> {
         let modulo = self.modu.pow(prm_k.into()); // Original modulo
         let modulo_t = self.modu.pow(t.into()); // >= 1
         let multiplier = self.modu.pow(m_prm_k.into());
@@ -602,7 +1449,7 @@ impl<T: 'static + UInt> QuadEq<T> {
         if sols.is_empty() {
             None
         } else {
-            Some(Vec::from_iter(sols))
+            Some(sols.into_iter().collect())
         }
     }
 
@@ -614,8 +1461,8 @@ impl<T: 'static + UInt> QuadEq<T> {
     ///
     /// Notice that `self.modu` is expected to be the prime factor prm and arg
     /// `prm_k` determines the final prime power prm^k of the lifting.
-    fn lift_with_hensel_method(&self, sub_sols: Vec<T>, prm_k: u8) -> Option<Vec<T>> {
-        let mut sols: Vec<T> = vec![];
+    fn lift_with_hensel_method(&self, sub_sols: Solutions<T>, prm_k: u8) -> Option<Solutions<T>> {
+        let mut sols: Solutions<T> = smallvec![];
 
         for sub_sol in sub_sols.into_iter() {
             let poly_d = T::add_mod(
@@ -632,24 +1479,10 @@ impl<T: 'static + UInt> QuadEq<T> {
                 continue;
             }
 
-            let t = T::multip_inv(poly_d, self.modu);
-
-            let mut modu = self.modu;
-            let mut lifted_sol = sub_sol;
-
-            for _ in 1..prm_k {
-                modu = modu * self.modu;
-
-                let ax = T::mult_mod(self.a, T::mult_mod(lifted_sol, lifted_sol, modu), modu);
-                let bx = T::mult_mod(self.b, lifted_sol, modu);
-                let cx = T::sub_mod(T::zero(), self.d, modu);
-
-                // poly = a * x_lifted^2 + b * x_lifted + c, where in this case c=-d
-                let poly = T::add_mod_unsafe(T::add_mod_unsafe(ax, bx, modu), cx, modu);
-                lifted_sol = T::sub_mod_unsafe(lifted_sol, T::mult_mod_unsafe(poly, t, modu), modu);
-            }
-
-            sols.push(lifted_sol);
+            // Simple root, lift it by Newton's iteration
+            sols.push(padic::lift_simple_root(
+                self.a, self.b, self.d, sub_sol, self.modu, prm_k,
+            ));
         }
 
         if sols.is_empty() {
@@ -659,15 +1492,15 @@ impl<T: 'static + UInt> QuadEq<T> {
         }
     }
 
-    fn lift_singular_root(&self, sub_sol: T, prm_k: u8) -> Option<Vec<T>> {
+    fn lift_singular_root(&self, sub_sol: T, prm_k: u8) -> Option<Solutions<T>> {
         let mut modu = self.modu;
 
-        let mut sols = vec![sub_sol];
+        let mut sols = smallvec![sub_sol];
 
         for _ in 1..prm_k {
             modu = modu * self.modu;
 
-            let mut lifted_sols = vec![];
+            let mut lifted_sols = smallvec![];
 
             for sol in sols.iter() {
                 let ax = T::mult_mod(self.a, T::mult_mod(*sol, *sol, modu), modu);
@@ -701,8 +1534,8 @@ impl<T: 'static + UInt> QuadEq<T> {
         compo_modu: T,
         modu_start_indices: Vec<usize>,
         modu_sol_counts: Vec<usize>,
-    ) -> Vec<T> {
-        let mut sols: Vec<T> = vec![];
+    ) -> Solutions<T> {
+        let mut sols: Solutions<T> = smallvec![];
 
         let index_combinations = match make_index_combinations(&modu_sol_counts) {
             Some(combi) => combi,
@@ -739,6 +1572,126 @@ impl<T: 'static + UInt> QuadEq<T> {
     }
 }
 
+impl<T: UInt> QuadEqBuilder<T> {
+    /// Set coefficient `a`.
+    pub fn a(mut self, a: T) -> Self {
+        self.a = Coef::unsigned(a);
+        self
+    }
+
+    /// Set coefficient `b`.
+    pub fn b(mut self, b: T) -> Self {
+        self.b = Coef::unsigned(b);
+        self
+    }
+
+    /// Set coefficient `c`.
+    pub fn c(mut self, c: T) -> Self {
+        self.c = Coef::unsigned(c);
+        self
+    }
+
+    /// Set coefficient `d`.
+    pub fn d(mut self, d: T) -> Self {
+        self.d = Coef::unsigned(d);
+        self
+    }
+
+    /// Set coefficient `a` from a signed value.
+    pub fn a_signed<S>(mut self, a: S) -> Self
+    where
+        S: Int + SignCast<S, T>,
+        T: TryFrom<S>,
+    {
+        self.a = self.set_signed(a);
+        self
+    }
+
+    /// Set coefficient `b` from a signed value.
+    pub fn b_signed<S>(mut self, b: S) -> Self
+    where
+        S: Int + SignCast<S, T>,
+        T: TryFrom<S>,
+    {
+        self.b = self.set_signed(b);
+        self
+    }
+
+    /// Set coefficient `c` from a signed value.
+    pub fn c_signed<S>(mut self, c: S) -> Self
+    where
+        S: Int + SignCast<S, T>,
+        T: TryFrom<S>,
+    {
+        self.c = self.set_signed(c);
+        self
+    }
+
+    /// Set coefficient `d` from a signed value.
+    pub fn d_signed<S>(mut self, d: S) -> Self
+    where
+        S: Int + SignCast<S, T>,
+        T: TryFrom<S>,
+    {
+        self.d = self.set_signed(d);
+        self
+    }
+
+    /// Set modulo `modu`.
+    pub fn modu(mut self, modu: T) -> Self {
+        self.modu = Some(modu);
+        self
+    }
+
+    /// Build the equation with reduced coefficients.
+    ///
+    /// Fails if some signed coefficient was S::min_value() or the modulo is missing or zero.
+    pub fn build(self) -> Result<QuadEq<T>, BuildError> {
+        if let Some(err) = self.error {
+            return Err(err);
+        }
+
+        let modu = self.modu.ok_or(BuildError::MissingModulus)?;
+
+        if modu == T::zero() {
+            return Err(BuildError::InvalidModulus);
+        }
+
+        Ok(QuadEq {
+            a: self.a.reduce(modu),
+            b: self.b.reduce(modu),
+            c: self.c.reduce(modu),
+            d: self.d.reduce(modu),
+            modu,
+        })
+    }
+
+    fn set_signed<S>(&mut self, x: S) -> Coef<T>
+    where
+        S: Int + SignCast<S, T>,
+        T: TryFrom<S>,
+    {
+        Coef::signed(x).unwrap_or_else(|err| {
+            self.error.get_or_insert(err);
+            Coef::unsigned(T::zero())
+        })
+    }
+}
+
+impl<T: 'static + UInt> ModularEquation<T> for QuadEq<T> {
+    fn solve(&self) -> Option<Vec<T>> {
+        QuadEq::solve(self)
+    }
+
+    fn count_solutions(&self) -> T {
+        QuadEq::count_solutions(self)
+    }
+
+    fn is_solution(&self, x: T) -> bool {
+        QuadEq::is_solution(self, x)
+    }
+}
+
 impl<T, S> QuadEqSigned<S, T>
 where
     S: Int + SignCast<S, T>,
@@ -757,6 +1710,115 @@ where
     ///
     /// Please see the documentation of `QuadEq` for examples.
     pub fn solve(&self) -> Option<Vec<T>> {
+        self.to_unsigned()?.solve()
+    }
+
+    /// Solve quadratic modular equation for signed type terms with factorization limits.
+    ///
+    /// Coefficients are cast to unsigned type as in `solve` and the equation is then solved
+    /// by the `solve_with_config` method of the struct `QuadEq`.
+    pub fn solve_with_config(&self, config: &FactorConfig) -> Result<Option<Vec<T>>, FactorError> {
+        match self.to_unsigned() {
+            Some(quad_eq) => quad_eq.solve_with_config(config),
+            None => Ok(None),
+        }
+    }
+
+    /// Solve quadratic modular equation for signed type terms using known prime
+    /// factorization `factors` of the modulo.
+    ///
+    /// Coefficients are cast to unsigned type as in `solve` and the equation is then solved
+    /// by the `solve_with_factors` method of the struct `QuadEq`.
+    pub fn solve_with_factors(&self, factors: &[(T, u8)]) -> Option<Vec<T>> {
+        self.to_unsigned()?.solve_with_factors(factors)
+    }
+
+    /// Solve quadratic modular equation for signed type terms as `try_solve` of `QuadEq`.
+    ///
+    /// If some of the coefficients cannot be cast to unsigned type, `SolveError::CastFailure`
+    /// is returned.
+    pub fn try_solve(&self) -> Result<Vec<T>, SolveError> {
+        self.to_unsigned()
+            .ok_or(SolveError::CastFailure)?
+            .try_solve()
+    }
+
+    /// Solve quadratic modular equation for signed type terms, returning the
+    /// solutions as `Solutions` similarly to the `solve_small` method of `QuadEq`.
+    pub fn solve_small(&self) -> Option<Solutions<T>> {
+        self.to_unsigned()?.solve_small()
+    }
+
+    /// Solve quadratic modular equation for signed type terms, returning the
+    /// solutions as `SolutionSet` similarly to the `solve_set` method of `QuadEq`.
+    pub fn solve_set(&self) -> Option<SolutionSet<T>> {
+        self.to_unsigned()?.solve_set()
+    }
+
+    /// Check whether signed `x` is a solution of quadratic modular equation for signed type terms.
+    ///
+    /// Coefficients and `x` are cast to unsigned type as in `solve` and the check is then
+    /// done by the `is_solution` method of `QuadEq`. If some of the casts fails, false is returned.
+    pub fn is_solution(&self, x: S) -> bool {
+        if self.modu == T::zero() {
+            return false;
+        }
+
+        match (self.to_unsigned(), S::cast_to_unsigned(x, self.modu)) {
+            (Some(quad_eq), Some(x)) => quad_eq.is_solution(x),
+            _ => false,
+        }
+    }
+
+    /// Evaluate the left and right hand sides of quadratic modular equation for signed
+    /// type terms at signed `x` as the `sides` method of `QuadEq`.
+    ///
+    /// Coefficients and `x` are cast to unsigned type as in `solve`. If some of the casts
+    /// fails or modulo is zero, None is returned.
+    pub fn sides(&self, x: S) -> Option<(T, T)> {
+        if self.modu == T::zero() {
+            return None;
+        }
+
+        self.to_unsigned()?
+            .sides(S::cast_to_unsigned(x, self.modu)?)
+    }
+
+    /// Solve quadratic modular equation for signed type terms, returning the solutions
+    /// as balanced representatives similarly to the `solve_balanced` method of `QuadEq`.
+    pub fn solve_balanced(&self) -> Option<Vec<S>> {
+        self.to_unsigned()?.solve_balanced()
+    }
+
+    /// Find all integer solutions of quadratic modular equation for signed type terms in
+    /// range `lo` <= x < `hi` as the `solutions_in_range` method of `QuadEq`.
+    ///
+    /// If some of the coefficients cannot be cast to unsigned type, an empty vector is returned.
+    pub fn solutions_in_range(&self, lo: T, hi: T) -> Vec<T> {
+        self.to_unsigned()
+            .map_or(vec![], |quad_eq| quad_eq.solutions_in_range(lo, hi))
+    }
+
+    /// Count the solutions of quadratic modular equation for signed type terms.
+    ///
+    /// Coefficients are cast to unsigned type as in `solve` and the solutions are then
+    /// counted by the `count_solutions` method of `QuadEq`. If some of the casts fails,
+    /// zero is returned.
+    pub fn count_solutions(&self) -> T {
+        self.to_unsigned()
+            .map_or(T::zero(), |quad_eq| quad_eq.count_solutions())
+    }
+
+    /// Count the solutions of quadratic modular equation for signed type terms with
+    /// factorization limits `config`, as `count_solutions_with_config` of `QuadEq`.
+    pub fn count_solutions_with_config(&self, config: &FactorConfig) -> Result<T, FactorError> {
+        match self.to_unsigned() {
+            Some(quad_eq) => quad_eq.count_solutions_with_config(config),
+            None => Ok(T::zero()),
+        }
+    }
+
+    fn to_unsigned(self) -> Option<QuadEq<T>> {
         let a_us = match S::cast_to_unsigned(self.a, self.modu) {
             Some(a) => a,
             None => {
@@ -789,15 +1851,369 @@ where
             }
         };
 
-        let quad_eq = QuadEq {
+        Some(QuadEq {
             a: a_us,
             b: b_us,
             c: c_us,
             d: d_us,
             modu: self.modu,
-        };
+        })
+    }
+}
+
+impl<T: UInt> SolutionSet<T> {
+    fn new(modu: T, moduli: Vec<T>, roots: Vec<Vec<T>>) -> Self {
+        let basis = moduli
+            .iter()
+            .map(|&m| {
+                let modu_div = modu / m;
+                T::mult_mod(modu_div, T::multip_inv(modu_div, m), modu)
+            })
+            .collect();
+
+        Self {
+            modu,
+            moduli,
+            roots,
+            basis,
+        }
+    }
+
+    /// Count of the solutions.
+    ///
+    /// Panics if the count exceeds usize::MAX.
+    pub fn len(&self) -> usize {
+        self.roots
+            .iter()
+            .try_fold(1usize, |count, sub_roots| {
+                count.checked_mul(sub_roots.len())
+            })
+            .expect("solution count exceeds usize::MAX")
+    }
+
+    /// Check whether the set is empty, never true for a set returned by the solvers.
+    pub fn is_empty(&self) -> bool {
+        self.roots.iter().any(|sub_roots| sub_roots.is_empty())
+    }
+
+    /// Modulo of the solutions.
+    pub fn modu(&self) -> T {
+        self.modu
+    }
+
+    /// Solution of index `n` or None if `n` isn't smaller than the count of the solutions.
+    pub fn nth(&self, n: usize) -> Option<T> {
+        let mut quotient = n;
+        let mut sol = T::zero();
+
+        for (sub_roots, &e) in self.roots.iter().zip(self.basis.iter()) {
+            if sub_roots.is_empty() {
+                return None;
+            }
+
+            let root = sub_roots[quotient % sub_roots.len()];
+            quotient /= sub_roots.len();
+
+            sol = T::add_mod(sol, T::mult_mod(root, e, self.modu), self.modu);
+        }
+
+        if quotient > 0 {
+            None
+        } else {
+            Some(sol)
+        }
+    }
+
+    /// Check whether residue class \[`x`\] is a solution.
+    pub fn contains(&self, x: T) -> bool {
+        self.roots
+            .iter()
+            .zip(self.moduli.iter())
+            .all(|(sub_roots, &m)| sub_roots.binary_search(&(x % m)).is_ok())
+    }
+
+    /// Iterate over the solutions in the order of their indices.
+    pub fn iter(&self) -> SolutionSetIter<'_, T> {
+        SolutionSetIter {
+            set: self,
+            index: 0,
+            len: self.len(),
+        }
+    }
+}
+
+impl<T: UInt> Iterator for SolutionSetIter<'_, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if self.index >= self.len {
+            return None;
+        }
+
+        self.index += 1;
+        self.set.nth(self.index - 1)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.len - self.index;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<T: UInt> ExactSizeIterator for SolutionSetIter<'_, T> {}
+
+/// Largest power k of `prm` dividing `x`, at most `max_k` which is returned for x = 0.
+fn prime_power_valuation<T: UInt>(x: T, prm: T, max_k: u8) -> u8 {
+    let (mut x, mut k) = (x, 0);
+
+    while k < max_k && x % prm == T::zero() {
+        x = x / prm;
+        k += 1;
+    }
+
+    k
+}
+
+/// Product of the prime powers of `factors`, sorted by the primes.
+///
+/// None is returned if the primes aren't distinct primes, some exponent is zero
+/// or the product overflows.
+fn prime_factor_product<T: UInt>(factors: &[(T, u8)]) -> Option<T> {
+    let mut prod = T::one();
+
+    for (j, &(prm, k)) in factors.iter().enumerate() {
+        if k == 0 || !prime::is_prime(prm) || (j > 0 && factors[j - 1].0 == prm) {
+            return None;
+        }
 
-        quad_eq.solve()
+        prod = prod.checked_mul(&num::checked_pow(prm, k.into())?)?;
+    }
+
+    Some(prod)
+}
+
+/// Smallest quadratic non-residue modulo odd prime `modu`.
+///
+/// Found non-residues are cached per modulo and shared by all threads, hence solving
+/// many square roots modulo the same prime searches the non-residue only once. Cache
+/// is emptied when it holds `NON_RESIDUE_CACHE_CAPACITY` moduli.
+fn non_residue<T: UInt>(modu: T) -> Option<T> {
+    static CACHE: OnceLock<Mutex<HashMap<u128, u128>>> = OnceLock::new();
+
+    let cache = CACHE.get_or_init(|| Mutex::new(HashMap::new()));
+    let key: u128 = modu.into();
+
+    if let Some(non_resid) = cache.lock().ok().and_then(|cache| cache.get(&key).copied()) {
+        return NumCast::from(non_resid);
+    }
+
+    let modu_half = (modu - T::one()) / 2.into();
+
+    let non_resid =
+        iter::range(2.into(), modu).find(|&b| T::exp_mod_unsafe(b, modu_half, modu) != T::one())?;
+
+    if let Ok(mut cache) = cache.lock() {
+        if cache.len() >= NON_RESIDUE_CACHE_CAPACITY {
+            cache.clear();
+        }
+        cache.insert(key, non_resid.into());
+    }
+
+    Some(non_resid)
+}
+
+/// Check that `factors`, sorted by the primes, is the prime factor representation of `modu`.
+fn is_prime_factor_repr<T: UInt>(factors: &[(T, u8)], modu: T) -> bool {
+    modu > T::zero() && prime_factor_product(factors) == Some(modu)
+}
+
+/// All square roots x of `a` modulo `modu`, i.e. solutions of x^2 = a (mod modu).
+///
+/// Roots are solved as the quadratic equation x^2 = a (mod modu), using closed forms,
+/// Tonelli-Shanks or Cipolla's algorithm for odd primes depending on the prime and
+/// Hensel lifting for their powers, and combined by the
+/// Chinese remainder theorem for a composite modulo. Modulo must be positive and for
+/// modulo one the only root is 0. If `a` isn't a quadratic residue, None is returned.
+///
+/// # Examples
+///
+/// ```
+/// use modular_equations::sqrt_mod;
+///
+/// assert_eq!(sqrt_mod::<u32>(4, 15), Some(vec![2, 7, 8, 13]));
+/// assert_eq!(sqrt_mod::<u32>(3, 17), None);
+/// ```
+pub fn sqrt_mod<T: 'static + UInt>(a: T, modu: T) -> Option<Vec<T>> {
+    let quad_eq = QuadEq {
+        a: T::one(),
+        b: T::zero(),
+        c: T::zero(),
+        d: a,
+        modu,
+    };
+
+    quad_eq.solve()
+}
+
+/// All square roots x of `a` modulo prime power `prm`^`prm_k`, i.e. solutions of
+/// x^2 = a (mod p^k).
+///
+/// Roots are solved as in `sqrt_mod` modulo the prime and lifted to its power by
+/// Hensel's lemma, or for p = 2 by the dedicated logic for powers of two, without
+/// factorizing the modulo. Roots are returned in increasing order. If `prm` isn't a
+/// prime, `prm_k` is zero, the power overflows or `a` isn't a quadratic residue,
+/// None is returned.
+///
+/// # Examples
+///
+/// ```
+/// use modular_equations::sqrt_mod_prime_power;
+///
+/// assert_eq!(sqrt_mod_prime_power::<u32>(2, 7, 2), Some(vec![10, 39]));
+/// assert_eq!(sqrt_mod_prime_power::<u32>(17, 2, 5), Some(vec![7, 9, 23, 25]));
+/// assert_eq!(sqrt_mod_prime_power::<u32>(3, 7, 3), None);
+/// ```
+pub fn sqrt_mod_prime_power<T: 'static + UInt>(a: T, prm: T, prm_k: u8) -> Option<Vec<T>> {
+    let quad_eq = QuadEq {
+        a: T::one(),
+        b: T::zero(),
+        c: T::zero(),
+        d: a,
+        modu: num::checked_pow(prm, prm_k.into())?,
+    };
+
+    quad_eq.solve_with_factors(&[(prm, prm_k)])
+}
+
+/// Rabin decryption of ciphertext `c` with the private key, primes `p` and `q` of the
+/// public modulus n = p * q.
+///
+/// Candidate plaintexts are the square roots of `c` modulo n, which are solved modulo
+/// `p` and `q` and combined by the Chinese remainder theorem as in `sqrt_mod`, without
+/// factorizing n. For `c` coprime to n there are four roots, returned in increasing
+/// order. If `p` and `q` aren't distinct primes, their product overflows or `c` isn't a
+/// quadratic residue, None is returned.
+///
+/// # Examples
+///
+/// ```
+/// use modular_equations::rabin_decrypt;
+///
+/// let (p, q) = (4_294_967_291u64, 4_294_967_279u64);
+/// let c = 1_000_000u64 * 1_000_000 % (p * q);
+///
+/// let roots = rabin_decrypt(c, p, q).unwrap();
+///
+/// assert_eq!(roots.len(), 4);
+/// assert!(roots.contains(&1_000_000));
+/// ```
+pub fn rabin_decrypt<T: 'static + UInt>(c: T, p: T, q: T) -> Option<Vec<T>> {
+    let quad_eq = QuadEq {
+        a: T::one(),
+        b: T::zero(),
+        c: T::zero(),
+        d: c,
+        modu: p.checked_mul(&q)?,
+    };
+
+    quad_eq.solve_with_factors(&[(p, 1), (q, 1)])
+}
+
+/// Check whether `a` is a quadratic residue modulo `modu`, i.e. x^2 = a (mod modu) is solvable.
+///
+/// Roots aren't computed. For every prime power factor p^k of the modulo, a is written as
+/// p^e * u (mod p^k) with u coprime to p, and solvability requires that either e >= k or
+/// e is even and u is a square modulo p^(k-e). For odd p the latter is decided by the
+/// Jacobi symbol (u|p) and for p = 2 by the residue of u modulo 8. Zero is a quadratic
+/// residue and so is every `a` modulo one, whereas for zero modulo false is returned.
+///
+/// # Examples
+///
+/// ```
+/// use modular_equations::is_quadratic_residue;
+///
+/// assert!(is_quadratic_residue::<u32>(4, 15));
+/// assert!(!is_quadratic_residue::<u32>(3, 17));
+/// // 68 = 2^2 * 17 and 17 = 1 (mod 8)
+/// assert!(is_quadratic_residue::<u32>(68, 128));
+/// ```
+pub fn is_quadratic_residue<T: 'static + UInt>(a: T, modu: T) -> bool {
+    if modu <= T::one() {
+        return modu == T::one();
+    }
+
+    let prm_factor_repr = if prime::is_odd_prime(modu) {
+        vec![(modu, 1)]
+    } else {
+        let mut factors = Factors::new(modu);
+        factors.factorize();
+
+        factors.prime_factor_repr()
+    };
+
+    is_square_mod(a, &prm_factor_repr)
+}
+
+/// Check whether `a` is a quadratic residue modulo n = p_1^k_1 * ... * p_n^k_n.
+///
+/// Factorization `factors` is given as pairs (p_i, k_i) of distinct primes and their
+/// exponents, in any order, and residuosity is decided for every prime power as in
+/// `is_quadratic_residue` without factorizing n or computing any roots. Hence this
+/// is cheap for large moduli whose factorization is known, e.g. for a Blum integer of
+/// the Goldwasser-Micali cryptosystem. Empty factorization stands for modulo one.
+///
+/// If `factors` isn't a valid prime factorization or n would overflow the type,
+/// None is returned.
+///
+/// # Examples
+///
+/// ```
+/// use modular_equations::is_qr_with_factors;
+///
+/// let (p, q) = (4_294_967_291u64, 4_294_967_279u64);
+///
+/// assert_eq!(is_qr_with_factors(4, &[(p, 1), (q, 1)]), Some(true));
+/// // -1 isn't a square modulo p = 3 (mod 4)
+/// assert_eq!(is_qr_with_factors(p * q - 1, &[(p, 1), (q, 1)]), Some(false));
+/// assert_eq!(is_qr_with_factors(4, &[(p, 1), (p, 1)]), None);
+/// ```
+pub fn is_qr_with_factors<T: UInt>(a: T, factors: &[(T, u8)]) -> Option<bool> {
+    let mut prm_factor_repr = factors.to_vec();
+    prm_factor_repr.sort_unstable();
+
+    prime_factor_product(&prm_factor_repr)?;
+
+    Some(is_square_mod(a, &prm_factor_repr))
+}
+
+/// Check whether `a` is a square modulo the prime factor representation `factors`.
+fn is_square_mod<T: UInt>(a: T, factors: &[(T, u8)]) -> bool {
+    factors
+        .iter()
+        .all(|&(prm, prm_k)| is_square_prime_power_mod(a % prm.pow(prm_k.into()), prm, prm_k))
+}
+
+/// Check whether `x`, smaller than `prm`^`prm_k`, is a square modulo `prm`^`prm_k`.
+fn is_square_prime_power_mod<T: UInt>(x: T, prm: T, prm_k: u8) -> bool {
+    let e = prime_power_valuation(x, prm, prm_k);
+
+    if e == prm_k {
+        return true;
+    }
+    if e & 1 == 1 {
+        return false;
+    }
+
+    let u = x / prm.pow(e.into());
+
+    if prm == 2.into() {
+        match prm_k - e {
+            1 => true,
+            2 => u & 3.into() == T::one(),
+            _ => u & 7.into() == T::one(),
+        }
+    } else {
+        T::jacobi_symbol(u % prm, prm) == 1
     }
 }
 
@@ -19,6 +19,10 @@
 //! -> eq_small_type_quad_residue_and_nonresidue_odd_prime_mod
 //! -> eq_mid_type_quad_residue_odd_prime_mod
 //! -> eq_large_type_quad_residue_odd_prime_mod
+//! -> eq_quad_residue_odd_prime_mod_residue_classes
+//! -> eq_quad_residue_proth_prime_mod
+//! -> cipolla_small_odd_primes
+//! -> non_residue_cached_per_modulo
 //!
 //! 2) ax^2 + bx = d (mod odd_prime)
 //! -> eq_small_type_odd_prime_mod
@@ -37,6 +41,7 @@
 //! -> eq_large_type_composite_mod
 //! -> eq_large_signed_type_composite_mod
 //! -> eq_large_signed_type_composite_mod_count_of_solutions
+//! -> eq_large_type_composite_mod_parallel
 //!
 //! 5) ax^2 + bx = d (mod 2^k)
 //! -> eq_small_type_mod_two
@@ -50,10 +55,33 @@
 //! -> eq_mid_type_general_mod_power_of_two_no_solution
 //! -> eq_signed_large_type_mix_mod_higher_power_of_two
 //!
+//! 6) ax^2 + bx = d (mod m) together with linear constraints on x
+//! -> eq_with_linear_constraints_small_type
+//! -> eq_with_linear_constraints_brute_force
+//! -> eq_with_linear_constraints_common_factors
+//! -> eq_with_linear_constraints_large_type
+//! -> eq_with_linear_constraints_invalid
+//!
+//! 7) ax^2 + bx = d (mod composite) with factorization limits
+//! -> eq_with_factor_config
+//! -> eq_signed_with_factor_config_timeout
+//!
+//! 8) Solutions returned as a small vector
+//! -> eq_solve_small_matches_solve
+//! -> eq_solve_small_inline_and_spilled
+//!
 use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+
+use smallvec::smallvec;
 
-use crate::quad::{QuadEq, QuadEqSigned};
-use crate::UInt;
+use crate::factor::{FactorConfig, FactorError, Factors};
+use crate::lin::{BuildError, LinEq, SolveError};
+use crate::quad::{
+    is_qr_with_factors, is_quadratic_residue, non_residue, rabin_decrypt, sqrt_mod,
+    sqrt_mod_prime_power, QuadEq, QuadEqSigned, Solutions,
+};
+use crate::{arith::Arith, prime, UInt};
 
 /// Check whether solutions arrays match. Arg `sols_cand` should be the array returned
 /// by the quadratic solver. Second arg `sols_corr` should contain the correct solutions
@@ -343,6 +371,134 @@ fn eq_large_type_quad_residue_odd_prime_mod() {
     }
 }
 
+#[test]
+fn eq_quad_residue_odd_prime_mod_residue_classes() {
+    // Primes p = 3 (mod 4), p = 5 (mod 8) and p = 1 (mod 8) have their own root algorithms
+    for modu in (3..2000u16).filter(|&p| prime::is_odd_prime(p)) {
+        let squares: HashSet<u16> = (0..modu).map(|x| u16::mult_mod(x, x, modu)).collect();
+
+        for d in 0..modu {
+            let quad_eq = QuadEq::<u16> {
+                a: 1,
+                b: 0,
+                c: 0,
+                d,
+                modu,
+            };
+
+            match quad_eq.solve() {
+                Some(sols) => {
+                    assert!(squares.contains(&d), "d: {}, modu: {}", d, modu);
+
+                    for x in sols {
+                        assert_eq!(u16::mult_mod(x, x, modu), d, "d: {}, modu: {}", d, modu);
+                    }
+                }
+                None => assert!(!squares.contains(&d), "d: {}, modu: {}", d, modu),
+            }
+        }
+    }
+
+    let moduli: [u128; 3] = [
+        340_282_366_920_938_463_463_374_607_431_768_211_283,
+        340_282_366_920_938_463_463_374_607_431_768_211_181,
+        340_282_366_920_938_463_463_374_607_431_768_211_297,
+    ];
+
+    for modu in moduli {
+        for x in [2, 12_345_678_901_234_567_890, modu / 3, modu - 5] {
+            let quad_eq = QuadEq::<u128> {
+                a: 1,
+                b: 0,
+                c: 0,
+                d: u128::mult_mod(x, x, modu),
+                modu,
+            };
+            let mut sols_corr = [x, modu - x];
+            sols_corr.sort();
+
+            assert_eq!(quad_eq.solve(), Some(sols_corr.to_vec()), "modu: {}", modu);
+        }
+    }
+}
+
+#[test]
+fn eq_quad_residue_proth_prime_mod() {
+    // Square roots modulo 2^16 + 1 are found by Cipolla's algorithm
+    let modu = 65_537u32;
+    let squares: HashSet<u32> = (0..modu).map(|x| u32::mult_mod(x, x, modu)).collect();
+
+    for d in 0..modu {
+        let quad_eq = QuadEq::<u32> {
+            a: 1,
+            b: 0,
+            c: 0,
+            d,
+            modu,
+        };
+
+        match quad_eq.solve() {
+            Some(sols) => {
+                for x in sols {
+                    assert_eq!(u32::mult_mod(x, x, modu), d, "d: {}", d);
+                }
+            }
+            None => assert!(!squares.contains(&d), "d: {}", d),
+        }
+    }
+
+    // 3 * 2^30 + 1, 2^64 - 2^32 + 1 and 7 * 2^120 + 1
+    let moduli: [u128; 3] = [
+        3_221_225_473,
+        18_446_744_069_414_584_321,
+        9_304_595_970_494_411_110_326_649_421_962_412_033,
+    ];
+
+    for modu in moduli {
+        for x in [3, 12_345_678_901 % modu, modu / 7, modu - 2] {
+            let quad_eq = QuadEq::<u128> {
+                a: 1,
+                b: 0,
+                c: 0,
+                d: u128::mult_mod(x, x, modu),
+                modu,
+            };
+            let mut sols_corr = [x, modu - x];
+            sols_corr.sort();
+
+            assert_eq!(quad_eq.solve(), Some(sols_corr.to_vec()), "modu: {}", modu);
+        }
+    }
+}
+
+#[test]
+fn cipolla_small_odd_primes() {
+    for modu in (3..500u32).filter(|&p| prime::is_odd_prime(p)) {
+        for q in 1..modu {
+            if u32::jacobi_symbol(q, modu) == 1 {
+                let x = QuadEq::<u32>::cipolla(q, modu).unwrap();
+
+                assert_eq!(x * x % modu, q, "q: {}, modu: {}", q, modu);
+            }
+        }
+    }
+}
+
+#[test]
+fn non_residue_cached_per_modulo() {
+    // More primes than fit in the cache at once
+    let primes: Vec<u32> = (3..5000).filter(|&p| prime::is_odd_prime(p)).collect();
+
+    for _ in 0..2 {
+        for &modu in primes.iter() {
+            let non_resid = (2..modu).find(|&b| u32::jacobi_symbol(b, modu) == -1);
+
+            assert_eq!(non_residue(modu), non_resid, "modu: {}", modu);
+            assert_eq!(non_residue(modu as u64), non_resid.map(u64::from));
+        }
+    }
+}
+
 #[test]
 fn eq_small_type_odd_prime_mod() {
     // [a, b, c, d, modu, res_1, res_2]: ax^2 + bx + c = d (mod modu)
@@ -636,7 +792,7 @@ fn hensel_method_with_power_of_three() {
     };
 
     // quad_eq sols: 1, 2
-    let quad_sols: Vec<u8> = vec![1, 2];
+    let quad_sols: Solutions<u8> = smallvec![1, 2];
 
     // [x_1, x_2, prm_k]: lifted solutions x_1 and x_2 for modu^prm_k
     let test_cases: [[u8; 3]; 3] = [
@@ -651,7 +807,11 @@ fn hensel_method_with_power_of_three() {
 
         let lifted_sols = quad_eq.lift_with_hensel_method(quad_sols.clone(), test[2]);
 
-        check_multiple_sols_correctness(lifted_sols, &correct_sols, modulo);
+        check_multiple_sols_correctness(
+            lifted_sols.map(Solutions::into_vec),
+            &correct_sols,
+            modulo,
+        );
     }
 }
 
@@ -666,7 +826,7 @@ fn hensel_method_with_power_of_five() {
     };
 
     // quad_eq sols: 2, 3
-    let quad_sols: Vec<u32> = vec![2, 3];
+    let quad_sols: Solutions<u32> = smallvec![2, 3];
 
     // [x_1, x_2, prm_k]: lifted solutions x_1 and x_2 for modu^prm_k
     let test_cases: [(u32, u32, u8); 3] = [
@@ -681,7 +841,11 @@ fn hensel_method_with_power_of_five() {
 
         let lifted_sols = quad_eq.lift_with_hensel_method(quad_sols.clone(), test.2);
 
-        check_multiple_sols_correctness(lifted_sols, &correct_sols, modulo);
+        check_multiple_sols_correctness(
+            lifted_sols.map(Solutions::into_vec),
+            &correct_sols,
+            modulo,
+        );
     }
 }
 
@@ -695,7 +859,7 @@ fn hensel_method_with_power_of_two() {
         modu: 2,
     };
     // quad_eq sols: 1
-    let quad_sols: Vec<u8> = vec![1];
+    let quad_sols: Solutions<u8> = smallvec![1];
 
     // lift solutions to 2^2
     match quad_eq.lift_with_hensel_method(quad_sols.clone(), 2) {
@@ -703,7 +867,7 @@ fn hensel_method_with_power_of_two() {
         Some(mut lifted) => {
             assert_eq!(lifted.len(), 2);
             lifted.sort();
-            assert_eq!(lifted, vec![1, 3]);
+            assert_eq!(lifted.as_slice(), &[1, 3]);
         }
     }
 
@@ -713,7 +877,7 @@ fn hensel_method_with_power_of_two() {
         Some(mut lifted) => {
             assert_eq!(lifted.len(), 4);
             lifted.sort();
-            assert_eq!(lifted, vec![1, 3, 5, 7]);
+            assert_eq!(lifted.as_slice(), &[1, 3, 5, 7]);
         }
     }
 
@@ -723,7 +887,7 @@ fn hensel_method_with_power_of_two() {
         Some(mut lifted) => {
             assert_eq!(lifted.len(), 4);
             lifted.sort();
-            assert_eq!(lifted, vec![1, 7, 9, 15]);
+            assert_eq!(lifted.as_slice(), &[1, 7, 9, 15]);
         }
     }
 
@@ -733,7 +897,7 @@ fn hensel_method_with_power_of_two() {
         Some(mut lifted) => {
             assert_eq!(lifted.len(), 4);
             lifted.sort();
-            assert_eq!(lifted, vec![1, 15, 17, 31]);
+            assert_eq!(lifted.as_slice(), &[1, 15, 17, 31]);
         }
     }
 }
@@ -756,7 +920,7 @@ fn combine_solution_for_composite_modu_small_type() {
     let correct_sols: Vec<u8> = vec![10, 32, 45, 67];
 
     assert_eq!(combined_sols.len(), correct_sols.len());
-    assert_eq!(combined_sols, correct_sols);
+    assert_eq!(combined_sols.as_slice(), correct_sols.as_slice());
 }
 
 #[test]
@@ -777,7 +941,7 @@ fn combine_solution_for_composite_modu_small_type_zero_sol() {
     let correct_sols: Vec<u8> = vec![12, 48];
 
     assert_eq!(combined_sols.len(), correct_sols.len());
-    assert_eq!(combined_sols, correct_sols);
+    assert_eq!(combined_sols.as_slice(), correct_sols.as_slice());
 }
 
 #[test]
@@ -798,7 +962,7 @@ fn combine_solution_for_composite_modu_mid_type() {
     let correct_sols: Vec<u32> = vec![29, 38, 94, 148, 164, 218, 274, 283];
 
     assert_eq!(combined_sols.len(), correct_sols.len());
-    assert_eq!(combined_sols, correct_sols);
+    assert_eq!(combined_sols.as_slice(), correct_sols.as_slice());
 }
 
 #[test]
@@ -1731,3 +1895,1269 @@ fn eq_signed_large_type_mix_mod_higher_power_of_two() {
         check_multiple_sols_correctness(quad_eq.solve(), corr, modu);
     }
 }
+
+#[test]
+fn eq_with_linear_constraints_small_type() {
+    let quad_eq = QuadEq::<u16> {
+        a: 1,
+        b: 0,
+        c: 0,
+        d: 1,
+        modu: 105,
+    };
+
+    // x^2 = 1 (mod 105) has eight solutions [1, 29, 34, 41, 64, 71, 76, 104]
+    let lin_eqs = [
+        LinEq {
+            a: 2,
+            b: 0,
+            c: 2,
+            modu: 3,
+        },
+        LinEq {
+            a: 1,
+            b: 1,
+            c: 0,
+            modu: 5,
+        },
+    ];
+
+    // 2x = 2 (mod 3) gives x = 1 (mod 3) and x + 1 = 0 (mod 5) gives x = 4 (mod 5)
+    assert_eq!(quad_eq.solve_with_linear(&lin_eqs), Some(vec![34, 64]));
+    assert_eq!(
+        quad_eq.solve_with_linear(&lin_eqs[1..]),
+        Some(vec![29, 34, 64, 104])
+    );
+    assert_eq!(
+        quad_eq.solve_with_linear(&[]),
+        Some(vec![1, 29, 34, 41, 64, 71, 76, 104])
+    );
+}
+
+#[test]
+fn eq_with_linear_constraints_brute_force() {
+    for modu in 2..60u32 {
+        let divisors: Vec<u32> = (2..=modu).filter(|m| modu % m == 0).collect();
+
+        for (a, b, d) in [(1, 0, 1), (3, 2, 5), (2, 7, 0), (6, 0, 3)].iter() {
+            let quad_eq = QuadEq {
+                a: *a,
+                b: *b,
+                c: 1,
+                d: *d,
+                modu,
+            };
+
+            for lin_modu in divisors.iter() {
+                for lin_a in 1..4 {
+                    let lin_eq = LinEq {
+                        a: lin_a,
+                        b: 1,
+                        c: 3,
+                        modu: *lin_modu,
+                    };
+
+                    let corr_sols: Vec<u32> = (0..modu)
+                        .filter(|x| (a * x * x + b * x + 1) % modu == d % modu)
+                        .filter(|x| (lin_a * x + 1) % lin_modu == 3 % lin_modu)
+                        .collect();
+
+                    let sols = quad_eq.solve_with_linear(&[lin_eq]);
+
+                    if corr_sols.is_empty() {
+                        assert_eq!(sols, None, "modu: {}, lin_modu: {}", modu, lin_modu);
+                    } else {
+                        assert_eq!(
+                            sols,
+                            Some(corr_sols),
+                            "quad: {:?}, lin_modu: {}, lin_a: {}",
+                            quad_eq,
+                            lin_modu,
+                            lin_a
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn eq_with_linear_constraints_common_factors() {
+    // x^2 = 0 (mod 18) with x = 0 (mod 3) substitutes x = 3y, leaving 3y^2 = 0 (mod 6)
+    let quad_eq = QuadEq::<u32> {
+        a: 1,
+        b: 0,
+        c: 0,
+        d: 0,
+        modu: 18,
+    };
+    let lin_eq = LinEq::<u32> {
+        a: 1,
+        b: 0,
+        c: 0,
+        modu: 3,
+    };
+    assert_eq!(quad_eq.solve_with_linear(&[lin_eq]), Some(vec![0, 6, 12]));
+
+    // Constraint x = r (mod m) with gcd(a * m, modu / m) > 1 for many of the cases
+    for modu in 2..150u32 {
+        for lin_modu in (2..=modu).filter(|m| modu % m == 0) {
+            for r in 0..lin_modu {
+                for (a, b, d) in [(1, 0, 0), (2, 0, 4), (3, 0, 9), (6, 3, 0), (9, 0, 1)] {
+                    let quad_eq = QuadEq {
+                        a,
+                        b,
+                        c: 0,
+                        d,
+                        modu,
+                    };
+                    let lin_eq = LinEq {
+                        a: 1,
+                        b: 0,
+                        c: r,
+                        modu: lin_modu,
+                    };
+
+                    let corr_sols: Vec<u32> = (0..modu)
+                        .filter(|x| (a * x * x + b * x) % modu == d % modu && x % lin_modu == r)
+                        .collect();
+                    let corr_sols = Some(corr_sols).filter(|sols| !sols.is_empty());
+
+                    assert_eq!(
+                        quad_eq.solve_with_linear(&[lin_eq]),
+                        corr_sols,
+                        "quad: {:?}, r: {}, lin_modu: {}",
+                        quad_eq,
+                        r,
+                        lin_modu
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn eq_with_linear_constraints_large_type() {
+    // 2^64 - 59 and 2^64 - 83 are primes
+    let prm_1: u128 = 18_446_744_073_709_551_557;
+    let prm_2: u128 = 18_446_744_073_709_551_533;
+    let modu = prm_1 * prm_2;
+
+    let quad_eq = QuadEq {
+        a: 1,
+        b: 0,
+        c: 0,
+        d: 1,
+        modu,
+    };
+    let lin_eq = LinEq {
+        a: 1,
+        b: 0,
+        c: 1,
+        modu: prm_2,
+    };
+
+    // x = 1 (mod prm_2) and x = +-1 (mod prm_1)
+    match quad_eq.solve_with_linear(&[lin_eq]) {
+        Some(sols) => {
+            assert_eq!(sols.len(), 2);
+            assert_eq!(sols[0], 1);
+            assert_eq!(sols[1] % prm_1, prm_1 - 1);
+            assert_eq!(sols[1] % prm_2, 1);
+        }
+        None => panic!("sols: None"),
+    }
+}
+
+#[test]
+fn eq_with_linear_constraints_invalid() {
+    let quad_eq = QuadEq::<u32> {
+        a: 1,
+        b: 0,
+        c: 0,
+        d: 4,
+        modu: 60,
+    };
+
+    // Modulo 7 doesn't divide 60
+    let lin_eq = LinEq {
+        a: 1,
+        b: 0,
+        c: 2,
+        modu: 7,
+    };
+    assert_eq!(quad_eq.solve_with_linear(&[lin_eq]), None);
+
+    // Inconsistent constraints x = 0 (mod 2) and x = 1 (mod 4)
+    let lin_eqs = [
+        LinEq {
+            a: 1,
+            b: 0,
+            c: 0,
+            modu: 2,
+        },
+        LinEq {
+            a: 1,
+            b: 0,
+            c: 1,
+            modu: 4,
+        },
+    ];
+    assert_eq!(quad_eq.solve_with_linear(&lin_eqs), None);
+}
+
+#[test]
+fn eq_with_factor_config() {
+    let config = FactorConfig {
+        max_curves: 10,
+        curve_bound: 5_000,
+        small_prime_bound: 500,
+        trial_bound: Some(10_000),
+        timeout: Some(Duration::from_secs(600)),
+        workers: 3,
+        cancel: None,
+        progress: None,
+        cache: None,
+    };
+
+    let test_cases: [(u128, u128, u128, u128); 3] = [
+        (1, 0, 1, 2_854_159_729_781),
+        (3, 5, 7, 25_645_121_643_901_801),
+        (1, 1, 1_000, 9_804_659_461_513_846_513),
+    ];
+
+    for (a, b, d, modu) in test_cases.iter() {
+        let quad_eq = QuadEq::<u128> {
+            a: *a,
+            b: *b,
+            c: 0,
+            d: *d,
+            modu: *modu,
+        };
+
+        assert_eq!(
+            quad_eq.solve_with_config(&config),
+            Ok(quad_eq.solve()),
+            "modu: {}",
+            modu
+        );
+    }
+}
+
+#[test]
+fn eq_with_factors() {
+    type FactorRepr = [(u128, u8)];
+
+    let test_cases: [(u128, u128, u128, &FactorRepr); 4] = [
+        (1, 0, 1, &[(2, 1), (3, 2), (5, 1)]),
+        (3, 5, 7, &[(5_394_769, 1), (4_753_701_529, 1)]),
+        (1, 1, 1_000, &[(2_112_166_839_943, 1), (4_641_991, 1)]),
+        (1, 0, 4, &[(3, 4), (7, 2), (103_979, 1)]),
+    ];
+
+    for (a, b, d, factors) in test_cases.iter() {
+        let modu = factors
+            .iter()
+            .map(|&(prm, k)| prm.pow(k.into()))
+            .product::<u128>();
+
+        let quad_eq = QuadEq::<u128> {
+            a: *a,
+            b: *b,
+            c: 0,
+            d: *d,
+            modu,
+        };
+
+        assert_eq!(
+            quad_eq.solve_with_factors(factors),
+            quad_eq.solve(),
+            "modu: {}",
+            modu
+        );
+    }
+
+    let quad_eq = QuadEqSigned::<i32, u32> {
+        a: -1,
+        b: 0,
+        c: 0,
+        d: -1,
+        modu: 1,
+    };
+    assert_eq!(quad_eq.solve_with_factors(&[]), Some(vec![0]));
+}
+
+#[test]
+fn eq_with_invalid_factors() {
+    let quad_eq = QuadEq::<u64> {
+        a: 1,
+        b: 0,
+        c: 0,
+        d: 1,
+        modu: 45,
+    };
+
+    let test_cases: [&[(u64, u8)]; 6] = [
+        &[],
+        &[(3, 2)],
+        &[(3, 1), (3, 1), (5, 1)],
+        &[(9, 1), (5, 1)],
+        &[(3, 2), (5, 1), (7, 0)],
+        &[(3, 2), (5, 1), (2, 64)],
+    ];
+
+    for factors in test_cases.iter() {
+        assert_eq!(quad_eq.solve_with_factors(factors), None, "{:?}", factors);
+    }
+
+    assert_eq!(
+        quad_eq.solve_with_factors(&[(5, 1), (3, 2)]),
+        Some(vec![1, 19, 26, 44])
+    );
+}
+
+#[test]
+fn eq_signed_with_factor_config_timeout() {
+    let config = FactorConfig {
+        timeout: Some(Duration::ZERO),
+        ..FactorConfig::default()
+    };
+
+    let quad_eq = QuadEqSigned::<i128, u128> {
+        a: 1,
+        b: -1,
+        c: 0,
+        d: 5,
+        modu: 90_124_258_835_295_998_242_413_094_252_351,
+    };
+
+    assert_eq!(
+        quad_eq.solve_with_config(&config),
+        Err(FactorError::Timeout)
+    );
+    assert_eq!(
+        quad_eq.count_solutions_with_config(&config),
+        Err(FactorError::Timeout)
+    );
+
+    // Prime modulo doesn't need factorization
+    let quad_eq = QuadEqSigned::<i128, u128> {
+        a: 1,
+        b: 0,
+        c: 0,
+        d: 4,
+        modu: 1_000_000_007,
+    };
+
+    assert_eq!(
+        quad_eq.solve_with_config(&config),
+        Ok(Some(vec![2, 1_000_000_005]))
+    );
+    assert_eq!(quad_eq.count_solutions_with_config(&config), Ok(2));
+}
+
+#[test]
+fn eq_solve_small_matches_solve() {
+    for modu in 2..120u32 {
+        for a in 0..6 {
+            for b in 0..4 {
+                for d in 0..modu.min(10) {
+                    let quad_eq = QuadEq::<u32> {
+                        a,
+                        b,
+                        c: 1,
+                        d,
+                        modu,
+                    };
+
+                    assert_eq!(
+                        quad_eq.solve_small().map(|sols| sols.into_vec()),
+                        quad_eq.solve(),
+                        "a: {}, b: {}, d: {}, modu: {}",
+                        a,
+                        b,
+                        d,
+                        modu
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn eq_solve_small_inline_and_spilled() {
+    // x^2 = 2 (mod 1_000_000_007) has two solutions, stored inline
+    let quad_eq = QuadEqSigned::<i64, u64> {
+        a: 1,
+        b: 0,
+        c: -2,
+        d: 0,
+        modu: 1_000_000_007,
+    };
+
+    match quad_eq.solve_small() {
+        Some(sols) => {
+            assert!(!sols.spilled());
+            assert_eq!(sols.as_slice(), &[59_713_600, 940_286_407]);
+        }
+        None => panic!("no solutions"),
+    }
+
+    // x^2 = 1 (mod 3 * 5 * 7) has eight solutions
+    let quad_eq = QuadEq::<u64> {
+        a: 1,
+        b: 0,
+        c: 0,
+        d: 1,
+        modu: 105,
+    };
+
+    match quad_eq.solve_small() {
+        Some(sols) => {
+            assert!(sols.spilled());
+            assert_eq!(sols.as_slice(), &[1, 29, 34, 41, 64, 71, 76, 104]);
+        }
+        None => panic!("no solutions"),
+    }
+}
+
+#[test]
+fn eq_large_type_composite_mod_parallel() {
+    // Moduli have at least two prime factors larger than 2^32, solved in parallel
+    let test_cases: [(u128, Vec<u128>); 2] = [
+        (
+            4_294_967_311 * 4_294_967_357 * 9,
+            vec![
+                2,
+                18_446_744_400_127_067_029,
+                29_274_181_331_290_014_440,
+                47_720_925_731_417_081_467,
+                118_299_773_869_726_521_773,
+                136_746_518_269_853_588_800,
+                147_573_955_201_016_536_211,
+                166_020_699_601_143_603_238,
+            ],
+        ),
+        (
+            4_294_967_311 * 1_099_511_627_791 * 7,
+            vec![
+                2,
+                10_880_579_335_805_383_709_080,
+                22_175_986_160_181_825_270_924,
+                33_056_565_495_987_208_980_002,
+            ],
+        ),
+    ];
+
+    for (modu, corr_sols) in test_cases.iter() {
+        let quad_eq = QuadEq::<u128> {
+            a: 1,
+            b: 3,
+            c: 0,
+            d: 10,
+            modu: *modu,
+        };
+
+        assert_eq!(quad_eq.solve().as_ref(), Some(corr_sols), "modu: {}", modu);
+    }
+}
+
+#[test]
+fn sqrt_mod_brute_force() {
+    for modu in 2..300u32 {
+        for a in 0..modu {
+            let corr_sols: Vec<u32> = (0..modu).filter(|&x| x * x % modu == a).collect();
+
+            let corr_sols = if corr_sols.is_empty() {
+                None
+            } else {
+                Some(corr_sols)
+            };
+
+            assert_eq!(sqrt_mod(a, modu), corr_sols, "a: {}, modu: {}", a, modu);
+        }
+    }
+}
+
+#[test]
+fn sqrt_mod_large_type() {
+    let modu = u128::MAX;
+    let a = u128::mult_mod(12_345_678_901_234_567_890, 12_345_678_901_234_567_890, modu);
+
+    match sqrt_mod(a, modu) {
+        Some(sols) => {
+            assert!(sols.contains(&12_345_678_901_234_567_890));
+            assert!(sols.iter().all(|&x| u128::mult_mod(x, x, modu) == a));
+        }
+        None => panic!("no square roots"),
+    }
+
+    assert_eq!(sqrt_mod::<u128>(2, 1), Some(vec![0]));
+    assert_eq!(sqrt_mod::<u128>(2, 0), None);
+}
+
+#[test]
+fn sqrt_mod_prime_power_brute_force() {
+    for (prm, max_k) in [
+        (2u32, 10),
+        (3, 6),
+        (5, 4),
+        (7, 3),
+        (11, 2),
+        (13, 2),
+        (97, 1),
+    ] {
+        for prm_k in 1..=max_k {
+            let modu = prm.pow(prm_k.into());
+
+            for a in 0..modu {
+                let corr_sols: Vec<u32> = (0..modu).filter(|&x| x * x % modu == a).collect();
+                let corr_sols = Some(corr_sols).filter(|sols| !sols.is_empty());
+
+                assert_eq!(
+                    sqrt_mod_prime_power(a, prm, prm_k),
+                    corr_sols,
+                    "a: {}, modu: {}^{}",
+                    a,
+                    prm,
+                    prm_k
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn sqrt_mod_prime_power_large_type() {
+    // 3^80 fits into u128
+    let modu = 3u128.pow(80);
+    let x = 12_345_678_901_234_567_890_121u128;
+    let a = u128::mult_mod(x, x, modu);
+
+    assert_eq!(sqrt_mod_prime_power(a, 3, 80), Some(vec![x, modu - x]));
+
+    let a = u128::mult_mod(x, x, 1 << 127);
+    let sols = sqrt_mod_prime_power(a, 2, 127).unwrap();
+
+    assert_eq!(sols.len(), 4);
+    assert!(sols.contains(&x));
+
+    assert_eq!(sqrt_mod_prime_power::<u128>(4, 3, 81), None);
+    assert_eq!(sqrt_mod_prime_power::<u128>(4, 9, 2), None);
+    assert_eq!(sqrt_mod_prime_power::<u128>(4, 3, 0), None);
+}
+
+#[test]
+fn rabin_decrypt_roots() {
+    // Primes p = q = 3 (mod 4) used in Rabin cryptosystem
+    let (p, q) = (
+        18_446_744_073_709_551_427u128,
+        18_446_744_073_709_551_359u128,
+    );
+    let modu = p * q;
+
+    for msg in [2, 12_345_678_901_234_567_890, modu - 1] {
+        let c = u128::mult_mod(msg, msg, modu);
+        let roots = rabin_decrypt(c, p, q).unwrap();
+
+        assert_eq!(roots.len(), 4);
+        assert!(roots.contains(&msg) && roots.contains(&(modu - msg)));
+        assert_eq!(Some(roots), sqrt_mod(c, modu));
+    }
+
+    // Ciphertext not coprime to the modulo has fewer roots
+    assert_eq!(rabin_decrypt(p, p, q), sqrt_mod(p, modu));
+    assert_eq!(rabin_decrypt(4u32, 7, 7), None);
+    assert_eq!(rabin_decrypt(4u32, 7, 15), None);
+    assert_eq!(rabin_decrypt(4u32, 65_537, 65_539), None);
+    assert_eq!(rabin_decrypt(3u32, 7, 11), None);
+}
+
+#[test]
+fn is_quadratic_residue_brute_force() {
+    for modu in 1..300u32 {
+        let squares: HashSet<u32> = (0..modu).map(|x| x * x % modu).collect();
+
+        for a in 0..2 * modu {
+            assert_eq!(
+                is_quadratic_residue(a, modu),
+                squares.contains(&(a % modu)),
+                "a: {}, modu: {}",
+                a,
+                modu
+            );
+        }
+    }
+
+    assert!(!is_quadratic_residue::<u32>(0, 0));
+}
+
+#[test]
+fn is_quadratic_residue_large_type() {
+    let modu = u128::MAX;
+    let a = u128::mult_mod(12_345_678_901_234_567_890, 12_345_678_901_234_567_890, modu);
+
+    assert!(is_quadratic_residue(a, modu));
+    assert!(is_quadratic_residue::<u128>(1 << 126, 1 << 127));
+    assert!(!is_quadratic_residue::<u128>(1 << 125, 1 << 127));
+    // 2^128 - 159 = 1 (mod 8), hence 2 is a quadratic residue
+    assert!(is_quadratic_residue::<u128>(2, u128::MAX - 158));
+}
+
+#[test]
+fn is_qr_with_factors_brute_force() {
+    for modu in 2..300u32 {
+        let mut factors = Factors::new(modu);
+        factors.factorize();
+        let mut prm_factor_repr = factors.prime_factor_repr();
+        prm_factor_repr.reverse();
+
+        for a in 0..2 * modu {
+            assert_eq!(
+                is_qr_with_factors(a, &prm_factor_repr),
+                Some(is_quadratic_residue(a, modu)),
+                "a: {}, modu: {}",
+                a,
+                modu
+            );
+        }
+    }
+}
+
+#[test]
+fn is_qr_with_factors_invalid_factors() {
+    assert_eq!(is_qr_with_factors::<u32>(5, &[]), Some(true));
+    assert_eq!(is_qr_with_factors::<u32>(4, &[(9, 1)]), None);
+    assert_eq!(is_qr_with_factors::<u32>(4, &[(3, 0)]), None);
+    assert_eq!(
+        is_qr_with_factors::<u32>(4, &[(5, 1), (3, 2), (5, 1)]),
+        None
+    );
+    assert_eq!(
+        is_qr_with_factors::<u32>(4, &[(65_537, 1), (65_539, 1)]),
+        None
+    );
+}
+
+#[test]
+fn is_qr_with_factors_large_type() {
+    // Blum integer p * q with p = q = 3 (mod 4)
+    let (p, q) = (
+        18_446_744_073_709_551_427u128,
+        18_446_744_073_709_551_359u128,
+    );
+    let x = 12_345_678_901_234_567_890u128;
+    let square = u128::mult_mod(x, x, p * q);
+
+    assert_eq!(is_qr_with_factors(square, &[(p, 1), (q, 1)]), Some(true));
+    assert_eq!(
+        is_qr_with_factors(p * q - square, &[(p, 1), (q, 1)]),
+        Some(false)
+    );
+}
+
+#[test]
+fn eq_even_non_square_residue_power_of_two_mod() {
+    // E.g. 68 = 4 * 17 isn't a square but x^2 = 68 (mod 128) has solutions
+    for k in 3..10 {
+        let modu = 1u32 << k;
+
+        for a in [1, 3, 5, 7].iter() {
+            for d in (0..modu).step_by(4) {
+                let corr_sols: Vec<u32> = (0..modu).filter(|&x| a * x * x % modu == d).collect();
+
+                let quad_eq = QuadEq {
+                    a: *a,
+                    b: 0,
+                    c: 0,
+                    d,
+                    modu,
+                };
+
+                match quad_eq.solve() {
+                    Some(sols) => assert_eq!(sols, corr_sols, "a: {}, d: {}, modu: {}", a, d, modu),
+                    None => assert!(corr_sols.is_empty(), "a: {}, d: {}, modu: {}", a, d, modu),
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn eq_modulo_one() {
+    let quad_eq = QuadEq::<u64> {
+        a: 3,
+        b: 5,
+        c: 7,
+        d: 2,
+        modu: 1,
+    };
+    assert_eq!(quad_eq.solve(), Some(vec![0]));
+    assert_eq!(quad_eq.try_solve(), Ok(vec![0]));
+
+    let lin_eq = LinEq {
+        a: 1,
+        b: 0,
+        c: 0,
+        modu: 1,
+    };
+    assert_eq!(quad_eq.solve_with_linear(&[lin_eq]), Some(vec![0]));
+
+    let quad_eq = QuadEqSigned::<i64, u64> {
+        a: -3,
+        b: 5,
+        c: -7,
+        d: 2,
+        modu: 1,
+    };
+    assert_eq!(quad_eq.solve(), Some(vec![0]));
+}
+
+#[test]
+fn eq_vanishing_unknown() {
+    // [a, b, c, d, modu]: unknown vanishes, every residue is a solution iff c = d (mod modu)
+    let test_cases: [[u16; 5]; 4] = [
+        [0, 0, 3, 3, 7],
+        [12, 24, 5, 17, 12],
+        [0, 0, 1, 2, 7],
+        [12, 24, 5, 16, 12],
+    ];
+
+    for test in test_cases.iter() {
+        let quad_eq = QuadEq {
+            a: test[0],
+            b: test[1],
+            c: test[2],
+            d: test[3],
+            modu: test[4],
+        };
+
+        let corr_sols = if test[2] % test[4] == test[3] % test[4] {
+            Some((0..test[4]).collect())
+        } else {
+            None
+        };
+
+        assert_eq!(quad_eq.solve(), corr_sols, "test: {:?}", test);
+        assert_eq!(
+            quad_eq.try_solve(),
+            corr_sols.ok_or(SolveError::VariableVanished),
+            "test: {:?}",
+            test
+        );
+    }
+
+    // Every residue holds but the modulo is too large for them to be enumerated
+    let quad_eq = QuadEq::<u64> {
+        a: 0,
+        b: 0,
+        c: 1,
+        d: 1,
+        modu: 1 << 40,
+    };
+    assert_eq!(quad_eq.solve(), None);
+    assert_eq!(quad_eq.try_solve(), Err(SolveError::TooManySolutions));
+    assert_eq!(quad_eq.count_solutions(), 1 << 40);
+}
+
+#[test]
+fn try_solve_errors() {
+    let test_cases = [
+        (
+            QuadEqSigned::<i32, u32> {
+                a: 1,
+                b: 1,
+                c: 1,
+                d: -1,
+                modu: 22,
+            },
+            Ok(vec![4, 6, 15, 17]),
+        ),
+        (
+            QuadEqSigned {
+                a: 1,
+                b: 0,
+                c: 0,
+                d: 3,
+                modu: 17,
+            },
+            Err(SolveError::NoSolution),
+        ),
+        (
+            QuadEqSigned {
+                a: 1,
+                b: 0,
+                c: 0,
+                d: 3,
+                modu: 0,
+            },
+            Err(SolveError::InvalidModulus),
+        ),
+        (
+            QuadEqSigned {
+                a: 17,
+                b: -34,
+                c: 0,
+                d: 3,
+                modu: 17,
+            },
+            Err(SolveError::VariableVanished),
+        ),
+        (
+            QuadEqSigned {
+                a: i32::MIN,
+                b: 1,
+                c: 0,
+                d: 3,
+                modu: 17,
+            },
+            Err(SolveError::CastFailure),
+        ),
+        (
+            QuadEqSigned {
+                a: 0,
+                b: 2,
+                c: 0,
+                d: 3,
+                modu: 8,
+            },
+            Err(SolveError::NoSolution),
+        ),
+    ];
+
+    for (quad_eq, corr_res) in test_cases.iter() {
+        assert_eq!(quad_eq.try_solve(), *corr_res, "{:?}", quad_eq);
+    }
+}
+
+#[test]
+fn count_solutions_small_modulo() {
+    for modu in 0..130u32 {
+        for a in [0, 1, 2, 3, 4, 6, 8, 9, 12, 25, 27].iter() {
+            for b in [0, 1, 2, 4, 5, 6, 9].iter() {
+                for d in 0..modu + 1 {
+                    let quad_eq = QuadEq {
+                        a: *a,
+                        b: *b,
+                        c: 1,
+                        d,
+                        modu,
+                    };
+                    let corr_count = (0..modu)
+                        .filter(|&x| (a * x * x + b * x + 1) % modu == d % modu)
+                        .count();
+
+                    assert_eq!(
+                        quad_eq.count_solutions() as usize,
+                        corr_count,
+                        "{:?}",
+                        quad_eq
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn count_solutions_large_modulo() {
+    let quad_eq = QuadEq::<u128> {
+        a: 1,
+        b: 0,
+        c: 0,
+        d: 1,
+        modu: u128::MAX,
+    };
+    assert_eq!(quad_eq.count_solutions(), 512);
+
+    // x^2 = 0 (mod 2^120) holds for multiples of 2^60
+    let quad_eq = QuadEq::<u128> {
+        a: 1,
+        b: 0,
+        c: 0,
+        d: 0,
+        modu: 1 << 120,
+    };
+    assert_eq!(quad_eq.count_solutions(), 1 << 60);
+
+    let quad_eq = QuadEqSigned::<i64, u64> {
+        a: 1,
+        b: 1,
+        c: 1,
+        d: -1,
+        modu: 22,
+    };
+    assert_eq!(quad_eq.count_solutions(), 4);
+
+    let quad_eq = QuadEqSigned::<i64, u64> {
+        a: i64::MIN,
+        b: 1,
+        c: 1,
+        d: -1,
+        modu: 22,
+    };
+    assert_eq!(quad_eq.count_solutions(), 0);
+}
+
+#[test]
+fn count_solutions_matches_solve() {
+    // Coefficient a shares factors with the modulo or vanishes modulo some prime power
+    for modu in 1..50u32 {
+        for a in 0..modu {
+            for b in [0, 1, 2, 3, 6] {
+                for d in 0..modu {
+                    let quad_eq = QuadEq {
+                        a,
+                        b,
+                        c: 0,
+                        d,
+                        modu,
+                    };
+                    let corr_sols: Vec<u32> = (0..modu)
+                        .filter(|&x| (a * x * x + b * x) % modu == d)
+                        .collect();
+                    let sols = quad_eq.solve().unwrap_or_default();
+
+                    assert_eq!(sols, corr_sols, "{:?}", quad_eq);
+                    assert_eq!(quad_eq.count_solutions() as usize, sols.len());
+                }
+            }
+        }
+    }
+
+    // 3x^2 = 0 (mod 6) holds for every even x
+    let quad_eq = QuadEq::<u32> {
+        a: 3,
+        b: 0,
+        c: 0,
+        d: 0,
+        modu: 6,
+    };
+    assert_eq!(quad_eq.solve(), Some(vec![0, 2, 4]));
+    assert_eq!(quad_eq.count_solutions(), 3);
+    assert_eq!(quad_eq.solve_set().map(|sols| sols.len()), Some(3));
+}
+
+#[test]
+fn solve_set_small_modulo() {
+    for modu in 1..130u32 {
+        for a in [0, 1, 2, 3, 4, 6, 8, 9].iter() {
+            for b in [0, 1, 2, 5, 6].iter() {
+                for d in 0..modu {
+                    let quad_eq = QuadEq {
+                        a: *a,
+                        b: *b,
+                        c: 1,
+                        d,
+                        modu,
+                    };
+                    let corr_sols: Vec<u32> = (0..modu)
+                        .filter(|&x| (a * x * x + b * x + 1) % modu == d)
+                        .collect();
+
+                    match quad_eq.solve_set() {
+                        Some(sols) => {
+                            let mut x_sols: Vec<u32> = sols.iter().collect();
+                            x_sols.sort();
+
+                            assert_eq!(x_sols, corr_sols, "{:?}", quad_eq);
+                            assert_eq!(sols.len(), corr_sols.len());
+                            assert_eq!(sols.nth(sols.len()), None);
+                            assert!((0..modu).all(|x| sols.contains(x) == corr_sols.contains(&x)));
+                        }
+                        None => assert!(corr_sols.is_empty(), "{:?}", quad_eq),
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn solve_set_large_modulo() {
+    // Square roots of one modulo primorial 2 * 3 * ... * 101, 2^25 solutions
+    let modu: u128 = [
+        2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61, 67, 71, 73, 79, 83, 89,
+        97, 101,
+    ]
+    .iter()
+    .product();
+
+    let quad_eq = QuadEqSigned::<i128, u128> {
+        a: 1,
+        b: 0,
+        c: -1,
+        d: 0,
+        modu,
+    };
+
+    match quad_eq.solve_set() {
+        Some(sols) => {
+            assert_eq!(sols.len(), 1 << 25);
+            assert_eq!(sols.modu(), modu);
+            assert!(sols.contains(1) && sols.contains(modu - 1) && !sols.contains(2));
+
+            for n in [0, 1, 12_345, (1 << 25) - 1].iter() {
+                match sols.nth(*n) {
+                    Some(x) => assert_eq!(u128::mult_mod(x, x, modu), 1),
+                    None => panic!("no solution of index {}", n),
+                }
+            }
+            assert_eq!(sols.nth(1 << 25), None);
+            assert_eq!(sols.iter().len(), 1 << 25);
+        }
+        None => panic!("no solutions"),
+    }
+}
+
+#[test]
+fn solutions_in_range() {
+    for modu in 1..60u32 {
+        for (a, b) in [(1, 0), (2, 3), (6, 4), (0, 5), (0, 0)].iter() {
+            for d in 0..modu {
+                for (lo, hi) in [(0, 0), (50, 40), (0, 200), (1_000, 1_333)].iter() {
+                    let quad_eq = QuadEq {
+                        a: *a,
+                        b: *b,
+                        c: 0,
+                        d,
+                        modu,
+                    };
+                    let corr_sols: Vec<u32> = quad_eq
+                        .solve()
+                        .map(|sols| (*lo..*hi).filter(|x| sols.contains(&(x % modu))).collect())
+                        .unwrap_or_default();
+
+                    assert_eq!(
+                        quad_eq.solutions_in_range(*lo, *hi),
+                        corr_sols,
+                        "{:?}, lo: {}, hi: {}",
+                        quad_eq,
+                        lo,
+                        hi
+                    );
+                }
+            }
+        }
+    }
+
+    let quad_eq = QuadEqSigned::<i8, u8> {
+        a: 1,
+        b: 0,
+        c: -4,
+        d: 0,
+        modu: 100,
+    };
+    assert_eq!(
+        quad_eq.solutions_in_range(150, u8::MAX),
+        vec![152, 198, 202, 248, 252]
+    );
+}
+
+#[test]
+fn solve_balanced() {
+    let test_cases = [
+        (
+            QuadEq::<u64> {
+                a: 1,
+                b: 0,
+                c: 0,
+                d: 4,
+                modu: 100,
+            },
+            Some(vec![-48, -2, 2, 48]),
+        ),
+        (
+            QuadEq {
+                a: 1,
+                b: 0,
+                c: 0,
+                d: 0,
+                modu: 16,
+            },
+            Some(vec![-4, 0, 4, 8]),
+        ),
+        (
+            QuadEq {
+                a: 1,
+                b: 0,
+                c: 0,
+                d: 3,
+                modu: 17,
+            },
+            None,
+        ),
+    ];
+
+    for (quad_eq, corr_sols) in test_cases.iter() {
+        assert_eq!(quad_eq.solve_balanced::<i64>(), *corr_sols, "{:?}", quad_eq);
+    }
+
+    let quad_eq = QuadEqSigned::<i128, u128> {
+        a: 1,
+        b: 0,
+        c: 0,
+        d: 1,
+        modu: u128::MAX,
+    };
+    match quad_eq.solve_balanced() {
+        Some(sols) => {
+            assert_eq!(sols.len(), 512);
+            assert!(sols.contains(&-1) && sols.contains(&1));
+            assert!(sols.windows(2).all(|w| w[0] < w[1]));
+        }
+        None => panic!("no solutions"),
+    }
+}
+
+#[test]
+fn is_solution() {
+    for modu in 0..50u32 {
+        for (a, b) in [(1, 0), (2, 3), (4, 6), (0, 5)].iter() {
+            for d in 0..modu {
+                let quad_eq = QuadEq {
+                    a: *a,
+                    b: *b,
+                    c: 1,
+                    d,
+                    modu,
+                };
+                let sols = quad_eq.solve().unwrap_or_default();
+
+                for x in 0..2 * modu {
+                    assert_eq!(
+                        quad_eq.is_solution(x),
+                        sols.contains(&(x % modu)),
+                        "{:?}, x: {}",
+                        quad_eq,
+                        x
+                    );
+                }
+            }
+        }
+    }
+
+    let quad_eq = QuadEqSigned::<i128, u128> {
+        a: 1,
+        b: 0,
+        c: 0,
+        d: 1,
+        modu: u128::MAX,
+    };
+    assert!(quad_eq.is_solution(1) && quad_eq.is_solution(-1) && !quad_eq.is_solution(2));
+    assert!(!quad_eq.is_solution(i128::MIN));
+}
+
+#[test]
+fn sides() {
+    let quad_eq = QuadEq::<u32> {
+        a: 1,
+        b: 1,
+        c: 3,
+        d: 11,
+        modu: 41,
+    };
+    assert_eq!(quad_eq.sides(9), Some((11, 11)));
+    assert_eq!(quad_eq.sides(31 + 41), Some((11, 11)));
+    assert_eq!(quad_eq.sides(10), Some((31, 11)));
+    assert_eq!(QuadEq::<u32> { modu: 0, ..quad_eq }.sides(1), None);
+
+    let quad_eq = QuadEqSigned::<i128, u128> {
+        a: 1,
+        b: 0,
+        c: 0,
+        d: -1,
+        modu: u128::MAX,
+    };
+    assert_eq!(quad_eq.sides(-1), Some((1, u128::MAX - 1)));
+    assert_eq!(quad_eq.sides(i128::MIN), None);
+}
+
+#[test]
+fn eq_signed_wider_modulo_type() {
+    let quad_eq = QuadEqSigned::<i16, u128> {
+        a: 1,
+        b: 1,
+        c: 1,
+        d: -1,
+        modu: 22,
+    };
+    assert_eq!(quad_eq.solve(), Some(vec![4, 6, 15, 17]));
+    assert_eq!(quad_eq.solve_balanced(), Some(vec![-7, -5, 4, 6]));
+
+    let quad_eq = QuadEqSigned::<i16, u128> {
+        a: 1,
+        b: 0,
+        c: -9,
+        d: -5,
+        modu: u128::MAX,
+    };
+    match quad_eq.solve() {
+        Some(sols) => {
+            assert_eq!(sols.len(), 512);
+            assert!(sols.iter().all(|&x| u128::mult_mod(x, x, u128::MAX) == 4));
+        }
+        None => panic!("no solutions"),
+    }
+
+    let quad_eq = QuadEqSigned::<i32, u64> {
+        a: i32::MIN,
+        b: 0,
+        c: 0,
+        d: 1,
+        modu: 15,
+    };
+    assert_eq!(quad_eq.try_solve(), Err(SolveError::CastFailure));
+}
+
+#[test]
+fn builder() {
+    match QuadEq::<u64>::builder()
+        .a_signed(-3i64)
+        .b(40)
+        .c_signed(-1i8)
+        .d(100)
+        .modu(17)
+        .build()
+    {
+        Ok(quad_eq) => {
+            assert_eq!(
+                (quad_eq.a, quad_eq.b, quad_eq.c, quad_eq.d, quad_eq.modu),
+                (14, 6, 16, 15, 17)
+            );
+            assert_eq!(
+                quad_eq.solve(),
+                QuadEqSigned::<i64, u64> {
+                    a: -3,
+                    b: 40,
+                    c: -1,
+                    d: 100,
+                    modu: 17
+                }
+                .solve()
+            );
+        }
+        Err(err) => panic!("{}", err),
+    }
+
+    assert_eq!(
+        QuadEq::<u64>::builder().a(1).d(4).build().err(),
+        Some(BuildError::MissingModulus)
+    );
+    assert_eq!(
+        QuadEq::<u64>::builder().a(1).modu(0).build().err(),
+        Some(BuildError::InvalidModulus)
+    );
+    assert_eq!(
+        QuadEq::<u64>::builder()
+            .a_signed(i64::MIN)
+            .d(4)
+            .modu(7)
+            .build()
+            .err(),
+        Some(BuildError::CastFailure)
+    );
+}
@@ -0,0 +1,338 @@
+//! Implements structural queries for the ring of integers Z/nZ.
+//!
+//! Ring Z/nZ decomposes by the Chinese remainder theorem into a product of
+//! rings Z/p_i^k_iZ, where n = p_1^k_1 * ... * p_m^k_m is the prime factor
+//! representation of the modulo n. Many structural properties of the ring
+//! can be read directly from this representation.
+//!
+//! Idempotent elements e (e^2 = e) correspond to choosing either 0 or 1 in
+//! every component ring, hence there are exactly 2^m of them. Nilpotent elements
+//! (x^j = 0 for some j >= 1) form an ideal, the nilradical, which is generated
+//! by the radical rad(n) = p_1 * ... * p_m of the modulo.
+//!
+//! Unit group (Z/nZ)* decomposes similarly to a product of the unit groups
+//! (Z/p_i^k_iZ)*. For an odd prime p these are cyclic and generated by a primitive
+//! root modulo p^k. For powers of two the group is cyclic only for 2 and 4, and
+//! for 2^k, k >= 3, it's the product of cyclic groups generated by -1 and 5.
+//! Roots of unity, solutions of x^k = 1, form a subgroup that is read from these
+//! cyclic components as well.
+//!
+use num::iter;
+
+use crate::{
+    arith::{Arith, CoreArith},
+    numtheory::{prime_factor_repr, totient},
+    UInt,
+};
+
+/// Cyclic component of the unit group (Z/nZ)*.
+///
+/// Component belongs to the prime power factor `prm_power` = `prm`^k of the
+/// modulo and it's generated by `generator` (mod `prm_power`) having
+/// the multiplicative order `order`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CyclicComponent<T: UInt> {
+    pub prm: T,
+    pub prm_power: T,
+    pub generator: T,
+    pub order: T,
+}
+
+/// Iterator over the unit group (Z/nZ)*.
+///
+/// Units are the residue classes having a multiplicative inverse, or
+/// equivalently those represented by integers coprime to the modulo n.
+/// They are produced lazily in increasing order.
+pub struct Units<T: UInt> {
+    modu: T,
+    next: T,
+}
+
+/// Idempotent elements of the ring Z/nZ, `modu` being the modulo n.
+///
+/// Idempotents are the solutions of x^2 = x (mod n) and for a modulo with
+/// m distinct prime factors there are 2^m of them, returned in increasing
+/// order. Modulo must be strictly larger than one, otherwise None is returned.
+///
+/// # Examples
+///
+/// ```
+/// use modular_equations::idempotents;
+///
+/// // 12 = 2^2 * 3, hence four idempotents
+/// assert_eq!(idempotents::<u32>(12), Some(vec![0, 1, 4, 9]));
+/// ```
+pub fn idempotents<T: 'static + UInt>(modu: T) -> Option<Vec<T>> {
+    if modu <= T::one() {
+        return None;
+    }
+
+    let prm_factor_repr = prime_factor_repr(modu);
+
+    // Component idempotents e_i, e_i = 1 (mod p_i^k_i) and e_i = 0 (mod p_j^k_j), j != i
+    let comp_idempotents: Vec<T> = prm_factor_repr
+        .iter()
+        .map(|&(prm, k)| {
+            let prm_power = prm.pow(k.into());
+            let cofactor = modu / prm_power;
+
+            T::mult_mod(cofactor, T::multip_inv(cofactor, prm_power), modu)
+        })
+        .collect();
+
+    let mut idempotents: Vec<T> = (0..1usize << comp_idempotents.len())
+        .map(|mask| {
+            comp_idempotents
+                .iter()
+                .enumerate()
+                .filter(|(j, _)| (mask >> j) & 1 == 1)
+                .fold(T::zero(), |sum, (_, &e)| T::add_mod_unsafe(sum, e, modu))
+        })
+        .collect();
+
+    idempotents.sort_unstable();
+
+    Some(idempotents)
+}
+
+/// Generator of the nilradical of the ring Z/nZ, `modu` being the modulo n.
+///
+/// Nilradical is the ideal of all nilpotent elements and it's generated by
+/// the radical rad(n), the product of distinct prime factors of n. Modulo must
+/// be strictly larger than one, otherwise None is returned.
+///
+/// # Examples
+///
+/// ```
+/// use modular_equations::nilradical;
+///
+/// // rad(72) = rad(2^3 * 3^2) = 6
+/// assert_eq!(nilradical::<u8>(72), Some(6));
+/// ```
+pub fn nilradical<T: 'static + UInt>(modu: T) -> Option<T> {
+    if modu <= T::one() {
+        return None;
+    }
+
+    Some(
+        prime_factor_repr(modu)
+            .iter()
+            .fold(T::one(), |rad, &(prm, _)| rad * prm),
+    )
+}
+
+/// Nilpotent elements of the ring Z/nZ, `modu` being the modulo n.
+///
+/// Nilpotent elements are exactly the multiples of rad(n) and there are
+/// n / rad(n) of them, returned in increasing order. Zero is always nilpotent.
+/// Modulo must be strictly larger than one, otherwise None is returned.
+///
+/// # Examples
+///
+/// ```
+/// use modular_equations::nilpotents;
+///
+/// assert_eq!(nilpotents::<u16>(72), Some(vec![0, 6, 12, 18, 24, 30, 36, 42, 48, 54, 60, 66]));
+/// ```
+pub fn nilpotents<T: 'static + UInt>(modu: T) -> Option<Vec<T>> {
+    let rad = nilradical(modu)?;
+
+    Some(iter::range_step(T::zero(), modu, rad).collect())
+}
+
+/// Units of the ring Z/nZ, `modu` being the modulo n.
+///
+/// Returned iterator produces the units lazily by filtering out residues that are
+/// not coprime to the modulo. Order of the unit group is available without the
+/// enumeration via the `unit_count` method. Modulo one has the single residue
+/// class zero, which is a unit of the zero ring, whereas for modulo zero the
+/// iterator is empty.
+///
+/// # Examples
+///
+/// ```
+/// use modular_equations::units;
+///
+/// let units_mod_12 = units::<u8>(12);
+///
+/// assert_eq!(units_mod_12.unit_count(), 4);
+/// assert_eq!(units_mod_12.collect::<Vec<u8>>(), vec![1, 5, 7, 11]);
+/// ```
+pub fn units<T: 'static + UInt>(modu: T) -> Units<T> {
+    Units {
+        modu,
+        next: T::zero(),
+    }
+}
+
+impl<T: 'static + UInt> Units<T> {
+    /// Order of the unit group, computed by Euler's totient function.
+    ///
+    /// This doesn't consume the iterator nor depend on its state. For
+    /// modulo one the count is one and for modulo zero it's zero.
+    pub fn unit_count(&self) -> T {
+        totient(self.modu)
+    }
+}
+
+impl<T: UInt> Iterator for Units<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        while self.next < self.modu {
+            let cand = self.next;
+            self.next = self.next + T::one();
+
+            // Zero is coprime only to modulo one, in which case it's the sole unit
+            if T::gcd_mod(cand, self.modu) == T::one() {
+                return Some(cand);
+            }
+        }
+
+        None
+    }
+}
+
+/// Decompose the unit group (Z/nZ)* to its cyclic components.
+///
+/// Components are ordered by their prime factors and for modulo 2^k, k >= 3,
+/// there are two components, first generated by -1 and the second by 5. Trivial
+/// components (modulo 2) are left out. Modulo must be strictly larger than one.
+pub fn unit_group_components<T: 'static + UInt>(modu: T) -> Vec<CyclicComponent<T>> {
+    let mut components = vec![];
+
+    for (prm, k) in prime_factor_repr(modu).into_iter() {
+        let prm_power = prm.pow(k.into());
+
+        if prm == 2.into() {
+            match k {
+                1 => (),
+                2 => components.push(CyclicComponent {
+                    prm,
+                    prm_power,
+                    generator: 3.into(),
+                    order: 2.into(),
+                }),
+                _ => {
+                    components.push(CyclicComponent {
+                        prm,
+                        prm_power,
+                        generator: prm_power - T::one(),
+                        order: 2.into(),
+                    });
+                    components.push(CyclicComponent {
+                        prm,
+                        prm_power,
+                        generator: 5.into(),
+                        order: prm_power.unsigned_shr(2),
+                    });
+                }
+            }
+        } else {
+            components.push(CyclicComponent {
+                prm,
+                prm_power,
+                generator: primitive_root_odd_prime_power(prm, k),
+                order: prm_power / prm * (prm - T::one()),
+            });
+        }
+    }
+
+    components
+}
+
+/// Roots of unity modulo `modu`, i.e. solutions of x^`k` = 1 (mod modu).
+///
+/// Roots are units and they form a subgroup of the unit group. In a cyclic component
+/// <g> of order o, the roots are the powers of g^(o/d), d being gcd(k, o), hence
+/// there are d_1 * ... * d_m of them in total. Roots are returned in increasing order.
+/// Modulo must be strictly larger than one and exponent `k` positive, otherwise None
+/// is returned.
+///
+/// # Examples
+///
+/// ```
+/// use modular_equations::roots_of_unity;
+///
+/// assert_eq!(roots_of_unity::<u32>(2, 24), Some(vec![1, 5, 7, 11, 13, 17, 19, 23]));
+/// assert_eq!(roots_of_unity::<u32>(3, 7), Some(vec![1, 2, 4]));
+/// assert_eq!(roots_of_unity::<u32>(3, 5), Some(vec![1]));
+/// ```
+pub fn roots_of_unity<T: 'static + UInt>(k: T, modu: T) -> Option<Vec<T>> {
+    if modu <= T::one() || k == T::zero() {
+        return None;
+    }
+
+    let mut roots = vec![T::one()];
+
+    for comp in unit_group_components(modu).iter() {
+        let d = T::gcd_mod(k, comp.order);
+        let h = T::exp_mod(lift_generator(comp, modu), comp.order / d, modu);
+
+        let mut comp_roots = Vec::with_capacity(roots.len());
+        let mut h_power = T::one();
+
+        for _ in iter::range(T::zero(), d) {
+            comp_roots.extend(roots.iter().map(|&x| T::mult_mod(x, h_power, modu)));
+            h_power = T::mult_mod(h_power, h, modu);
+        }
+
+        roots = comp_roots;
+    }
+
+    roots.sort_unstable();
+
+    Some(roots)
+}
+
+/// Lift the generator of a cyclic component to the residue class modulo `modu`.
+pub fn lift_generator<T: UInt>(comp: &CyclicComponent<T>, modu: T) -> T {
+    let cofactor = modu / comp.prm_power;
+
+    if cofactor == T::one() {
+        return comp.generator;
+    }
+
+    // x = 1 (mod cofactor) and x = g (mod prm_power)
+    let inv = T::multip_inv(cofactor % comp.prm_power, comp.prm_power);
+    let t = T::mult_mod(comp.generator - T::one(), inv, comp.prm_power);
+
+    T::add_mod(T::one(), T::mult_mod(cofactor, t, modu), modu)
+}
+
+/// Primitive root modulo `prm`^`k`, where `prm` is an odd prime.
+///
+/// Smallest primitive root g modulo the prime is searched first. If g^(prm-1)
+/// differs from one modulo prm^2, g is a primitive root modulo every power
+/// of the prime, otherwise g + prm is.
+pub fn primitive_root_odd_prime_power<T: 'static + UInt>(prm: T, k: u8) -> T {
+    let prm_ev = prm - T::one();
+
+    let prm_ev_factors: Vec<T> = prime_factor_repr(prm_ev)
+        .into_iter()
+        .map(|(q, _)| q)
+        .collect();
+
+    let g = iter::range(2.into(), prm)
+        .find(|&g| {
+            prm_ev_factors
+                .iter()
+                .all(|&q| T::exp_mod_unsafe(g, prm_ev / q, prm) != T::one())
+        })
+        .unwrap_or_else(T::one);
+
+    if k < 2 {
+        return g;
+    }
+
+    let prm_square = prm * prm;
+
+    if T::exp_mod_unsafe(g, prm_ev, prm_square) == T::one() {
+        g + prm
+    } else {
+        g
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,267 @@
+use crate::arith::Arith;
+use crate::ring::{
+    idempotents, nilpotents, nilradical, primitive_root_odd_prime_power, roots_of_unity,
+    unit_group_components, units, CyclicComponent,
+};
+
+#[test]
+fn idempotents_small_type() {
+    let test_cases: [(u8, &[u8]); 6] = [
+        (2, &[0, 1]),
+        (12, &[0, 1, 4, 9]),
+        (30, &[0, 1, 6, 10, 15, 16, 21, 25]),
+        (49, &[0, 1]),
+        (100, &[0, 1, 25, 76]),
+        (u8::MAX, &[0, 1, 51, 85, 120, 136, 171, 205]),
+    ];
+
+    for (modu, corr_idempotents) in test_cases.iter() {
+        assert_eq!(
+            idempotents(*modu),
+            Some(corr_idempotents.to_vec()),
+            "modu: {}",
+            modu
+        );
+    }
+}
+
+#[test]
+fn idempotents_brute_force_small_moduli() {
+    for modu in 2..500u32 {
+        let corr_idempotents: Vec<u32> = (0..modu).filter(|x| (x * x) % modu == *x).collect();
+
+        assert_eq!(idempotents(modu), Some(corr_idempotents), "modu: {}", modu);
+    }
+}
+
+#[test]
+fn idempotents_large_type() {
+    let modu: u128 = 340_282_366_920_938_463_463_374_607_431_768_211_455; // u128::MAX
+
+    match idempotents(modu) {
+        Some(idem) => {
+            // u128::MAX has nine distinct prime factors
+            assert_eq!(idem.len(), 512);
+
+            for e in idem.iter() {
+                assert_eq!(u128::mult_mod(*e, *e, modu), *e, "e: {}", e);
+            }
+        }
+        None => panic!("modu: {}, idempotents: None", modu),
+    }
+}
+
+#[test]
+fn idempotents_invalid_modulo() {
+    assert_eq!(idempotents(0u32), None);
+    assert_eq!(idempotents(1u32), None);
+}
+
+#[test]
+fn nilradical_generators() {
+    // [modu, rad(modu)]
+    let test_cases: [[u64; 2]; 7] = [
+        [2, 2],
+        [8, 2],
+        [72, 6],
+        [1_024, 2],
+        [3_675, 105],
+        [1_000_000_007, 1_000_000_007],
+        [2_u64.pow(10) * 3_u64.pow(7) * 11_u64.pow(3), 66],
+    ];
+
+    for test in test_cases.iter() {
+        assert_eq!(nilradical(test[0]), Some(test[1]), "modu: {}", test[0]);
+    }
+
+    assert_eq!(nilradical(1u64), None);
+}
+
+#[test]
+fn nilpotents_brute_force_small_moduli() {
+    for modu in 2..300u32 {
+        let corr_nilpotents: Vec<u32> = (0..modu)
+            .filter(|&x| {
+                let mut y = x;
+                // Exponents of modu < 300 are at most 8
+                for _ in 0..8 {
+                    y = (y * x) % modu;
+                }
+                y == 0
+            })
+            .collect();
+
+        assert_eq!(nilpotents(modu), Some(corr_nilpotents), "modu: {}", modu);
+    }
+}
+
+#[test]
+fn units_small_type() {
+    let test_cases: [(u8, &[u8]); 5] = [
+        (2, &[1]),
+        (9, &[1, 2, 4, 5, 7, 8]),
+        (12, &[1, 5, 7, 11]),
+        (13, &[1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12]),
+        (30, &[1, 7, 11, 13, 17, 19, 23, 29]),
+    ];
+
+    for (modu, corr_units) in test_cases.iter() {
+        let units_iter = units(*modu);
+
+        assert_eq!(units_iter.unit_count() as usize, corr_units.len());
+        assert_eq!(units_iter.collect::<Vec<u8>>(), corr_units.to_vec());
+    }
+}
+
+#[test]
+fn units_count_matches_enumeration() {
+    for modu in [u8::MAX, 128, 210, 251].iter() {
+        let units_iter = units(*modu);
+        let count = units_iter.unit_count();
+
+        assert_eq!(units_iter.count(), count as usize, "modu: {}", modu);
+    }
+}
+
+#[test]
+fn units_are_invertible() {
+    let modu = 1_000u16;
+
+    for x in units(modu) {
+        let inv = u16::multip_inv(x, modu);
+        assert_eq!(u16::mult_mod(x, inv, modu), 1, "x: {}", x);
+    }
+}
+
+#[test]
+fn units_large_type_lazily() {
+    let modu = u128::MAX;
+    let first_units: Vec<u128> = units(modu).take(5).collect();
+
+    // 2^128 - 1 is divisible by 3 and 5
+    assert_eq!(first_units, vec![1, 2, 4, 7, 8]);
+    assert_eq!(
+        units(modu).unit_count(),
+        169_875_107_699_410_294_159_549_716_941_399_654_400
+    );
+}
+
+#[test]
+fn units_invalid_modulo() {
+    assert_eq!(units(0u32).unit_count(), 0);
+    assert_eq!(units(0u32).count(), 0);
+}
+
+#[test]
+fn units_modulo_one() {
+    assert_eq!(units(1u32).unit_count(), 1);
+    assert_eq!(units(1u32).collect::<Vec<u32>>(), vec![0]);
+}
+
+#[test]
+fn primitive_roots_odd_prime_powers() {
+    // [prm, k, primitive root]
+    let test_cases: [[u32; 3]; 7] = [
+        [3, 1, 2],
+        [3, 4, 2],
+        [7, 1, 3],
+        [7, 2, 3],
+        [29, 3, 2],
+        // 5 is the smallest primitive root modulo 40487 but 5^40486 = 1 (mod 40487^2)
+        [40_487, 1, 5],
+        [40_487, 2, 40_492],
+    ];
+
+    for test in test_cases.iter() {
+        assert_eq!(
+            primitive_root_odd_prime_power(test[0], test[1] as u8),
+            test[2],
+            "prm: {}, k: {}",
+            test[0],
+            test[1]
+        );
+    }
+}
+
+#[test]
+fn unit_group_components_powers_of_two() {
+    assert_eq!(unit_group_components(2u16), vec![]);
+    assert_eq!(
+        unit_group_components(4u16),
+        vec![CyclicComponent {
+            prm: 2,
+            prm_power: 4,
+            generator: 3,
+            order: 2
+        }]
+    );
+    assert_eq!(
+        unit_group_components(64u16),
+        vec![
+            CyclicComponent {
+                prm: 2,
+                prm_power: 64,
+                generator: 63,
+                order: 2
+            },
+            CyclicComponent {
+                prm: 2,
+                prm_power: 64,
+                generator: 5,
+                order: 16
+            }
+        ]
+    );
+}
+
+#[test]
+fn unit_group_components_orders_multiply_to_unit_count() {
+    for modu in 2..1_000u32 {
+        let order_product = unit_group_components(modu)
+            .iter()
+            .fold(1, |prod, comp| prod * comp.order);
+
+        assert_eq!(order_product, units(modu).unit_count(), "modu: {}", modu);
+    }
+}
+
+#[test]
+fn roots_of_unity_brute_force_small_moduli() {
+    for modu in 2..300u32 {
+        for k in [1, 2, 3, 4, 6, 12, 300].iter() {
+            let corr_roots: Vec<u32> = (0..modu)
+                .filter(|&x| u32::exp_mod(x, *k, modu) == 1)
+                .collect();
+
+            assert_eq!(
+                roots_of_unity(*k, modu),
+                Some(corr_roots),
+                "k: {}, modu: {}",
+                k,
+                modu
+            );
+        }
+    }
+}
+
+#[test]
+fn roots_of_unity_large_type() {
+    // 2^128 - 159 is a prime and 2^5 is the largest power of two dividing p - 1
+    let prm = u128::MAX - 158;
+
+    assert_eq!(roots_of_unity(2, prm), Some(vec![1, prm - 1]));
+    assert_eq!(roots_of_unity(64, prm).map(|roots| roots.len()), Some(32));
+
+    let modu = 1u128 << 100;
+    let roots = roots_of_unity(8, modu).unwrap();
+
+    assert_eq!(roots.len(), 16);
+    assert!(roots.iter().all(|&x| u128::exp_mod(x, 8, modu) == 1));
+}
+
+#[test]
+fn roots_of_unity_invalid_arguments() {
+    assert_eq!(roots_of_unity(2u32, 0), None);
+    assert_eq!(roots_of_unity(2u32, 1), None);
+    assert_eq!(roots_of_unity(0u32, 7), None);
+}
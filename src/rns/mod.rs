@@ -0,0 +1,181 @@
+//! Implements solving of quadratic equations over moduli exceeding u128.
+//!
+//! Modulo n = p_1^k_1 * ... * p_m^k_m is given directly by its prime power factors,
+//! each of which must fit into u64. Equation is then solved separately modulo every
+//! prime power with native integer arithmetic and no factorization of n is needed.
+//!
+//! Solutions are kept in the residue number system (RNS), i.e. as tuples of residues
+//! modulo the prime powers. Conversion to the mixed radix representation uses Garner's
+//! algorithm with u64 arithmetic only and the actual solution as a big integer is
+//! obtained from the mixed radix digits by Horner's rule.
+//!
+use num::{BigUint, ToPrimitive, Zero};
+
+use crate::{crt, prime, quad::QuadEq, utils};
+
+/// Type for quadratic equations with modulo given by its prime power factors.
+///
+/// Quadratic modular equations are of the form ax^2 + bx + c = d (mod modu) where
+/// modu = p_1^k_1 * ... * p_m^k_m is given as `modu_factors` \[(p_1,k_1), ..., (p_m,k_m)\].
+/// Primes must be distinct and every prime power p_i^k_i must fit into u64. Coefficients
+/// `a`, `b`, `c` and `d` are nonnegative big integers.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct QuadEqRns {
+    pub a: BigUint,
+    pub b: BigUint,
+    pub c: BigUint,
+    pub d: BigUint,
+    pub modu_factors: Vec<(u64, u8)>,
+}
+
+/// Solution in the residue number system.
+///
+/// Solution x is represented by its residues x (mod m_i) for pairwise
+/// coprime moduli m_i, these being the prime powers of the modulo. Solutions are
+/// created only by `QuadEqRns::solve`, thus the moduli are always pairwise coprime.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RnsSolution {
+    residues: Vec<u64>,
+    moduli: Vec<u64>,
+}
+
+impl QuadEqRns {
+    /// Solve quadratic modular equation ax^2 + bx + c = d (mod modu).
+    ///
+    /// Solutions are returned in increasing order. If the modulo factors are invalid
+    /// (the modulo must be strictly larger than one) or there aren't solutions,
+    /// None is returned. As for `QuadEq`, if the unknown vanishes modulo a prime power
+    /// every residue is enumerated only for prime powers up to 2^20 and for larger ones
+    /// None is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use modular_equations::QuadEqRns;
+    /// use num::BigUint;
+    ///
+    /// // Modulo (2^64 - 59) * (2^64 - 83) * (2^61 - 1) exceeds u128
+    /// let quad_eq = QuadEqRns {
+    ///     a: BigUint::from(1u8),
+    ///     b: BigUint::from(0u8),
+    ///     c: BigUint::from(0u8),
+    ///     d: BigUint::from(4u8),
+    ///     modu_factors: vec![
+    ///         (18_446_744_073_709_551_557, 1),
+    ///         (18_446_744_073_709_551_533, 1),
+    ///         (2_305_843_009_213_693_951, 1),
+    ///     ],
+    /// };
+    ///
+    /// let sols = quad_eq.solve().unwrap();
+    ///
+    /// assert_eq!(sols.len(), 8);
+    /// assert_eq!(sols[0].to_decimal_string(), "2");
+    /// ```
+    pub fn solve(&self) -> Option<Vec<RnsSolution>> {
+        let moduli = self.prime_power_moduli()?;
+
+        let mut component_sols: Vec<Vec<u64>> = Vec::with_capacity(moduli.len());
+
+        for &modu in moduli.iter() {
+            let quad = QuadEq {
+                a: reduce(&self.a, modu),
+                b: reduce(&self.b, modu),
+                c: reduce(&self.c, modu),
+                d: reduce(&self.d, modu),
+                modu,
+            };
+
+            component_sols.push(quad.solve()?);
+        }
+
+        let sol_counts: Vec<usize> = component_sols.iter().map(|sols| sols.len()).collect();
+
+        let mut sols: Vec<(BigUint, RnsSolution)> = utils::make_index_combinations(&sol_counts)?
+            .into_iter()
+            .map(|comb| {
+                let residues: Vec<u64> = comb
+                    .iter()
+                    .zip(component_sols.iter())
+                    .map(|(&idx, sols)| sols[idx])
+                    .collect();
+
+                let sol = RnsSolution {
+                    residues,
+                    moduli: moduli.clone(),
+                };
+
+                (sol.to_biguint(), sol)
+            })
+            .collect();
+
+        sols.sort_unstable_by(|x, y| x.0.cmp(&y.0));
+
+        Some(sols.into_iter().map(|(_, sol)| sol).collect())
+    }
+
+    /// Prime powers of the modulo or None if the factors are invalid.
+    fn prime_power_moduli(&self) -> Option<Vec<u64>> {
+        if self.modu_factors.is_empty() {
+            return None;
+        }
+
+        let mut moduli = Vec::with_capacity(self.modu_factors.len());
+
+        for (j, &(prm, k)) in self.modu_factors.iter().enumerate() {
+            if k == 0 || (prm != 2 && !prime::is_odd_prime(prm)) {
+                return None;
+            }
+            if self.modu_factors[..j].iter().any(|&(q, _)| q == prm) {
+                return None;
+            }
+
+            moduli.push(prm.checked_pow(k.into())?);
+        }
+
+        Some(moduli)
+    }
+}
+
+impl RnsSolution {
+    /// Residues x (mod m_i) of the solution.
+    pub fn residues(&self) -> &[u64] {
+        &self.residues
+    }
+
+    /// Pairwise coprime moduli m_i of the residues.
+    pub fn moduli(&self) -> &[u64] {
+        &self.moduli
+    }
+
+    /// Mixed radix digits v_i of the solution,
+    /// x = v_1 + v_2 * m_1 + ... + v_k * m_1 * ... * m_(k-1).
+    pub fn mixed_radix_digits(&self) -> Vec<u64> {
+        crt::mixed_radix_digits(&self.residues, &self.moduli)
+            .expect("moduli of a solution are pairwise coprime")
+    }
+
+    /// Solution as a big integer, reduced modulo the product of the moduli.
+    pub fn to_biguint(&self) -> BigUint {
+        self.mixed_radix_digits()
+            .iter()
+            .zip(self.moduli.iter())
+            .rev()
+            .fold(BigUint::zero(), |x, (&v, &m)| x * m + v)
+    }
+
+    /// Solution as a decimal string.
+    pub fn to_decimal_string(&self) -> String {
+        self.to_biguint().to_str_radix(10)
+    }
+}
+
+/// Reduce big integer `x` modulo `modu`.
+fn reduce(x: &BigUint, modu: u64) -> u64 {
+    (x % modu)
+        .to_u64()
+        .expect("residue is smaller than the modulo")
+}
+
+#[cfg(test)]
+mod tests;
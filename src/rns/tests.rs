@@ -0,0 +1,163 @@
+use num::BigUint;
+
+use crate::quad::QuadEq;
+use crate::rns::{QuadEqRns, RnsSolution};
+
+fn big(x: u128) -> BigUint {
+    BigUint::from(x)
+}
+
+#[test]
+fn solutions_match_native_solver() {
+    let modu_factors: [&[(u64, u8)]; 4] = [
+        &[(3, 1), (5, 1)],
+        &[(2, 3), (7, 2)],
+        &[(2, 1), (3, 2), (5, 1), (11, 1)],
+        &[(1_000_000_007, 1), (998_244_353, 1)],
+    ];
+
+    // [a, b, c, d]
+    let coefs: [[u128; 4]; 3] = [[1, 0, 0, 4], [3, 5, 1, 7], [2, 0, 3, 11]];
+
+    for factors in modu_factors.iter() {
+        let modu: u128 = factors
+            .iter()
+            .map(|&(p, k)| (p as u128).pow(k.into()))
+            .product();
+
+        for coef in coefs.iter() {
+            let quad_eq = QuadEq {
+                a: coef[0],
+                b: coef[1],
+                c: coef[2],
+                d: coef[3],
+                modu,
+            };
+            let quad_eq_rns = QuadEqRns {
+                a: big(coef[0]),
+                b: big(coef[1]),
+                c: big(coef[2]),
+                d: big(coef[3]),
+                modu_factors: factors.to_vec(),
+            };
+
+            let sols: Option<Vec<BigUint>> = quad_eq_rns
+                .solve()
+                .map(|sols| sols.iter().map(|sol| sol.to_biguint()).collect());
+
+            let corr_sols: Option<Vec<BigUint>> = quad_eq
+                .solve()
+                .map(|sols| sols.into_iter().map(big).collect());
+
+            assert_eq!(sols, corr_sols, "modu: {}, coef: {:?}", modu, coef);
+        }
+    }
+}
+
+#[test]
+fn modulo_exceeding_u128() {
+    let quad_eq = QuadEqRns {
+        a: big(1),
+        b: big(0),
+        c: big(0),
+        d: big(4),
+        modu_factors: vec![
+            (18_446_744_073_709_551_557, 1),
+            (18_446_744_073_709_551_533, 1),
+            (2_305_843_009_213_693_951, 1),
+        ],
+    };
+
+    match quad_eq.solve() {
+        Some(sols) => {
+            assert_eq!(sols.len(), 8);
+            assert_eq!(sols[0].to_decimal_string(), "2");
+            assert_eq!(
+                sols[1].to_decimal_string(),
+                "129029313449615103541510112063977190633704727830193575685"
+            );
+            assert_eq!(
+                sols[7].to_decimal_string(),
+                "784637716923335089099179298133362125985671415798565956829"
+            );
+        }
+        None => panic!("sols: None"),
+    }
+}
+
+#[test]
+fn mixed_radix_digits_of_solution() {
+    let sol = RnsSolution {
+        residues: vec![2, 3, 2],
+        moduli: vec![3, 5, 7],
+    };
+
+    // 23 = 2 + 2 * 3 + 1 * 15
+    assert_eq!(sol.mixed_radix_digits(), vec![2, 2, 1]);
+    assert_eq!(sol.to_biguint(), big(23));
+}
+
+#[test]
+fn invalid_modulo_factors() {
+    let factors: [&[(u64, u8)]; 5] = [&[], &[(4, 1)], &[(3, 1), (3, 2)], &[(5, 0)], &[(3, 41)]];
+
+    for modu_factors in factors.iter() {
+        let quad_eq = QuadEqRns {
+            a: big(1),
+            b: big(0),
+            c: big(0),
+            d: big(1),
+            modu_factors: modu_factors.to_vec(),
+        };
+
+        assert_eq!(quad_eq.solve(), None, "factors: {:?}", modu_factors);
+    }
+}
+
+#[test]
+fn no_solution_in_one_component() {
+    // 3 is not a quadratic residue modulo 7
+    let quad_eq = QuadEqRns {
+        a: big(1),
+        b: big(0),
+        c: big(0),
+        d: big(3),
+        modu_factors: vec![(11, 1), (7, 1)],
+    };
+
+    assert_eq!(quad_eq.solve(), None);
+}
+
+#[test]
+fn unknown_vanishes_in_one_component() {
+    // 15x^2 + 15x + 1 = 1 holds for every x modulo 3 and 5, 15 being a unit modulo 7
+    let quad_eq = QuadEqRns {
+        a: big(15),
+        b: big(15),
+        c: big(1),
+        d: big(1),
+        modu_factors: vec![(3, 1), (5, 1), (7, 1)],
+    };
+
+    match quad_eq.solve() {
+        Some(sols) => {
+            assert_eq!(sols.len(), 30);
+            assert!(sols.iter().all(|sol| sol.moduli() == [3, 5, 7]));
+            assert!(sols
+                .iter()
+                .all(|sol| sol.residues()[2] == 0 || sol.residues()[2] == 6));
+        }
+        None => panic!("sols: None"),
+    }
+
+    // Every residue modulo 2^61 - 1 is a solution, too many to be enumerated
+    let quad_eq = QuadEqRns {
+        a: big(0),
+        b: big(0),
+        c: big(1),
+        d: big(1),
+        modu_factors: vec![(2_305_843_009_213_693_951, 1), (7, 1)],
+    };
+
+    assert_eq!(quad_eq.solve(), None);
+}
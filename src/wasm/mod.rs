@@ -0,0 +1,98 @@
+//! Implements the WebAssembly configuration of the crate.
+//!
+//! Module is compiled only with the feature `wasm`. Within this configuration
+//! factorization runs single-threaded on the calling thread, as threads aren't
+//! available in browsers, and on target wasm32 its clock is read from JavaScript.
+//!
+//! Functions of this module are exported with `wasm-bindgen` and thus restricted to
+//! types that cross the JavaScript boundary: coefficients are i64 and moduli u64
+//! (BigInt in JavaScript) and the solutions are returned as a BigUint64Array, which
+//! is empty if there are no solutions.
+//!
+use wasm_bindgen::prelude::wasm_bindgen;
+
+use crate::{LinEqSigned, QuadEqSigned};
+
+/// Solve linear modular equation ax + b = c (mod modu).
+///
+/// Solutions are the smallest nonnegative representatives of their residue classes
+/// in increasing order. Empty vector is returned if there are no solutions or the
+/// modulo is smaller than two.
+///
+/// # Examples
+///
+/// ```
+/// use modular_equations::solve_linear;
+///
+/// assert_eq!(solve_linear(3, 3, 1, 1223), vec![407]);
+/// assert!(solve_linear(2, 0, 1, 4).is_empty());
+/// ```
+#[wasm_bindgen]
+pub fn solve_linear(a: i64, b: i64, c: i64, modu: u64) -> Vec<u64> {
+    if modu < 2 {
+        return vec![];
+    }
+
+    LinEqSigned { a, b, c, modu }.solve().unwrap_or_default()
+}
+
+/// Solve quadratic modular equation ax^2 + bx + c = d (mod modu).
+///
+/// Solutions are the smallest nonnegative representatives of their residue classes
+/// in increasing order. Empty vector is returned if there are no solutions or the
+/// modulo is smaller than two. Composite modulo is factorized single-threaded.
+///
+/// # Examples
+///
+/// ```
+/// use modular_equations::solve_quadratic;
+///
+/// assert_eq!(solve_quadratic(1, 0, 0, 1, 35), vec![1, 6, 29, 34]);
+/// assert!(solve_quadratic(1, 0, 0, 3, 8).is_empty());
+/// ```
+#[wasm_bindgen]
+pub fn solve_quadratic(a: i64, b: i64, c: i64, d: i64, modu: u64) -> Vec<u64> {
+    if modu < 2 {
+        return vec![];
+    }
+
+    QuadEqSigned { a, b, c, d, modu }
+        .solve()
+        .unwrap_or_default()
+}
+
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_namespace = Date, js_name = now)]
+    fn date_now() -> f64;
+}
+
+/// Point of time read from `Date.now()` of JavaScript, as `std::time::Instant` panics
+/// on target wasm32-unknown-unknown.
+#[cfg(target_arch = "wasm32")]
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub(crate) struct Instant(f64);
+
+#[cfg(target_arch = "wasm32")]
+impl Instant {
+    pub(crate) fn now() -> Self {
+        Self(date_now())
+    }
+
+    pub(crate) fn elapsed(&self) -> std::time::Duration {
+        std::time::Duration::from_secs_f64((date_now() - self.0).max(0.0) / 1000.0)
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+impl std::ops::Add<std::time::Duration> for Instant {
+    type Output = Self;
+
+    fn add(self, duration: std::time::Duration) -> Self {
+        Self(self.0 + duration.as_secs_f64() * 1000.0)
+    }
+}
+
+#[cfg(test)]
+mod tests;
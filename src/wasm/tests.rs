@@ -0,0 +1,35 @@
+use crate::wasm::{solve_linear, solve_quadratic};
+use crate::{FactorConfig, Factors};
+
+#[test]
+fn solve_linear_wrapper() {
+    assert_eq!(solve_linear(3, 3, 1, 1223), vec![407]);
+    assert_eq!(solve_linear(-2, 0, 4, 8), vec![2, 6]);
+    assert!(solve_linear(2, 0, 1, 4).is_empty());
+    assert!(solve_linear(1, 0, 0, 1).is_empty());
+    assert!(solve_linear(i64::MIN, 0, 0, 7).is_empty());
+}
+
+#[test]
+fn solve_quadratic_wrapper() {
+    assert_eq!(solve_quadratic(1, 0, 0, 1, 35), vec![1, 6, 29, 34]);
+    assert_eq!(solve_quadratic(1, 1, 3, 11, 41), vec![9, 31]);
+    assert!(solve_quadratic(1, 0, 0, 3, 8).is_empty());
+    assert!(solve_quadratic(1, 0, 0, 1, 0).is_empty());
+
+    // Square roots of unity modulo 1_000_000_007 * 4_294_967_311
+    let sols = solve_quadratic(1, 0, 0, 1, 4_294_967_341_064_771_177);
+    assert_eq!(sols.len(), 4);
+}
+
+#[test]
+fn factorize_single_threaded() {
+    // Factors are found by the elliptic curve method
+    let mut factors = Factors::new(1_099_511_627_791u128 * 2_199_023_255_579);
+    let stats = factors
+        .factorize_with_stats(&FactorConfig::default())
+        .unwrap();
+
+    assert_eq!(factors.factors, vec![1_099_511_627_791, 2_199_023_255_579]);
+    assert_eq!(stats.worker_threads, 1);
+}
@@ -0,0 +1,148 @@
+//! Integration tests.
+//!
+//! Tests for the command line interface, running the binary.
+//!
+use std::process::{Command, Output};
+
+fn run(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_modular_equations"))
+        .args(args)
+        .output()
+        .expect("binary should run")
+}
+
+#[test]
+fn inverse_small_modulo() {
+    let output = run(&["inverse", "-3", "7"]);
+
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "Inverse of 4 in Z/7Z: 2\n"
+    );
+}
+
+#[test]
+fn inverse_modulo_above_i128_max() {
+    let output = run(&["inverse", "2", "340282366920938463463374607431768211455"]);
+
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "Inverse of 2 in Z/340282366920938463463374607431768211455Z: \
+         170141183460469231731687303715884105728\n"
+    );
+
+    // -1 = u128::MAX - 1 is its own inverse
+    let output = run(&["inverse", "-1", "340282366920938463463374607431768211455"]);
+
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "Inverse of 340282366920938463463374607431768211454 in \
+         Z/340282366920938463463374607431768211455Z: 340282366920938463463374607431768211454\n"
+    );
+}
+
+#[test]
+fn solve_higher_degree_equations() {
+    // x^4 = 1 (mod 65) given by seven coefficients
+    let output = run(&["--count-only", "solve", "1", "0", "0", "0", "0", "1", "65"]);
+
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "Count of solutions x in Z/65Z: 16\n"
+    );
+
+    let output = run(&["--json", "x^3 + 1 = 0 (mod 9)"]);
+
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "{\"modulus\": \"9\", \"solutions\": [\"2\", \"5\", \"8\"], \"count\": 3}\n"
+    );
+
+    // Zero leading coefficients lower the degree down to a linear equation
+    let output = run(&["0", "0", "0", "3", "1", "7", "11"]);
+
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "Solutions x in Z/11Z\nx_1: 2\n"
+    );
+}
+
+#[test]
+fn timeout_of_cubic_equation() {
+    // Modulo (2^64 - 59) * (2^61 - 1) cannot be factorized instantly
+    let output = run(&[
+        "--timeout",
+        "0",
+        "solve",
+        "1",
+        "0",
+        "0",
+        "1",
+        "0",
+        "42535295865117307778430344311653531707",
+    ]);
+
+    assert_eq!(output.status.code(), Some(3));
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "undecided (timeout)\n"
+    );
+}
+
+#[test]
+fn timeout_of_order_and_dlog() {
+    let modu = "42535295865117307778430344311653531707";
+
+    for args in [
+        ["--timeout", "0", "order", "3", modu].as_slice(),
+        ["--timeout", "0", "dlog", "3", "9", modu].as_slice(),
+    ] {
+        let output = run(args);
+
+        assert_eq!(output.status.code(), Some(3));
+        assert_eq!(
+            String::from_utf8_lossy(&output.stdout),
+            "undecided (timeout)\n"
+        );
+    }
+}
+
+#[test]
+fn crt_modulo_zero_is_invalid() {
+    let output = run(&["--json", "crt", "2", "3", "1", "0"]);
+
+    assert_eq!(output.status.code(), Some(2));
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "{\"error\": \"invalid_value\", \"arg\": \"<M>\", \"value\": \"0\"}\n"
+    );
+
+    let output = run(&["crt", "2", "3", "3", "5"]);
+
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "x = 8 (mod 15)\n");
+}
+
+#[test]
+fn workers_below_two_are_invalid() {
+    for option in ["--workers", "--threads"] {
+        let output = run(&["--json", option, "1", "factor", "91"]);
+
+        assert_eq!(output.status.code(), Some(2));
+        assert_eq!(
+            String::from_utf8_lossy(&output.stdout),
+            "{\"error\": \"invalid_option\", \"option\": \"--workers\", \"value\": \"1\"}\n"
+        );
+    }
+
+    let output = run(&["--threads", "2", "factor", "91"]);
+
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "91 = 7 * 13\n");
+}
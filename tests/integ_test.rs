@@ -2,7 +2,9 @@
 //!
 //! Tests for linear and quadratic equations.
 //!
-use modular_equations::{LinEq, LinEqSigned, QuadEq, QuadEqSigned};
+use std::collections::HashMap;
+
+use modular_equations::{LinEq, LinEqSigned, ModularEquation, QuadEq, QuadEqSigned};
 
 #[test]
 fn linear_equation() {
@@ -343,3 +345,90 @@ fn quadratic_signed_equation_readme() {
         assert!(false);
     }
 }
+
+#[test]
+fn heterogeneous_equations() {
+    let eqs: Vec<Box<dyn ModularEquation<u64>>> = vec![
+        Box::new(LinEq {
+            a: 6,
+            b: 1,
+            c: 7,
+            modu: 9,
+        }),
+        Box::new(QuadEq {
+            a: 1,
+            b: 1,
+            c: 1,
+            d: 21,
+            modu: 22,
+        }),
+        Box::new(QuadEq {
+            a: 1,
+            b: 0,
+            c: 0,
+            d: 3,
+            modu: 17,
+        }),
+    ];
+
+    let corr_sols = [Some(vec![1, 4, 7]), Some(vec![4, 6, 15, 17]), None];
+
+    for (eq, corr_sol) in eqs.iter().zip(corr_sols.iter()) {
+        let sols = eq.solve();
+
+        assert_eq!(sols, *corr_sol);
+        assert_eq!(
+            eq.count_solutions() as usize,
+            sols.as_ref().map_or(0, |x| x.len())
+        );
+        assert!(sols.unwrap_or_default().iter().all(|&x| eq.is_solution(x)));
+    }
+}
+
+#[test]
+fn equations_as_hash_map_keys() {
+    let eqs = [
+        QuadEq::<u32> {
+            a: 1,
+            b: 0,
+            c: 0,
+            d: 4,
+            modu: 15,
+        },
+        QuadEq {
+            a: 2,
+            b: 5,
+            c: 3,
+            d: 0,
+            modu: 7,
+        },
+        QuadEq {
+            a: 1,
+            b: 0,
+            c: 0,
+            d: 4,
+            modu: 15,
+        },
+    ];
+
+    let mut sols = HashMap::new();
+
+    for eq in eqs {
+        sols.entry(eq).or_insert_with(|| eq.solve());
+    }
+
+    assert_eq!(sols.len(), 2);
+    assert_eq!(sols[&eqs[0]], Some(vec![2, 7, 8, 13]));
+    assert_eq!(sols[&eqs[1]], Some(vec![2, 6]));
+
+    let lin_eq = LinEqSigned::<i32, u32> {
+        a: -3,
+        b: 1,
+        c: 7,
+        modu: 10,
+    };
+    let lin_eq_copy = lin_eq;
+
+    assert_eq!(lin_eq, lin_eq_copy);
+    assert_eq!(lin_eq.solve(), lin_eq_copy.solve());
+}